@@ -0,0 +1,99 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use html_tag::escape::{escape_attribute, escape_text};
+use html_tag::{HtmlTag, Node};
+
+/// The recursive, `format!` based, renderer that `to_html` used to be,
+/// kept around to compare the streaming renderer against.
+fn legacy_to_html(tag: &HtmlTag) -> String {
+    let mut html = format!("<{}", tag.tag_type.html());
+    if let Some(id) = &tag.id {
+        html.push_str(&format!(" id=\"{}\"", escape_attribute(id)));
+    }
+    if !tag.class_names.is_empty() {
+        html.push_str(&format!(
+            " class=\"{}\"",
+            escape_attribute(&tag.class_names.to_string())
+        ));
+    }
+    if !tag.inline_styles.is_empty() {
+        let styles = tag
+            .inline_styles
+            .iter()
+            .map(|(property, value)| format!("{}: {};", property, value))
+            .collect::<String>();
+        html.push_str(&format!(" style=\"{}\"", escape_attribute(&styles)));
+    }
+    for (key, value) in tag.attributes.iter() {
+        match value {
+            Some(value) => html.push_str(&format!(" {}=\"{}\"", key, escape_attribute(value))),
+            None => html.push_str(&format!(" {}", key)),
+        }
+    }
+    html.push('>');
+    for child in &tag.children {
+        match child {
+            Node::Element(child) => html.push_str(&legacy_to_html(child)),
+            Node::Text(text) => html.push_str(&escape_text(text)),
+            other => html.push_str(&other.to_html()),
+        }
+    }
+    html.push_str(&format!("</{}>", tag.tag_type.html()));
+    html
+}
+
+fn wide_tree(rows: usize) -> HtmlTag {
+    let mut tbody = HtmlTag::new("tbody");
+    for i in 0..rows {
+        tbody.add_child(
+            HtmlTag::new("tr")
+                .with_class("row")
+                .with_style("color", if i % 2 == 0 { "black" } else { "gray" })
+                .with_attribute("data-index", i.to_string())
+                .with_child(HtmlTag::new("td").with_body(&format!("Row {}", i)))
+                .with_child(HtmlTag::new("td").with_body("Some <escaped> & text")),
+        );
+    }
+    HtmlTag::new("table").with_id("wide").with_child(tbody)
+}
+
+fn deep_tree(depth: usize) -> HtmlTag {
+    let mut tag = HtmlTag::new("span").with_body("leaf");
+    for i in 0..depth {
+        tag = HtmlTag::new("div")
+            .with_class("level")
+            .with_style("margin-left", "1px")
+            .with_attribute("data-depth", i.to_string())
+            .with_child(tag);
+    }
+    tag
+}
+
+fn bench_trees(c: &mut Criterion) {
+    let trees = [("wide", wide_tree(1_000)), ("deep", deep_tree(200))];
+
+    let mut group = c.benchmark_group("render");
+    for (name, tree) in &trees {
+        assert_eq!(tree.to_html(), legacy_to_html(tree));
+
+        group.bench_with_input(BenchmarkId::new("to_html", name), tree, |b, tree| {
+            b.iter(|| black_box(tree.to_html()))
+        });
+        group.bench_with_input(BenchmarkId::new("write_html", name), tree, |b, tree| {
+            b.iter(|| {
+                let mut html = String::new();
+                tree.write_html(&mut html).unwrap();
+                black_box(html)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("legacy", name), tree, |b, tree| {
+            b.iter(|| black_box(legacy_to_html(tree)))
+        });
+        group.bench_with_input(BenchmarkId::new("estimated_len", name), tree, |b, tree| {
+            b.iter(|| black_box(tree.estimated_len()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_trees);
+criterion_main!(benches);
@@ -0,0 +1,195 @@
+/// An ordered map of HTML attributes.
+///
+/// Attributes are kept in insertion order, and every attribute can
+/// only be present once. Setting an attribute that already exists
+/// replaces its value in place.
+///
+/// A value of `None` represents an attribute that is present but
+/// has no value, like `disabled`.
+///
+/// Names that could not be rendered as a single attribute, see
+/// `is_valid_name`, are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::attributes::Attributes;
+///
+/// let mut attributes = Attributes::new();
+/// attributes.set("href", Some("/home"));
+/// attributes.set("disabled", None);
+/// attributes.set("href", Some("/about"));
+///
+/// assert_eq!(attributes.get("href"), Some("/about"));
+/// assert_eq!(attributes.get("disabled"), Some(""));
+/// assert_eq!(attributes.len(), 2);
+///
+/// attributes.set("onclick=alert(1) x", Some("y"));
+/// assert_eq!(attributes.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Attributes {
+    entries: Vec<(String, Option<String>)>,
+}
+
+impl Attributes {
+    /// Creates a new, empty `Attributes` map.
+    pub fn new() -> Attributes {
+        Attributes {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the given attribute.
+    ///
+    /// Attributes without a value return an empty string.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_deref().unwrap_or(""))
+    }
+
+    /// Returns whether the given attribute is present.
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.iter().any(|(k, _)| k == key)
+    }
+
+    /// Sets the given attribute, replacing the previous value
+    /// while keeping its position.
+    pub fn set(&mut self, key: &str, value: Option<&str>) {
+        if !is_valid_name(key) {
+            return;
+        }
+        let value = value.map(|v| v.to_string());
+        match self.entries.iter_mut().find(|(k, _)| k == key) {
+            Some((_, current)) => *current = value,
+            None => self.entries.push((key.to_string(), value)),
+        }
+    }
+
+    /// Appends a space separated token to the given attribute,
+    /// which is useful for attributes like `rel`.
+    ///
+    /// Tokens that are already present are not added again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::attributes::Attributes;
+    ///
+    /// let mut attributes = Attributes::new();
+    /// attributes.append("rel", "noopener");
+    /// attributes.append("rel", "noreferrer");
+    /// attributes.append("rel", "noopener");
+    ///
+    /// assert_eq!(attributes.get("rel"), Some("noopener noreferrer"));
+    /// ```
+    pub fn append(&mut self, key: &str, token: &str) {
+        if !is_valid_name(key) {
+            return;
+        }
+        match self.entries.iter_mut().find(|(k, _)| k == key) {
+            Some((_, Some(current))) if !current.trim().is_empty() => {
+                let tokens = token
+                    .split_whitespace()
+                    .filter(|t| !current.split_whitespace().any(|c| c == *t))
+                    .collect::<Vec<_>>();
+                for t in tokens {
+                    current.push(' ');
+                    current.push_str(t);
+                }
+            }
+            Some((_, current)) => *current = Some(token.trim().to_string()),
+            None => self
+                .entries
+                .push((key.to_string(), Some(token.trim().to_string()))),
+        }
+    }
+
+    /// Removes the given attribute.
+    ///
+    /// Returns whether the attribute was present.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(k, _)| k != key);
+        self.entries.len() != len
+    }
+
+    /// Removes all the attributes.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the attributes in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_deref()))
+    }
+}
+
+/// Returns whether a name can be used for an attribute.
+///
+/// A name must not be empty, and must not contain whitespace,
+/// control characters, or any of `"`, `'`, `>`, `/` and `=`,
+/// since those would end the name when the attribute is rendered.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::attributes::is_valid_name;
+///
+/// assert!(is_valid_name("data-user-id"));
+/// assert!(is_valid_name("@click"));
+/// assert!(!is_valid_name("a b"));
+/// assert!(!is_valid_name("x\"onclick"));
+/// assert!(!is_valid_name(""));
+/// ```
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '>' | '/' | '=')
+        })
+}
+
+/// A value that can be used for an attribute.
+///
+/// This is implemented for the usual string types, as well as for
+/// `Option`s of them, where `None` represents an attribute that is
+/// present but has no value.
+pub trait AttributeValue {
+    fn into_attribute_value(self) -> Option<String>;
+}
+
+impl AttributeValue for &str {
+    fn into_attribute_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl AttributeValue for &String {
+    fn into_attribute_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl AttributeValue for String {
+    fn into_attribute_value(self) -> Option<String> {
+        Some(self)
+    }
+}
+
+impl AttributeValue for Option<&str> {
+    fn into_attribute_value(self) -> Option<String> {
+        self.map(|v| v.to_string())
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt::Display;
+
+/// An ordered set of class names, modelled after the DOM `classList`.
+///
+/// Class names are kept in insertion order and are never duplicated.
+/// Since class names can not contain whitespace, `add` and `remove`
+/// treat their argument as a whitespace separated list of names,
+/// while the other methods expect a single name and do nothing
+/// when given one with whitespace in it.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::class_list::ClassList;
+///
+/// let mut classes = ClassList::new();
+/// classes.add("btn btn-primary");
+/// classes.add("btn");
+/// classes.toggle("active");
+///
+/// assert_eq!(classes.to_string(), "btn btn-primary active");
+/// assert!(classes.contains("active"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClassList {
+    names: Vec<String>,
+}
+
+impl ClassList {
+    /// Creates a new, empty `ClassList`.
+    pub fn new() -> ClassList {
+        ClassList { names: Vec::new() }
+    }
+
+    /// Returns whether the given string is a valid single class name.
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty() && !name.chars().any(char::is_whitespace)
+    }
+
+    /// Adds the given, whitespace separated, class names.
+    ///
+    /// Names that are already present are ignored.
+    pub fn add(&mut self, names: &str) {
+        for name in names.split_whitespace() {
+            if !self.contains(name) {
+                self.names.push(name.to_string());
+            }
+        }
+    }
+
+    /// Removes the given, whitespace separated, class names.
+    pub fn remove(&mut self, names: &str) {
+        for name in names.split_whitespace() {
+            self.names.retain(|n| n != name);
+        }
+    }
+
+    /// Returns whether the given class name is present.
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name)
+    }
+
+    /// Toggles the given class name.
+    ///
+    /// Returns whether the class name is present afterwards.
+    pub fn toggle(&mut self, name: &str) -> bool {
+        if !ClassList::is_valid_name(name) {
+            return false;
+        }
+        if self.contains(name) {
+            self.remove(name);
+            false
+        } else {
+            self.add(name);
+            true
+        }
+    }
+
+    /// Replaces a class name with another one, keeping its position.
+    ///
+    /// Returns whether the old class name was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::class_list::ClassList;
+    ///
+    /// let mut classes = ClassList::from("a b c");
+    ///
+    /// assert!(classes.replace("b", "d"));
+    /// assert_eq!(classes.to_string(), "a d c");
+    /// ```
+    pub fn replace(&mut self, old: &str, new: &str) -> bool {
+        if !ClassList::is_valid_name(old) || !ClassList::is_valid_name(new) {
+            return false;
+        }
+        let Some(index) = self.names.iter().position(|n| n == old) else {
+            return false;
+        };
+        if self.contains(new) {
+            self.names.remove(index);
+        } else {
+            self.names[index] = new.to_string();
+        }
+        true
+    }
+
+    /// Removes all the class names.
+    pub fn clear(&mut self) {
+        self.names.clear();
+    }
+
+    /// Returns the number of class names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns whether there are no class names.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Iterates over the class names in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|n| n.as_str())
+    }
+}
+
+impl From<&str> for ClassList {
+    fn from(names: &str) -> Self {
+        let mut classes = ClassList::new();
+        classes.add(names);
+        classes
+    }
+}
+
+impl<'a> FromIterator<&'a str> for ClassList {
+    fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+        let mut classes = ClassList::new();
+        for names in iter {
+            classes.add(names);
+        }
+        classes
+    }
+}
+
+impl Display for ClassList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.names.join(" "))
+    }
+}
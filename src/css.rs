@@ -0,0 +1,563 @@
+use std::fmt::Display;
+
+use crate::parser::line_column;
+
+/// The kind of error that occurred while parsing CSS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssParseErrorKind {
+    /// The input ended inside of a block.
+    UnexpectedEof,
+    /// A character that is not allowed where it was found, like a
+    /// stray `}`.
+    UnexpectedChar(char),
+    /// A comment was opened with `/*` but never closed.
+    UnterminatedComment,
+    /// A string that was not closed before the end of the line.
+    UnterminatedString,
+    /// A rule without a selector, or an empty selector in a list.
+    EmptySelector,
+    /// A declaration without a `:` or without a value.
+    InvalidDeclaration(String),
+}
+
+/// An error that occurred while parsing CSS, along with the position
+/// in the input where it occurred.
+///
+/// Lines and columns both start at 1, columns are counted
+/// in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CssParseError {
+    pub kind: CssParseErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl CssParseError {
+    pub(crate) fn new(kind: CssParseErrorKind, input: &str, offset: usize) -> CssParseError {
+        let (line, column) = line_column(input, offset);
+        CssParseError { kind, line, column }
+    }
+}
+
+impl Display for CssParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            CssParseErrorKind::UnexpectedEof => write!(f, "unexpected end of input")?,
+            CssParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected `{}`", c)?,
+            CssParseErrorKind::UnterminatedComment => write!(f, "unterminated comment")?,
+            CssParseErrorKind::UnterminatedString => write!(f, "unterminated string")?,
+            CssParseErrorKind::EmptySelector => write!(f, "empty selector")?,
+            CssParseErrorKind::InvalidDeclaration(declaration) => {
+                write!(f, "invalid declaration `{}`", declaration)?
+            }
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)
+    }
+}
+
+impl std::error::Error for CssParseError {}
+
+/// A single `property: value` pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Declaration {
+    pub(crate) property: String,
+    pub(crate) value: String,
+    pub(crate) important: bool,
+}
+
+impl Declaration {
+    /// Returns the value, with `!important` appended if needed.
+    pub(crate) fn full_value(&self) -> String {
+        if self.important {
+            format!("{} !important", self.value)
+        } else {
+            self.value.clone()
+        }
+    }
+}
+
+/// The block of an at-rule, which either holds declarations, like
+/// `@font-face`, or other rules, like `@media`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Block {
+    Declarations(Vec<Declaration>),
+    Rules(Vec<Rule>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Rule {
+    Style {
+        selectors: Vec<String>,
+        declarations: Vec<Declaration>,
+    },
+    At {
+        name: String,
+        prelude: String,
+        block: Option<Block>,
+    },
+}
+
+/// At-rules whose block holds other rules instead of declarations.
+const GROUPING_AT_RULES: &[&str] = &[
+    "media",
+    "supports",
+    "container",
+    "layer",
+    "scope",
+    "starting-style",
+    "document",
+    "-moz-document",
+    "keyframes",
+    "-webkit-keyframes",
+    "-moz-keyframes",
+];
+
+/// Parses a whole stylesheet into its rules.
+pub(crate) fn parse_rules(css: &str) -> Result<Vec<Rule>, CssParseError> {
+    CssParser::new(css, false).rules(None)
+}
+
+/// Parses a list of declarations, like the value of a `style` attribute.
+pub(crate) fn parse_declarations(css: &str) -> Result<Vec<Declaration>, CssParseError> {
+    CssParser::new(css, false).declarations(None)
+}
+
+/// Parses a list of declarations like browsers do, dropping the ones
+/// that are malformed instead of failing.
+pub(crate) fn parse_declarations_lenient(css: &str) -> Vec<Declaration> {
+    // A lenient parser only fails when the input ends in the middle of
+    // a string or comment, which drops the unfinished declaration.
+    let mut declarations = Vec::new();
+    let _ = CssParser::new(css, true).declarations_into(None, &mut declarations);
+    declarations
+}
+
+/// Removes the comments and the whitespace that does not change the
+/// meaning of a stylesheet, keeping strings and escapes as they are.
+pub(crate) fn minify(css: &str) -> String {
+    let mut minified = String::with_capacity(css.len());
+    // Whether each open block holds declarations, rather than rules.
+    let mut blocks: Vec<bool> = Vec::new();
+    let mut prelude_start = 0;
+    let mut space = false;
+    // Whether whitespace after the last character written can go.
+    let mut trim_after = false;
+    let mut chars = css.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if c == '/' && css[i..].starts_with("/*") {
+            let end = css[i + 2..]
+                .find("*/")
+                .map_or(css.len(), |end| i + 2 + end + 2);
+            while chars.next_if(|&(j, _)| j < end).is_some() {}
+            space = true;
+            continue;
+        }
+
+        let declarations = blocks.last() == Some(&true);
+        let trims = |c: char| match c {
+            '{' | '}' | ';' | ',' => true,
+            ':' => declarations,
+            '>' | '~' | '+' => !declarations,
+            _ => false,
+        };
+        if space && !minified.is_empty() && !trim_after && !trims(c) && c != ')' {
+            minified.push(' ');
+        }
+        space = false;
+        trim_after = trims(c) || c == '(';
+
+        match c {
+            '"' | '\'' => {
+                minified.push(c);
+                let mut escaped = false;
+                for (_, next) in chars.by_ref() {
+                    minified.push(next);
+                    match next {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        '\n' => break,
+                        next if next == c => break,
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            '\\' => {
+                minified.push(c);
+                if let Some((_, next)) = chars.next() {
+                    minified.push(next);
+                }
+                // An escaped character never loses its whitespace.
+                trim_after = false;
+                continue;
+            }
+            '{' => {
+                let prelude = &minified[prelude_start..];
+                let grouping = prelude.strip_prefix('@').is_some_and(|rule| {
+                    let name = rule
+                        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+                        .next()
+                        .unwrap_or_default()
+                        .to_ascii_lowercase();
+                    GROUPING_AT_RULES.contains(&name.as_str())
+                });
+                blocks.push(!grouping);
+            }
+            '}' => {
+                blocks.pop();
+            }
+            _ => {}
+        }
+        minified.push(c);
+        if matches!(c, '{' | '}' | ';') {
+            prelude_start = minified.len();
+        }
+    }
+    minified
+}
+
+struct CssParser<'a> {
+    input: &'a str,
+    pos: usize,
+    lenient: bool,
+}
+
+impl<'a> CssParser<'a> {
+    fn new(input: &'a str, lenient: bool) -> Self {
+        CssParser {
+            input,
+            pos: 0,
+            lenient,
+        }
+    }
+
+    fn error(&self, kind: CssParseErrorKind, offset: usize) -> CssParseError {
+        CssParseError::new(kind, self.input, offset)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_comment(&mut self) -> Result<(), CssParseError> {
+        let start = self.pos;
+        match self.rest()[2..].find("*/") {
+            Some(end) => {
+                self.pos += 2 + end + 2;
+                Ok(())
+            }
+            None => Err(self.error(CssParseErrorKind::UnterminatedComment, start)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), CssParseError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => self.pos += c.len_utf8(),
+                Some('/') if self.rest().starts_with("/*") => self.skip_comment()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Reads a string, including its quotes, from the current position.
+    fn string(&mut self, quote: char) -> Result<&'a str, CssParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut escaped = false;
+        while let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '\n' => break,
+                c if c == quote => return Ok(&self.input[start..self.pos]),
+                _ => {}
+            }
+        }
+        Err(self.error(CssParseErrorKind::UnterminatedString, start))
+    }
+
+    /// Reads everything up to one of the given characters, outside of
+    /// strings, comments and parentheses, returning the text with the
+    /// whitespace collapsed and the character that ended it, if any.
+    fn until(&mut self, stops: &[char]) -> Result<(String, Option<char>), CssParseError> {
+        let mut text = String::new();
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' | '\'' => {
+                    let string = self.string(c)?;
+                    text.push_str(string);
+                    continue;
+                }
+                '/' if self.rest().starts_with("/*") => {
+                    self.skip_comment()?;
+                    text.push(' ');
+                    continue;
+                }
+                '\\' => {
+                    // An escaped character never ends anything.
+                    text.push(c);
+                    self.pos += 1;
+                    if let Some(next) = self.peek() {
+                        text.push(next);
+                        self.pos += next.len_utf8();
+                    }
+                    continue;
+                }
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth = depth.saturating_sub(1),
+                c if depth == 0 && stops.contains(&c) => {
+                    return Ok((collapse_whitespace(&text), Some(c)));
+                }
+                _ => {}
+            }
+            text.push(c);
+            self.pos += c.len_utf8();
+        }
+        Ok((collapse_whitespace(&text), None))
+    }
+
+    /// Parses rules until the end of the input, or until the `}` that
+    /// closes the block that started at the given offset.
+    fn rules(&mut self, block_start: Option<usize>) -> Result<Vec<Rule>, CssParseError> {
+        let mut rules = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            match self.peek() {
+                None => match block_start {
+                    Some(start) => return Err(self.error(CssParseErrorKind::UnexpectedEof, start)),
+                    None => return Ok(rules),
+                },
+                Some('}') => match block_start {
+                    Some(_) => {
+                        self.pos += 1;
+                        return Ok(rules);
+                    }
+                    None => {
+                        return Err(self.error(CssParseErrorKind::UnexpectedChar('}'), self.pos))
+                    }
+                },
+                // Left over from HTML comments around `style` content.
+                Some('<') if self.rest().starts_with("<!--") => self.pos += 4,
+                Some('-') if self.rest().starts_with("-->") => self.pos += 3,
+                Some('@') => rules.push(self.at_rule()?),
+                Some(_) => rules.push(self.style_rule()?),
+            }
+        }
+    }
+
+    fn style_rule(&mut self) -> Result<Rule, CssParseError> {
+        let start = self.pos;
+        let (prelude, stop) = self.until(&['{', '}', ';'])?;
+        match stop {
+            Some('{') => {}
+            Some(c) => return Err(self.error(CssParseErrorKind::UnexpectedChar(c), self.pos)),
+            None => return Err(self.error(CssParseErrorKind::UnexpectedEof, start)),
+        }
+        let selectors = split_selectors(&prelude);
+        if selectors.iter().any(|s| s.is_empty()) {
+            return Err(self.error(CssParseErrorKind::EmptySelector, start));
+        }
+        let block_start = self.pos;
+        self.pos += 1;
+        let declarations = self.declarations(Some(block_start))?;
+        Ok(Rule::Style {
+            selectors,
+            declarations,
+        })
+    }
+
+    fn at_rule(&mut self) -> Result<Rule, CssParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let name_len = self
+            .rest()
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(self.rest().len());
+        let name = self.rest()[..name_len].to_ascii_lowercase();
+        self.pos += name_len;
+        if name.is_empty() {
+            return Err(self.error(CssParseErrorKind::UnexpectedChar('@'), start));
+        }
+
+        let (prelude, stop) = self.until(&['{', '}', ';'])?;
+        let block = match stop {
+            Some('{') => {
+                let block_start = self.pos;
+                self.pos += 1;
+                Some(if GROUPING_AT_RULES.contains(&name.as_str()) {
+                    Block::Rules(self.rules(Some(block_start))?)
+                } else {
+                    Block::Declarations(self.declarations(Some(block_start))?)
+                })
+            }
+            Some(';') => {
+                self.pos += 1;
+                None
+            }
+            // A statement at the end of a block or of the input.
+            _ => None,
+        };
+        Ok(Rule::At {
+            name,
+            prelude,
+            block,
+        })
+    }
+
+    /// Parses declarations until the end of the input, or until the `}`
+    /// that closes the block that started at the given offset.
+    fn declarations(
+        &mut self,
+        block_start: Option<usize>,
+    ) -> Result<Vec<Declaration>, CssParseError> {
+        let mut declarations = Vec::new();
+        self.declarations_into(block_start, &mut declarations)?;
+        Ok(declarations)
+    }
+
+    fn declarations_into(
+        &mut self,
+        block_start: Option<usize>,
+        declarations: &mut Vec<Declaration>,
+    ) -> Result<(), CssParseError> {
+        loop {
+            self.skip_whitespace()?;
+            let start = self.pos;
+            match self.peek() {
+                None => match block_start {
+                    Some(start) => return Err(self.error(CssParseErrorKind::UnexpectedEof, start)),
+                    None => return Ok(()),
+                },
+                Some('}') => match block_start {
+                    Some(_) => {
+                        self.pos += 1;
+                        return Ok(());
+                    }
+                    None if self.lenient => {
+                        self.pos += 1;
+                        continue;
+                    }
+                    None => return Err(self.error(CssParseErrorKind::UnexpectedChar('}'), start)),
+                },
+                Some(';') => {
+                    self.pos += 1;
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            match self.declaration(start) {
+                Ok(declaration) => declarations.push(declaration),
+                Err(_) if self.lenient => {
+                    // Skip the rest of the malformed declaration.
+                    self.until(&[';'])?;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn declaration(&mut self, start: usize) -> Result<Declaration, CssParseError> {
+        let (property, stop) = self.until(&[':', ';', '{', '}'])?;
+        if stop != Some(':') || property.is_empty() || property.contains(' ') {
+            return Err(self.error(CssParseErrorKind::InvalidDeclaration(property), start));
+        }
+        self.pos += 1;
+        let (value, stop) = self.until(&[';', '{', '}'])?;
+        if stop == Some('{') {
+            return Err(self.error(CssParseErrorKind::UnexpectedChar('{'), self.pos));
+        }
+
+        let (value, important) = split_important(&value);
+        let property = if property.starts_with("--") {
+            property
+        } else {
+            property.to_ascii_lowercase()
+        };
+        if value.is_empty() && !property.starts_with("--") {
+            return Err(self.error(CssParseErrorKind::InvalidDeclaration(property), start));
+        }
+        Ok(Declaration {
+            property,
+            value,
+            important,
+        })
+    }
+}
+
+/// Collapses runs of whitespace outside of strings into a single space,
+/// and trims the result.
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut quote = None;
+    let mut escaped = false;
+    let mut space = false;
+    for c in text.chars() {
+        if quote.is_none() && c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space && !collapsed.is_empty() {
+            collapsed.push(' ');
+        }
+        space = false;
+        collapsed.push(c);
+        match (quote, c) {
+            _ if escaped => escaped = false,
+            (_, '\\') => escaped = true,
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            _ => {}
+        }
+    }
+    collapsed
+}
+
+/// Splits a selector list at the commas that are not nested inside
+/// parentheses or strings, like the one in `:is(a, b)`.
+fn split_selectors(prelude: &str) -> Vec<String> {
+    let mut selectors = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    for c in prelude.chars() {
+        match (quote, c) {
+            (Some(q), c) if q == c => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                selectors.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    selectors.push(current.trim().to_string());
+    selectors
+}
+
+/// Splits a trailing `!important` off a value.
+pub(crate) fn split_important(value: &str) -> (String, bool) {
+    if let Some(i) = value.rfind('!') {
+        let (before, after) = value.split_at(i);
+        if after[1..].trim().eq_ignore_ascii_case("important") {
+            return (before.trim_end().to_string(), true);
+        }
+    }
+    (value.to_string(), false)
+}
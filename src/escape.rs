@@ -0,0 +1,122 @@
+use std::fmt::{Display, Write};
+
+/// A wrapper around a string of trusted HTML.
+///
+/// Everything that ends up in the body of a `HtmlTag` is escaped
+/// by default, so that user supplied content can never break out
+/// of the markup it is placed in.
+///
+/// If you already have a piece of markup that you trust, wrap it
+/// in `RawHtml` to have it rendered verbatim.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::{HtmlTag, RawHtml};
+///
+/// let mut p = HtmlTag::new("p");
+/// p.set_raw_body(RawHtml::new("<b>Hello</b> World"));
+///
+/// assert_eq!(p.to_html(), "<p><b>Hello</b> World</p>");
+/// ```
+///
+/// Only use this for content that you control, it is never escaped.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RawHtml(String);
+
+impl RawHtml {
+    /// Creates a new `RawHtml` from the given markup.
+    pub fn new(html: &str) -> RawHtml {
+        RawHtml(html.to_string())
+    }
+
+    /// Returns the wrapped markup.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for RawHtml {
+    fn from(html: &str) -> Self {
+        RawHtml::new(html)
+    }
+}
+
+impl From<String> for RawHtml {
+    fn from(html: String) -> Self {
+        RawHtml(html)
+    }
+}
+
+impl Display for RawHtml {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Escapes a string so that it can be safely used as text content.
+///
+/// Replaces `&`, `<` and `>` with their respective entities.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::escape::escape_text;
+///
+/// assert_eq!(escape_text("<script>"), "&lt;script&gt;");
+/// ```
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    write_escaped_text(&mut escaped, text).expect("writing to a String never fails");
+    escaped
+}
+
+/// Escapes a string so that it can be safely used as a quoted
+/// attribute value.
+///
+/// On top of what `escape_text` does, this also escapes both
+/// double and single quotes.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::escape::escape_attribute;
+///
+/// assert_eq!(escape_attribute("say \"hi\""), "say &quot;hi&quot;");
+/// ```
+pub fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    write_escaped_attribute(&mut escaped, value).expect("writing to a String never fails");
+    escaped
+}
+
+/// Writes the escaped text into the given writer, see `escape_text`.
+pub fn write_escaped_text<W: Write>(w: &mut W, text: &str) -> std::fmt::Result {
+    write_escaped(w, text, false)
+}
+
+/// Writes the escaped attribute value into the given writer,
+/// see `escape_attribute`.
+pub fn write_escaped_attribute<W: Write>(w: &mut W, value: &str) -> std::fmt::Result {
+    write_escaped(w, value, true)
+}
+
+/// Writes the unescaped runs of the string as a whole, since all
+/// the characters that need escaping are ASCII.
+fn write_escaped<W: Write>(w: &mut W, value: &str, attribute: bool) -> std::fmt::Result {
+    let mut last = 0;
+    for (i, byte) in value.bytes().enumerate() {
+        let entity = match byte {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'"' if attribute => "&quot;",
+            b'\'' if attribute => "&#39;",
+            _ => continue,
+        };
+        w.write_str(&value[last..i])?;
+        w.write_str(entity)?;
+        last = i + 1;
+    }
+    w.write_str(&value[last..])
+}
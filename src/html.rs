@@ -1,7 +1,16 @@
-use std::fmt::Display;
+use std::fmt::{Display, Write};
 
 use crate::{
-    styles::{convert_to_styles, sanitize_styles, Class, Style, StyleSheet},
+    attributes::{AttributeValue, Attributes},
+    class_list::ClassList,
+    escape::{write_escaped_attribute, RawHtml},
+    node::Node,
+    parser::{self, ParseError},
+    pretty::{self, PrettyOptions},
+    styles::{
+        convert_to_styles, custom_property_name, parse_styles_lenient, sanitize_styles, Class,
+        Style, StyleSheet,
+    },
     tags::TagType,
 };
 
@@ -60,15 +69,36 @@ use crate::{
 ///
 /// Remember, all of these can be nested as well as modifies using
 /// the methods provided.
+///
+/// # Escaping
+///
+/// The body and every attribute value are escaped when rendered,
+/// so user supplied content is always safe to use.
+///
+/// ```
+/// use html_tag::HtmlTag;
+///
+/// let mut p = HtmlTag::new("p");
+/// p.set_body("<script>alert(1)</script>");
+/// p.add_attribute("data-name", "\"quoted\"");
+///
+/// assert_eq!(
+///     p.to_html(),
+///     "<p data-name=\"&quot;quoted&quot;\">&lt;script&gt;alert(1)&lt;/script&gt;</p>"
+/// );
+/// ```
+///
+/// Trusted markup can be set using `set_raw_body`, which is also how
+/// the content of `script` and `style` is added without escaping.
 #[derive(Clone, PartialEq, Eq)]
 pub struct HtmlTag {
     pub pre_content: Option<String>,
     pub tag_type: TagType,
-    pub class_names: Vec<String>,
+    pub class_names: ClassList,
     pub id: Option<String>,
-    pub body: Option<String>,
-    pub children: Option<Vec<HtmlTag>>,
-    pub custom_attributes: Option<Vec<(String, String)>>,
+    pub inline_styles: Class,
+    pub children: Vec<Node>,
+    pub attributes: Attributes,
 }
 
 impl HtmlTag {
@@ -96,11 +126,11 @@ impl HtmlTag {
         HtmlTag {
             pre_content: None,
             tag_type: TagType::from(tag_type),
-            class_names: Vec::new(),
+            class_names: ClassList::new(),
             id: None,
-            body: None,
-            children: None,
-            custom_attributes: None,
+            inline_styles: Class::new(),
+            children: Vec::new(),
+            attributes: Attributes::new(),
         }
     }
 
@@ -121,21 +151,143 @@ impl HtmlTag {
     /// ```
     ///
     /// This is the most commonly used scaffold for creating a new `HtmlTag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a body is given for a void element.
     pub fn fresh(tag_type: TagType, body: Option<&str>, class_names: Vec<&str>) -> HtmlTag {
-        HtmlTag {
+        let mut tag = HtmlTag {
             pre_content: None,
             tag_type,
-            class_names: class_names.iter().map(|s| s.to_string()).collect(),
+            class_names: class_names.into_iter().collect(),
             id: None,
-            body: body.map(|s| s.to_string()),
-            children: None,
-            custom_attributes: None,
+            inline_styles: Class::new(),
+            children: Vec::new(),
+            attributes: Attributes::new(),
+        };
+        if let Some(body) = body {
+            tag.set_body(body);
+        }
+        tag
+    }
+
+    /// Parses a string of HTML into the `HtmlTag`s at its top level.
+    ///
+    /// Attributes can be quoted, unquoted or boolean, character
+    /// references are decoded, and the content of `script` and `style`
+    /// is kept as it is, as `RawHtml`. Void elements do not need to be closed, and
+    /// any other element can be self closed, like `<my-icon />`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let tags = HtmlTag::parse("<ul id=list><li class=\"item\">Tea &amp; cake<br></li></ul>").unwrap();
+    ///
+    /// assert_eq!(tags.len(), 1);
+    /// assert_eq!(tags[0].get_attribute("id"), Some("list".to_string()));
+    /// assert_eq!(tags[0].to_html(), "<ul id=\"list\"><li class=\"item\">Tea &amp; cake<br></li></ul>");
+    /// ```
+    ///
+    /// Comments and the doctype at the top level are skipped, while
+    /// text outside of any element is an error, use `Node::parse` to
+    /// keep those around.
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let error = HtmlTag::parse("<div>\n  <p>Hello</div>").unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "expected </p> but found </div> at line 2, column 11");
+    /// ```
+    pub fn parse(html: &str) -> Result<Vec<HtmlTag>, ParseError> {
+        parser::parse_elements(html)
+    }
+
+    /// Parses a string of HTML the way browsers do, into the `HtmlTag`s
+    /// at its top level.
+    ///
+    /// Unlike `parse`, this never fails. It follows the WHATWG parsing
+    /// rules instead, which fix up messy markup, like paragraphs and
+    /// list items that are not closed, misnested formatting elements
+    /// or tables without a `tbody`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let tags = HtmlTag::parse_lenient("<p>One<p><b>Two<i>Three</b>Four</i><table><tr><td>Five</table>");
+    ///
+    /// let html = tags.iter().map(|tag| tag.to_html()).collect::<String>();
+    /// assert_eq!(
+    ///     html,
+    ///     "<p>One</p><p><b>Two<i>Three</i></b><i>Four</i></p>\
+    ///      <table><tbody><tr><td>Five</td></tr></tbody></table>"
+    /// );
+    /// ```
+    ///
+    /// The HTML is parsed as the content of a `body`, text at the top
+    /// level is skipped, use `Node::parse_lenient` to keep it around.
+    pub fn parse_lenient(html: &str) -> Vec<HtmlTag> {
+        parser::parse_fragment(html, "body")
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Element(tag) => Some(tag),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Parses a whole HTML document the way browsers do, see
+    /// `parse_lenient`.
+    ///
+    /// This returns the `html` element, which always contains a `head`
+    /// and a `body`, even if they are missing in the markup. The doctype,
+    /// and any comment before the `html` element, ends up in the
+    /// `pre_content`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let html = HtmlTag::parse_document("<!DOCTYPE html><title>Hi</title><p>Hello");
+    ///
+    /// assert_eq!(
+    ///     html.to_html(),
+    ///     "<!DOCTYPE html><html><head><title>Hi</title></head><body><p>Hello</p></body></html>"
+    /// );
+    /// ```
+    ///
+    /// Comments after the end of the `html` element are dropped, since
+    /// there is nowhere to keep them.
+    pub fn parse_document(html: &str) -> HtmlTag {
+        let (doctype, nodes) = parser::parse_document(html);
+        let mut pre_content = doctype
+            .map(|name| format!("<!DOCTYPE {}>", name))
+            .unwrap_or_default();
+        let mut document = None;
+        for node in nodes {
+            match node {
+                Node::Element(tag) if document.is_none() => document = Some(tag),
+                Node::Comment(_) if document.is_none() => pre_content.push_str(&node.to_html()),
+                _ => {}
+            }
+        }
+        let mut document = document.expect("a document always has a html element");
+        if !pre_content.is_empty() {
+            document.set_pre_content(&pre_content);
         }
+        document
     }
 
-    /// Adds a child of the type `HtmlTag` to the current `HtmlTag`.
+    /// Adds a child to the current `HtmlTag`.
     ///
     /// This is used to essentially nest HTML tags.
+    /// The child can be another `HtmlTag` or any `Node`, and is
+    /// appended after the existing children.
     ///
     /// # Examples
     ///
@@ -151,22 +303,188 @@ impl HtmlTag {
     /// ```
     ///
     /// This needs a mutable reference to the current `HtmlTag`.
-    pub fn add_child(&mut self, child: HtmlTag) {
-        if let Some(children) = &mut self.children {
-            children.push(child);
-        } else {
-            self.children = Some(vec![child]);
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current `HtmlTag` is a void element, like `img`
+    /// or `br`, since those can not have any content.
+    /// Use `try_add_child` to handle this case yourself.
+    pub fn add_child(&mut self, child: impl Into<Node>) {
+        if let Err(err) = self.try_add_child(child) {
+            panic!("{}", err);
         }
     }
 
+    /// Adds a child to the current `HtmlTag`, reporting an error
+    /// if the current `HtmlTag` is a void element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    /// use html_tag::html::HtmlError;
+    /// use html_tag::TagType;
+    ///
+    /// let mut img = HtmlTag::new("img");
+    ///
+    /// assert_eq!(
+    ///     img.try_add_child(HtmlTag::new("p")),
+    ///     Err(HtmlError::VoidElementContent(TagType::Img))
+    /// );
+    /// ```
+    pub fn try_add_child(&mut self, child: impl Into<Node>) -> Result<(), HtmlError> {
+        if self.tag_type.is_void() {
+            return Err(HtmlError::VoidElementContent(self.tag_type.clone()));
+        }
+        self.children.push(child.into());
+        Ok(())
+    }
+
+    /// Adds a text node to the current `HtmlTag`.
+    ///
+    /// Unlike `set_body`, this keeps the existing children, so text
+    /// and elements can be interleaved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut p = HtmlTag::new("p");
+    /// p.add_text("Hello ");
+    /// p.add_child(HtmlTag::new("strong").with_body("world"));
+    /// p.add_text(", welcome");
+    ///
+    /// assert_eq!(p.to_html(), "<p>Hello <strong>world</strong>, welcome</p>");
+    /// ```
+    pub fn add_text(&mut self, text: &str) {
+        self.add_child(Node::text(text));
+    }
+
+    /// Adds a comment node to the current `HtmlTag`.
+    pub fn add_comment(&mut self, comment: &str) {
+        self.add_child(Node::comment(comment));
+    }
+
+    /// Returns the concatenated text of all the text nodes
+    /// in the current `HtmlTag` and its children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let p = HtmlTag::new("p")
+    ///     .with_text("Hello ")
+    ///     .with_child(HtmlTag::new("b").with_body("World"));
+    ///
+    /// assert_eq!(p.text_content(), "Hello World");
+    /// ```
+    pub fn text_content(&self) -> String {
+        let mut text = String::new();
+        for child in &self.children {
+            match child {
+                Node::Text(t) => text.push_str(t),
+                Node::Raw(html) if self.tag_type.is_raw_text() => text.push_str(html.as_str()),
+                Node::Element(tag) => text.push_str(&tag.text_content()),
+                _ => {}
+            }
+        }
+        text
+    }
+
     /// Adds a class name to the current `HtmlTag`.
+    ///
+    /// Class names that are already present are not added again,
+    /// and multiple class names can be separated by whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut button = HtmlTag::new("button");
+    /// button.add_class("btn btn-primary");
+    /// button.add_class("btn");
+    ///
+    /// assert_eq!(button.to_html(), "<button class=\"btn btn-primary\"></button>");
+    /// ```
     pub fn add_class(&mut self, class_name: &str) {
-        self.class_names.push(class_name.to_string());
+        self.class_names.add(class_name);
+    }
+
+    /// Removes a class name from the current `HtmlTag`.
+    pub fn remove_class(&mut self, class_name: &str) {
+        self.class_names.remove(class_name);
+    }
+
+    /// Toggles a class name on the current `HtmlTag`.
+    ///
+    /// Returns whether the class name is present afterwards.
+    pub fn toggle_class(&mut self, class_name: &str) -> bool {
+        self.class_names.toggle(class_name)
+    }
+
+    /// Returns whether the current `HtmlTag` has the given class name.
+    pub fn has_class(&self, class_name: &str) -> bool {
+        self.class_names.contains(class_name)
+    }
+
+    /// Replaces a class name of the current `HtmlTag` with another one.
+    ///
+    /// Returns whether the old class name was present.
+    pub fn replace_class(&mut self, old: &str, new: &str) -> bool {
+        self.class_names.replace(old, new)
     }
 
     /// Sets the body of the current `HtmlTag`.
+    ///
+    /// This replaces all the children with a single text node.
+    /// The body is escaped when rendered, use `set_raw_body`
+    /// for trusted markup.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current `HtmlTag` is a void element.
+    /// Use `try_set_body` to handle this case yourself.
     pub fn set_body(&mut self, body: &str) {
-        self.body = Some(body.to_string());
+        if let Err(err) = self.try_set_body(body) {
+            panic!("{}", err);
+        }
+    }
+
+    /// Sets the body of the current `HtmlTag`, reporting an error
+    /// if the current `HtmlTag` is a void element.
+    pub fn try_set_body(&mut self, body: &str) -> Result<(), HtmlError> {
+        if self.tag_type.is_void() {
+            return Err(HtmlError::VoidElementContent(self.tag_type.clone()));
+        }
+        self.children = vec![Node::text(body)];
+        Ok(())
+    }
+
+    /// Sets the body of the current `HtmlTag` to trusted markup.
+    ///
+    /// Unlike `set_body`, the body is rendered verbatim.
+    /// This replaces all the children with a single raw node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{HtmlTag, RawHtml};
+    ///
+    /// let mut div = HtmlTag::new("div");
+    /// div.set_raw_body(RawHtml::new("<p>Hello World</p>"));
+    ///
+    /// assert_eq!(div.to_html(), "<div><p>Hello World</p></div>");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current `HtmlTag` is a void element.
+    pub fn set_raw_body(&mut self, body: RawHtml) {
+        self.children.clear();
+        self.add_child(Node::raw(body));
     }
 
     /// Sets the id of the current `HtmlTag`.
@@ -180,13 +498,94 @@ impl HtmlTag {
     }
 
     /// Sets the style of the current `HtmlTag`.
-    pub fn set_style(&mut self, key: &str, value: &str) {
-        self.add_attribute("style", &format!("{}: {};", key, value));
+    ///
+    /// All the styles are merged into a single `style` attribute, in
+    /// the order they were set. Setting a property that is already
+    /// present overrides it and moves it to the end, so a shorthand
+    /// never undoes a longhand that was set after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut div = HtmlTag::new("div");
+    /// div.set_style("color", "red");
+    /// div.set_style("margin", "0");
+    /// div.set_style("color", "blue");
+    ///
+    /// assert_eq!(div.to_html(), "<div style=\"margin: 0;color: blue;\"></div>");
+    /// ```
+    pub fn set_style(&mut self, key: impl AsRef<str>, value: impl Display) {
+        self.inline_styles.insert(
+            key.as_ref().trim().to_string(),
+            value.to_string().trim().to_string(),
+        );
+    }
+
+    /// Returns the value of an inline style property of the current `HtmlTag`.
+    pub fn get_style(&self, key: &str) -> Option<&str> {
+        self.inline_styles.get(key).map(|v| v.as_str())
+    }
+
+    /// Returns all the inline style declarations of the current `HtmlTag`,
+    /// including the ones parsed from a `style` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let div = HtmlTag::new("div").with_attribute("style", "color: red; width: calc(100% - 1em)");
+    ///
+    /// assert_eq!(div.styles()["color"], "red");
+    /// assert_eq!(div.styles()["width"], "calc(100% - 1em)");
+    /// ```
+    pub fn styles(&self) -> &Class {
+        &self.inline_styles
+    }
+
+    /// Removes an inline style property from the current `HtmlTag`.
+    ///
+    /// Returns the removed value, if the property was present.
+    pub fn remove_style(&mut self, key: &str) -> Option<String> {
+        self.inline_styles.remove(key)
+    }
+
+    /// Removes all the inline styles from the current `HtmlTag`.
+    pub fn clear_styles(&mut self) {
+        self.inline_styles.clear();
+    }
+
+    /// Sets an inline custom property of the current `HtmlTag`, which
+    /// overrides the value it inherits for the element and its children.
+    ///
+    /// The leading `--` of the name is optional.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut div = HtmlTag::new("div").with_style("color", "var(--brand)");
+    /// div.set_custom_property("brand", "#f00");
+    ///
+    /// assert_eq!(div.get_custom_property("--brand"), Some("#f00"));
+    /// assert_eq!(div.to_html(), "<div style=\"color: var(--brand);--brand: #f00;\"></div>");
+    /// ```
+    pub fn set_custom_property(&mut self, name: &str, value: impl Display) {
+        self.set_style(custom_property_name(name), value);
+    }
+
+    /// Returns the value of an inline custom property of the current
+    /// `HtmlTag`, where the leading `--` of the name is optional.
+    pub fn get_custom_property(&self, name: &str) -> Option<&str> {
+        self.get_style(&custom_property_name(name))
     }
 
     /// Construct and applies styles
-    /// The Class struct is a HashMap<String, String>
-    /// This is to represent the key-value pairs of the styles
+    /// The Class struct is an ordered map of the styles, from property
+    /// to value
     ///
     /// # Examples
     ///
@@ -196,13 +595,18 @@ impl HtmlTag {
     ///
     /// let mut div = HtmlTag::new("div");
     /// let mut font_style = Class::new();
-    /// font_style.insert("font-size".to_string(), "20px".to_string());
-    /// font_style.insert("font-family".to_string(), "sans-serif".to_string());
+    /// font_style.insert("font-size", "20px");
+    /// font_style.insert("font-family", "sans-serif");
     /// div.add_styles(font_style);
     ///
-    /// assert_eq!(div.to_html(), "<div style=\"font-family: sans-serif;font-size: 20px;\"></div>");
+    /// assert_eq!(div.to_html(), "<div style=\"font-size: 20px;font-family: sans-serif;\"></div>");
+    /// ```
+    ///
+    /// The styles are merged with the ones already present.
     pub fn add_styles(&mut self, styles: Class) {
-        self.add_attribute("style", convert_to_styles(styles).as_str());
+        for (key, value) in styles {
+            self.set_style(&key, &value);
+        }
     }
 
     /// Chaining method for add_styles
@@ -217,12 +621,41 @@ impl HtmlTag {
         self
     }
 
+    /// Chaining method for add_class, that only adds the class
+    /// name if the condition holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let active = true;
+    /// let li = HtmlTag::new("li")
+    ///     .with_class("item")
+    ///     .with_class_if(active, "active")
+    ///     .with_class_if(!active, "inactive");
+    ///
+    /// assert_eq!(li.to_html(), "<li class=\"item active\"></li>");
+    /// ```
+    pub fn with_class_if(mut self, condition: bool, class_name: &str) -> Self {
+        if condition {
+            self.add_class(class_name);
+        }
+        self
+    }
+
     /// Chaining method for set_body
     pub fn with_body(mut self, body: &str) -> Self {
         self.set_body(body);
         self
     }
 
+    /// Chaining method for set_raw_body
+    pub fn with_raw_body(mut self, body: RawHtml) -> Self {
+        self.set_raw_body(body);
+        self
+    }
+
     /// Chaining method for set_id
     pub fn with_id(mut self, id: &str) -> Self {
         self.set_id(id);
@@ -236,23 +669,47 @@ impl HtmlTag {
     }
 
     /// Chaining method for set_style
-    pub fn with_style(mut self, key: &str, value: &str) -> Self {
+    pub fn with_style(mut self, key: impl AsRef<str>, value: impl Display) -> Self {
         self.set_style(key, value);
         self
     }
 
+    /// Chaining method for set_custom_property
+    pub fn with_custom_property(mut self, name: &str, value: impl Display) -> Self {
+        self.set_custom_property(name, value);
+        self
+    }
+
     /// Chaining method for add_child
-    pub fn with_child(mut self, child: HtmlTag) -> Self {
+    pub fn with_child(mut self, child: impl Into<Node>) -> Self {
         self.add_child(child);
         self
     }
 
+    /// Chaining method for add_text
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.add_text(text);
+        self
+    }
+
+    /// Chaining method for add_comment
+    pub fn with_comment(mut self, comment: &str) -> Self {
+        self.add_comment(comment);
+        self
+    }
+
     /// Chaining method for add_attribute
-    pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
+    pub fn with_attribute(mut self, key: &str, value: impl AttributeValue) -> Self {
         self.add_attribute(key, value);
         self
     }
 
+    /// Chaining method for set_boolean_attribute
+    pub fn with_boolean_attribute(mut self, key: &str) -> Self {
+        self.set_boolean_attribute(key);
+        self
+    }
+
     /// Sets the pre tag of the current `HtmlTag`.
     pub fn set_pre_content(&mut self, body: &str) {
         self.pre_content = Some(body.to_string());
@@ -260,40 +717,54 @@ impl HtmlTag {
 
     /// Embed custom StyleSheet
     pub fn embed_style_sheet(mut self, style_sheet: &StyleSheet) -> Self {
-        self.set_pre_content(sanitize_styles(style_sheet.get_with_tag()).as_str());
+        let styles = sanitize_styles(style_sheet.get_style_sheet());
+        self.set_pre_content(&format!("<style>{}</style>", styles));
         self
     }
 
-    fn get_tags(tag_type: &TagType) -> (String, String) {
-        let tag = format!("<{}", tag_type.html());
-        let closing_tag = format!("</{}>", tag_type.html());
-        (tag, closing_tag)
-    }
-
-    fn partial_convert(&self) -> String {
-        let mut html_to_return = if let Some(pre_tag) = &self.pre_content {
-            pre_tag.to_string()
-        } else {
-            String::new()
-        };
-        let (opening_tag, _) = HtmlTag::get_tags(&self.tag_type);
-        html_to_return.push_str(&opening_tag);
+    pub(crate) fn write_opening_tag<W: Write>(&self, w: &mut W) -> std::fmt::Result {
+        w.write_char('<')?;
+        w.write_str(self.tag_type.as_str())?;
 
         if let Some(id) = &self.id {
-            html_to_return.push_str(&format!(" id=\"{}\"", id));
+            w.write_str(" id=\"")?;
+            write_escaped_attribute(w, id)?;
+            w.write_char('"')?;
         }
 
         if !self.class_names.is_empty() {
-            html_to_return.push_str(&format!(" class=\"{}\"", self.class_names.join(" ")));
+            w.write_str(" class=\"")?;
+            for (i, class_name) in self.class_names.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(' ')?;
+                }
+                write_escaped_attribute(w, class_name)?;
+            }
+            w.write_char('"')?;
         }
 
-        if let Some(custom_attributes) = &self.custom_attributes {
-            for (key, value) in custom_attributes {
-                html_to_return.push_str(&format!(" {}=\"{}\"", key, value));
+        if !self.inline_styles.is_empty() {
+            w.write_str(" style=\"")?;
+            for (property, value) in &self.inline_styles {
+                write_escaped_attribute(w, property)?;
+                w.write_str(": ")?;
+                write_escaped_attribute(w, value)?;
+                w.write_char(';')?;
             }
+            w.write_char('"')?;
         }
 
-        html_to_return
+        for (key, value) in self.attributes.iter() {
+            w.write_char(' ')?;
+            w.write_str(key)?;
+            if let Some(value) = value {
+                w.write_str("=\"")?;
+                write_escaped_attribute(w, value)?;
+                w.write_char('"')?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Adds an attribute to the current `HtmlTag`.
@@ -314,19 +785,196 @@ impl HtmlTag {
     /// ```
     ///
     /// This is used to add custom attributes as well.
-    pub fn add_attribute(&mut self, key: &str, value: &str) {
+    /// Adding an attribute that is already present replaces its value,
+    /// except for `class` and `style`, which are merged.
+    ///
+    /// Passing `None` as the value adds an attribute that is present
+    /// but has no value, which is rendered as the bare attribute name.
+    /// `id`, `class` and `style` are only useful with a value, so
+    /// adding them with `None` does nothing. Names that are not valid,
+    /// like ones with whitespace or quotes, are ignored as well.
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut input = HtmlTag::new("input");
+    /// input.add_attribute("type", "checkbox");
+    /// input.add_attribute("checked", None);
+    ///
+    /// assert_eq!(input.to_html(), "<input type=\"checkbox\" checked>");
+    /// ```
+    pub fn add_attribute(&mut self, key: &str, value: impl AttributeValue) {
+        match (key, value.into_attribute_value()) {
+            ("class", Some(value)) => self.add_class(&value),
+            ("style", Some(value)) => self.add_styles(parse_styles_lenient(&value)),
+            ("id" | "class" | "style", None) => {}
+            (_, value) => self.set_attribute(key, value.as_deref()),
+        }
+    }
+
+    /// Sets an attribute on the current `HtmlTag`, replacing
+    /// any previous value.
+    ///
+    /// Unlike `add_attribute`, this also replaces all the classes
+    /// or styles when used with `class` or `style`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut a = HtmlTag::new("a").with_class("old").with_href("/home");
+    /// a.set_attribute("class", "new");
+    /// a.set_attribute("href", "/about");
+    ///
+    /// assert_eq!(a.to_html(), "<a class=\"new\" href=\"/about\"></a>");
+    /// ```
+    pub fn set_attribute(&mut self, key: &str, value: impl AttributeValue) {
+        let value = value.into_attribute_value();
+        match key {
+            "id" => self.id = Some(value.unwrap_or_default()),
+            "class" => {
+                self.class_names.clear();
+                if let Some(value) = value {
+                    self.add_class(&value);
+                }
+            }
+            "style" => {
+                self.clear_styles();
+                if let Some(value) = value {
+                    self.add_styles(parse_styles_lenient(&value));
+                }
+            }
+            _ => self.attributes.set(key, value.as_deref()),
+        }
+    }
+
+    /// Appends a space separated token to an attribute of the
+    /// current `HtmlTag`, which is useful for attributes like `rel`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut a = HtmlTag::new("a");
+    /// a.append_attribute("rel", "noopener");
+    /// a.append_attribute("rel", "noreferrer");
+    ///
+    /// assert_eq!(a.to_html(), "<a rel=\"noopener noreferrer\"></a>");
+    /// ```
+    pub fn append_attribute(&mut self, key: &str, token: &str) {
+        match key {
+            "class" => self.add_class(token),
+            "style" => self.add_styles(parse_styles_lenient(token)),
+            _ => self.attributes.append(key, token),
+        }
+    }
+
+    /// Returns the value of an attribute of the current `HtmlTag`.
+    ///
+    /// This also works for `id`, `class` and `style`.
+    /// Attributes without a value return an empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let div = HtmlTag::new("div")
+    ///     .with_class("a")
+    ///     .with_class("b")
+    ///     .with_attribute("data-test", "test");
+    ///
+    /// assert_eq!(div.get_attribute("class"), Some("a b".to_string()));
+    /// assert_eq!(div.get_attribute("data-test"), Some("test".to_string()));
+    /// assert_eq!(div.get_attribute("href"), None);
+    /// ```
+    pub fn get_attribute(&self, key: &str) -> Option<String> {
+        match key {
+            "id" => self.id.clone(),
+            "class" if !self.class_names.is_empty() => Some(self.class_names.to_string()),
+            "style" if !self.inline_styles.is_empty() => {
+                Some(convert_to_styles(self.inline_styles.clone()))
+            }
+            "class" | "style" => None,
+            _ => self.attributes.get(key).map(|v| v.to_string()),
+        }
+    }
+
+    /// Returns whether an attribute is present on the current `HtmlTag`.
+    pub fn has_attribute(&self, key: &str) -> bool {
+        match key {
+            "id" => self.id.is_some(),
+            "class" => !self.class_names.is_empty(),
+            "style" => !self.inline_styles.is_empty(),
+            _ => self.attributes.contains(key),
+        }
+    }
+
+    /// Removes an attribute from the current `HtmlTag`.
+    ///
+    /// Returns whether the attribute was present.
+    pub fn remove_attribute(&mut self, key: &str) -> bool {
+        let present = self.has_attribute(key);
         match key {
-            "class" => self.add_class(value),
-            "id" => self.set_id(value),
-            _ => self.add_custom_attribute(key, value),
+            "id" => self.id = None,
+            "class" => self.class_names.clear(),
+            "style" => self.clear_styles(),
+            _ => {
+                self.attributes.remove(key);
+            }
         }
+        present
     }
 
-    fn add_custom_attribute(&mut self, key: &str, value: &str) {
-        if let Some(custom_attributes) = &mut self.custom_attributes {
-            custom_attributes.push((key.to_string(), value.to_string()));
+    /// Sets a boolean attribute, like `disabled` or `checked`,
+    /// on the current `HtmlTag`.
+    ///
+    /// Boolean attributes are rendered as the bare attribute name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut button = HtmlTag::new("button");
+    /// button.set_boolean_attribute("disabled");
+    ///
+    /// assert_eq!(button.to_html(), "<button disabled></button>");
+    /// ```
+    pub fn set_boolean_attribute(&mut self, key: &str) {
+        self.set_attribute(key, None);
+    }
+
+    /// Removes a boolean attribute from the current `HtmlTag`.
+    pub fn unset_boolean_attribute(&mut self, key: &str) {
+        self.remove_attribute(key);
+    }
+
+    /// Toggles a boolean attribute on the current `HtmlTag`.
+    ///
+    /// Returns whether the attribute is present afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut details = HtmlTag::new("details");
+    ///
+    /// assert!(details.toggle_boolean_attribute("open"));
+    /// assert_eq!(details.to_html(), "<details open></details>");
+    /// assert!(!details.toggle_boolean_attribute("open"));
+    /// assert_eq!(details.to_html(), "<details></details>");
+    /// ```
+    pub fn toggle_boolean_attribute(&mut self, key: &str) -> bool {
+        if self.has_attribute(key) {
+            self.unset_boolean_attribute(key);
+            false
         } else {
-            self.custom_attributes = Some(vec![(key.to_string(), value.to_string())]);
+            self.set_boolean_attribute(key);
+            true
         }
     }
 
@@ -336,12 +984,13 @@ impl HtmlTag {
     ///
     /// Where the first element of the tuple is the key, and the second
     /// element is the value.
+    ///
+    /// The attributes are added using `add_attribute`, so the ones
+    /// already present are kept.
     pub fn add_custom(&mut self, attributes: Vec<(&str, &str)>) {
-        let mut custom_attributes = Vec::new();
         for (key, value) in attributes {
-            custom_attributes.push((key.to_string(), value.to_string()));
+            self.add_attribute(key, value);
         }
-        self.custom_attributes = Some(custom_attributes);
     }
 
     /// Converts the current `HtmlTag` to a HTML string.
@@ -378,36 +1027,318 @@ impl HtmlTag {
     /// ```
     ///
     /// This will print the following: `<div class="test" id="test"></div>`
+    ///
+    /// Void elements, like `img` or `br`, are rendered without
+    /// a closing tag.
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let img = HtmlTag::new("img").with_attribute("src", "cat.png");
+    ///
+    /// assert_eq!(img.to_html(), "<img src=\"cat.png\">");
+    /// ```
     pub fn to_html(&self) -> String {
-        let mut html = self.partial_convert();
-        let (_, closing_tag) = HtmlTag::get_tags(&self.tag_type);
+        self.render(false)
+    }
 
-        if let Some(body) = &self.body {
-            html.push_str(&format!(">{}</{}>", body, self.tag_type.html()));
-            return html;
-        } else {
-            html.push('>');
+    /// Converts the current `HtmlTag` to a XHTML string.
+    ///
+    /// This is the same as `to_html`, except that void elements
+    /// are self closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let div = HtmlTag::new("div").with_child(HtmlTag::new("br"));
+    ///
+    /// assert_eq!(div.to_xhtml(), "<div><br /></div>");
+    /// ```
+    pub fn to_xhtml(&self) -> String {
+        self.render(true)
+    }
+
+    /// Converts the current `HtmlTag` to an indented, multi line,
+    /// HTML string.
+    ///
+    /// Inline elements, like `span` or `a`, are never split across
+    /// lines, and the content of whitespace sensitive elements, like
+    /// `pre` or `textarea`, is kept exactly as it is. Custom elements
+    /// are laid out as blocks when they wrap blocks or sit among them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    /// use html_tag::pretty::PrettyOptions;
+    ///
+    /// let ul = HtmlTag::new("ul")
+    ///     .with_child(HtmlTag::new("li").with_body("One"))
+    ///     .with_child(
+    ///         HtmlTag::new("li")
+    ///             .with_text("Two ")
+    ///             .with_child(HtmlTag::new("b").with_body("bold")),
+    ///     );
+    ///
+    /// assert_eq!(
+    ///     ul.to_pretty_html(&PrettyOptions::default()),
+    ///     "<ul>\n  <li>One</li>\n  <li>Two <b>bold</b></li>\n</ul>"
+    /// );
+    /// ```
+    pub fn to_pretty_html(&self, options: &PrettyOptions) -> String {
+        let mut lines = Vec::new();
+        pretty::format_tag(self, options, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    /// Writes the current `HtmlTag` as HTML into the given writer.
+    ///
+    /// This streams the HTML directly into the writer, without
+    /// building any intermediate strings, which makes it a good
+    /// fit for large documents. `to_html` is built on top of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut html = String::from("<!DOCTYPE html>");
+    /// let body = HtmlTag::new("body").with_child(HtmlTag::new("p").with_body("Hello World"));
+    /// body.write_html(&mut html).unwrap();
+    ///
+    /// assert_eq!(html, "<!DOCTYPE html><body><p>Hello World</p></body>");
+    /// ```
+    pub fn write_html<W: Write>(&self, w: &mut W) -> std::fmt::Result {
+        self.write(w, false)
+    }
+
+    /// Writes the current `HtmlTag` as HTML into the given
+    /// `std::io::Write`, like a file or a socket.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let mut buffer = Vec::new();
+    /// let p = HtmlTag::new("p").with_body("Hello World");
+    /// p.write_html_io(&mut buffer).unwrap();
+    ///
+    /// assert_eq!(buffer, b"<p>Hello World</p>");
+    /// ```
+    ///
+    /// Wrap unbuffered writers in a `std::io::BufWriter`, as the HTML
+    /// is written in many small pieces.
+    pub fn write_html_io<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: w,
+            error: None,
+        };
+        match self.write_html(&mut adapter) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter
+                .error
+                .unwrap_or_else(|| std::io::Error::other("formatting error"))),
         }
+    }
 
-        if let Some(children) = &self.children {
-            for child in children {
-                html.push_str(&child.to_html());
-            }
+    pub(crate) fn write<W: Write>(&self, w: &mut W, xhtml: bool) -> std::fmt::Result {
+        if let Some(pre_content) = &self.pre_content {
+            w.write_str(pre_content)?;
+        }
+        self.write_element(w, xhtml)
+    }
+
+    pub(crate) fn write_element<W: Write>(&self, w: &mut W, xhtml: bool) -> std::fmt::Result {
+        self.write_opening_tag(w)?;
+
+        if self.tag_type.is_void() {
+            return w.write_str(if xhtml { " />" } else { ">" });
+        }
+
+        w.write_char('>')?;
+
+        for child in &self.children {
+            child.write(w, xhtml)?;
         }
 
-        html.push_str(&closing_tag);
+        w.write_str("</")?;
+        w.write_str(self.tag_type.as_str())?;
+        w.write_char('>')
+    }
+
+    /// Returns the exact length, in bytes, of the HTML that
+    /// `to_html` produces for the current `HtmlTag`.
+    ///
+    /// This walks the whole tree, without allocating, which costs
+    /// about as much as rendering it. It is useful to size a buffer
+    /// up front before calling `write_html` repeatedly, `to_html`
+    /// does not use it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::HtmlTag;
+    ///
+    /// let p = HtmlTag::new("p").with_class("test").with_body("a < b");
+    ///
+    /// assert_eq!(p.estimated_len(), p.to_html().len());
+    /// ```
+    pub fn estimated_len(&self) -> usize {
+        let mut counter = LenCounter(0);
+        self.write(&mut counter, false)
+            .expect("counting never fails");
+        counter.0
+    }
+
+    pub(crate) fn render(&self, xhtml: bool) -> String {
+        let mut html = String::new();
+        self.write(&mut html, xhtml)
+            .expect("writing to a String never fails");
+        html
+    }
 
+    pub(crate) fn render_element(&self, xhtml: bool) -> String {
+        let mut html = String::new();
+        self.write_element(&mut html, xhtml)
+            .expect("writing to a String never fails");
         html
     }
 
+    /// Checks the current `HtmlTag` and all its children for
+    /// void elements that have content.
+    ///
+    /// Such content is never rendered, this allows you to catch
+    /// it when the children were modified directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{HtmlTag, Node};
+    ///
+    /// let mut br = HtmlTag::new("br");
+    /// br.children.push(Node::text("oops"));
+    /// let div = HtmlTag::new("div").with_child(br);
+    ///
+    /// assert!(div.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), HtmlError> {
+        if self.tag_type.is_void() && !self.children.is_empty() {
+            return Err(HtmlError::VoidElementContent(self.tag_type.clone()));
+        }
+        for child in &self.children {
+            if let Node::Element(tag) = child {
+                tag.validate()?;
+            }
+        }
+        Ok(())
+    }
+
     /// Just a fancy name for `to_html`.
     pub fn construct(&self) -> String {
         self.to_html()
     }
 }
 
+/// Errors that can occur while building a `HtmlTag`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HtmlError {
+    /// Content was added to a void element, like `img` or `br`.
+    VoidElementContent(TagType),
+}
+
+impl Display for HtmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HtmlError::VoidElementContent(tag_type) => {
+                write!(f, "void element <{}> can not have content", tag_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HtmlError {}
+
+impl HtmlTag {
+    fn fmt_tree(&self, f: &mut std::fmt::Formatter<'_>, prefix: &str) -> std::fmt::Result {
+        self.write_opening_tag(f)?;
+        writeln!(f, ">")?;
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i + 1 == self.children.len();
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            write!(f, "{}{}", prefix, branch)?;
+            match child {
+                Node::Element(tag) => tag.fmt_tree(f, &format!("{}{}", prefix, indent))?,
+                Node::Text(text) => writeln!(f, "{:?}", text)?,
+                Node::Comment(comment) => writeln!(f, "<!--{}-->", comment)?,
+                Node::Raw(html) => writeln!(f, "Raw({:?})", html.as_str())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Prints the current `HtmlTag` as a tree, which is a lot
+/// easier to read than the rendered HTML.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::HtmlTag;
+///
+/// let div = HtmlTag::new("div")
+///     .with_id("main")
+///     .with_child(HtmlTag::new("p").with_body("Hello World"))
+///     .with_comment("note");
+///
+/// assert_eq!(
+///     format!("{:?}", div),
+///     "<div id=\"main\">\n├── <p>\n│   └── \"Hello World\"\n└── <!--note-->\n"
+/// );
+/// ```
+impl std::fmt::Debug for HtmlTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(pre_content) = &self.pre_content {
+            writeln!(f, "{:?}", pre_content)?;
+        }
+        self.fmt_tree(f, "")
+    }
+}
+
 impl Display for HtmlTag {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_html())
+        self.write_html(f)
+    }
+}
+
+/// A writer that only counts the bytes written to it.
+struct LenCounter(usize);
+
+impl Write for LenCounter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Forwards `std::fmt::Write` to a `std::io::Write`, keeping the
+/// actual `std::io::Error`, which `std::fmt::Error` can not carry.
+struct IoAdapter<'a, W: std::io::Write> {
+    inner: &'a mut W,
+    error: Option<std::io::Error>,
+}
+
+impl<W: std::io::Write> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            std::fmt::Error
+        })
     }
 }
@@ -52,9 +52,18 @@
 //! - Create HTML tags in a simple and intuitive way.
 //! - Chain methods to make it more readable.
 //! - Nest tags inside each other.
+//! - Mix text and tags inside a single tag.
 //! - Use custom tags.
+//! - Void elements without closing tags, or self closed in XHTML mode.
 //! - Use custom attributes.
+//! - Boolean attributes, like `disabled` or `checked`.
 //! - Use `Display` trait to print the HTML tag.
+//! - Pretty print the HTML tag with configurable indentation.
+//! - Automatic escaping of text and attribute values.
+//! - Parse HTML back into `HtmlTag`s, strictly or leniently like browsers do.
+//! - Build stylesheets with media queries and other nested at-rules, or parse them from CSS.
+//! - Optional typed CSS values, like lengths, colors and `calc()` expressions.
+//! - Typed selectors that compute their specificity, and applying stylesheets to `HtmlTag` trees.
 //! - No dependencies.
 //!
 //! ## Contributing
@@ -66,15 +75,32 @@
 //!
 //! This project is licensed under the MIT License.
 
+/// Attribute Related Stuff
+pub mod attributes;
+/// ClassList Related Stuff
+pub mod class_list;
+/// CSS Parsing Related Stuff
+pub mod css;
+/// Escaping Related Stuff
+pub mod escape;
 /// HTMLTag Related Stuff
 pub mod html;
+/// Node Related Stuff
+pub mod node;
+/// Parser Related Stuff
+pub mod parser;
 /// TagType Related Stuff
 pub mod tags;
 
+/// Pretty Printing Related Stuff
+pub mod pretty;
+
 /// StyleSheet Related Stuff
 pub mod styles;
 
+pub use crate::escape::RawHtml;
 pub use crate::html::HtmlTag;
+pub use crate::node::Node;
 pub use crate::styles::{Class, Style, StyleSheet};
 pub use crate::tags::TagType;
 
@@ -140,7 +166,7 @@ mod tests {
 
     #[test]
     fn test_styles_embed() {
-        let actual_html = "<style>.wow{color:red;font-family:sans-serif;font-size:20px;}h1{color:blue;font-size:30px;}</style><div id=\"wow\"><h1 class=\"wow\">Hello World</h1></div>";
+        let actual_html = "<style>.wow{color:red;font-size:20px;font-family:sans-serif;}h1{color:blue;font-size:30px;}</style><div id=\"wow\"><h1 class=\"wow\">Hello World</h1></div>";
         let mut style = styles::StyleSheet::new();
         style.add_style(".wow", "color", "red");
         style.add_style(".wow", "font-size", "20px");
@@ -161,6 +187,32 @@ mod tests {
         assert_eq!(div.to_html(), actual_html);
     }
 
+    #[test]
+    fn test_styles_embed_whitespace() {
+        let mut style = styles::StyleSheet::new();
+        style.add_style(".nav a", "font-family", "Times New Roman");
+        style.add_style(".nav a", "margin", "0 auto");
+        style.add_style(".grid", "grid-template-areas", "\"a  b\" \"c d\"");
+        style.add_style(".grid", "width", "calc(100% - 2em)");
+        style.add_style("ul > li + li", "content", "'\\2014 \\00A0'");
+        let div = html::HtmlTag::new("div").embed_style_sheet(&style);
+        assert_eq!(
+            div.pre_content.as_deref(),
+            Some(
+                "<style>.nav a{font-family:Times New Roman;margin:0 auto;}\
+                 .grid{grid-template-areas:\"a  b\" \"c d\";width:calc(100% - 2em);}\
+                 ul>li+li{content:'\\2014 \\00A0';}</style>"
+            )
+        );
+
+        let css = "@media screen and (min-width : 10px) {\n  a :hover { color : red }\n}\n\
+                   /* unused */ @import url(\"a.css\") ;";
+        assert_eq!(
+            styles::sanitize_styles(css.to_string()),
+            "@media screen and (min-width : 10px){a :hover{color:red}}@import url(\"a.css\");"
+        );
+    }
+
     #[test]
     fn test_href() {
         let actual_html = "<a href=\"https://www.google.com\">Hello World</a>";
@@ -236,19 +288,1288 @@ mod tests {
         assert_eq!(
             tags,
             vec![
-                tags::TagType::Div,
-                tags::TagType::H1,
-                tags::TagType::H2,
-                tags::TagType::H3,
-                tags::TagType::H4,
-                tags::TagType::H5,
-                tags::TagType::H6,
+                tags::TagType::Span,
+                tags::TagType::P,
                 tags::TagType::Img,
+                tags::TagType::H6,
+                tags::TagType::H5,
+                tags::TagType::H4,
+                tags::TagType::H3,
+                tags::TagType::H2,
+                tags::TagType::H1,
+                tags::TagType::Div,
                 tags::TagType::Custom("custom".to_string()),
                 tags::TagType::A,
-                tags::TagType::Span,
-                tags::TagType::P,
             ]
         );
     }
+
+    #[test]
+    fn escape_body_and_attributes() {
+        let actual_html =
+            "<p id=\"a&amp;b\" data-test=\"&quot;&gt;&lt;\">&lt;script&gt;alert('x')&lt;/script&gt;</p>";
+        let mut p = html::HtmlTag::new("p");
+        p.set_id("a&b");
+        p.add_attribute("data-test", "\"><");
+        p.set_body("<script>alert('x')</script>");
+        assert_eq!(p.to_html(), actual_html);
+
+        for name in [
+            "x onclick",
+            "x\"",
+            "x'",
+            "x>",
+            "x/",
+            "x=y",
+            "x\n",
+            "x\0",
+            "",
+        ] {
+            p.add_attribute(name, "1");
+            p.set_attribute(name, None);
+            p.append_attribute(name, "1");
+        }
+        assert_eq!(p.to_html(), actual_html);
+    }
+
+    #[test]
+    fn valueless_id_class_and_style() {
+        let mut div = html::HtmlTag::new("div")
+            .with_class("a b")
+            .with_style("color", "red");
+        div.add_attribute("class", None);
+        div.add_attribute("style", None);
+        div.add_attribute("id", None);
+        assert_eq!(
+            div.to_html(),
+            "<div class=\"a b\" style=\"color: red;\"></div>"
+        );
+
+        div.set_id("main");
+        div.add_attribute("id", None);
+        assert_eq!(div.get_attribute("id"), Some("main".to_string()));
+    }
+
+    #[test]
+    fn raw_body() {
+        let actual_html = "<div><b>Hello</b> World</div>";
+        let div = html::HtmlTag::new("div").with_raw_body(RawHtml::new("<b>Hello</b> World"));
+        assert_eq!(div.to_html(), actual_html);
+    }
+
+    #[test]
+    fn mixed_content() {
+        let actual_html = "<p>Hello <strong>world</strong>, welcome<!--end--></p>";
+        let p = html::HtmlTag::new("p")
+            .with_text("Hello ")
+            .with_child(html::HtmlTag::new("strong").with_body("world"))
+            .with_text(", welcome")
+            .with_comment("end");
+        assert_eq!(p.to_html(), actual_html);
+        assert_eq!(p.text_content(), "Hello world, welcome");
+    }
+
+    #[test]
+    fn comments_can_not_end_early() {
+        for (comment, html) in [
+            (">", "<!-- >-->"),
+            ("->x", "<!-- ->x-->"),
+            ("a--!>b", "<!--a- -!>b-->"),
+            ("x<!-", "<!--x<!- -->"),
+            ("-", "<!--- -->"),
+            ("x--->y", "<!--x- - ->y-->"),
+            ("--->", "<!--- - ->-->"),
+            ("a----b", "<!--a- - - -b-->"),
+        ] {
+            let node = node::Node::comment(comment);
+            assert_eq!(node.to_html(), html);
+            assert!(!html[4..html.len() - 3].contains("--"), "{:?}", comment);
+            let parsed = node::Node::parse(&format!("{}<p></p>", html)).unwrap();
+            assert_eq!(parsed.len(), 2, "{:?}", comment);
+            assert!(matches!(&parsed[0], node::Node::Comment(_)));
+        }
+    }
+
+    #[test]
+    fn set_body_replaces_children() {
+        let actual_html = "<div>Hello <b>World</b></div>";
+        let mut div = html::HtmlTag::new("div").with_child(html::HtmlTag::new("p"));
+        div.set_body("Hello ");
+        div.add_child(html::HtmlTag::new("b").with_body("World"));
+        assert_eq!(div.to_html(), actual_html);
+    }
+
+    #[test]
+    fn void_elements() {
+        let actual_html = "<div><img src=\"cat.png\"><br><input type=\"text\"></div>";
+        let div = html::HtmlTag::new("div")
+            .with_child(html::HtmlTag::new("img").with_attribute("src", "cat.png"))
+            .with_child(html::HtmlTag::new("br"))
+            .with_child(html::HtmlTag::new("input").with_attribute("type", "text"));
+        assert_eq!(div.to_html(), actual_html);
+        assert_eq!(
+            div.to_xhtml(),
+            "<div><img src=\"cat.png\" /><br /><input type=\"text\" /></div>"
+        );
+    }
+
+    #[test]
+    fn void_element_content_is_an_error() {
+        let mut meta = html::HtmlTag::new("meta");
+        assert_eq!(
+            meta.try_set_body("Hello"),
+            Err(html::HtmlError::VoidElementContent(tags::TagType::Meta))
+        );
+        assert!(meta.try_add_child(html::HtmlTag::new("p")).is_err());
+        assert_eq!(meta.to_html(), "<meta>");
+    }
+
+    #[test]
+    #[should_panic]
+    fn void_element_add_child_panics() {
+        html::HtmlTag::new("hr").add_child(html::HtmlTag::new("p"));
+    }
+
+    #[test]
+    fn boolean_attributes() {
+        let actual_html = "<input type=\"checkbox\" checked required>";
+        let mut input = html::HtmlTag::new("input")
+            .with_attribute("type", "checkbox")
+            .with_attribute("checked", None)
+            .with_boolean_attribute("required")
+            .with_boolean_attribute("disabled");
+        input.set_boolean_attribute("required");
+        input.unset_boolean_attribute("disabled");
+        assert_eq!(input.to_html(), actual_html);
+
+        assert!(!input.toggle_boolean_attribute("checked"));
+        assert!(input.toggle_boolean_attribute("hidden"));
+        assert_eq!(input.to_html(), "<input type=\"checkbox\" required hidden>");
+    }
+
+    #[test]
+    fn merged_styles() {
+        let actual_html = "<div style=\"font-size: 20px;margin: 0 auto;color: blue;\"></div>";
+        let mut font = styles::Class::new();
+        font.insert("font-size".to_string(), "20px".to_string());
+        font.insert("padding".to_string(), "1em".to_string());
+        let mut div = html::HtmlTag::new("div")
+            .with_style("color", "red")
+            .with_styles(font);
+        div.add_attribute("style", "margin: 0 auto; color: blue");
+        assert_eq!(div.remove_style("padding"), Some("1em".to_string()));
+        assert_eq!(div.get_style("color"), Some("blue"));
+        assert_eq!(div.to_html(), actual_html);
+
+        let mut div = html::HtmlTag::new("div");
+        div.set_style("background-color", "blue");
+        div.set_style("background", "none");
+        let actual_html = "<div style=\"background-color: blue;background: none;\"></div>";
+        assert_eq!(div.to_html(), actual_html);
+        let parsed = html::HtmlTag::parse(actual_html).unwrap();
+        assert_eq!(parsed[0].to_html(), actual_html);
+    }
+
+    #[test]
+    fn attribute_map() {
+        let actual_html = "<a id=\"home\" class=\"link\" style=\"color: red;\" href=\"/about\" data-test=\"test\" rel=\"noopener noreferrer\"></a>";
+        let mut a = html::HtmlTag::new("a")
+            .with_href("/home")
+            .with_style("color", "red")
+            .with_attribute("target", "_blank");
+        a.add_custom(vec![
+            ("id", "home"),
+            ("class", "link"),
+            ("data-test", "test"),
+        ]);
+        a.set_attribute("href", "/about");
+        a.append_attribute("rel", "noopener");
+        a.append_attribute("rel", "noreferrer noopener");
+        assert!(a.remove_attribute("target"));
+        assert!(!a.remove_attribute("target"));
+        assert!(a.has_attribute("style"));
+        assert_eq!(
+            a.get_attribute("rel"),
+            Some("noopener noreferrer".to_string())
+        );
+        assert_eq!(
+            a.attributes.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            vec!["href", "data-test", "rel"]
+        );
+        assert_eq!(a.to_html(), actual_html);
+    }
+
+    #[test]
+    fn class_list_operations() {
+        let actual_html = "<div class=\"card card-large selected\"></div>";
+        let mut div = html::HtmlTag::new("div")
+            .with_class("card card-small")
+            .with_class("card")
+            .with_class_if(true, "selected")
+            .with_class_if(false, "disabled");
+        assert!(div.replace_class("card-small", "card-large"));
+        assert!(div.toggle_class("hidden"));
+        assert!(!div.toggle_class("hidden"));
+        assert!(!div.toggle_class("not valid"));
+        div.add_class("temp");
+        div.remove_class("temp");
+        assert!(div.has_class("selected"));
+        assert!(!div.has_class("temp"));
+        assert_eq!(div.to_html(), actual_html);
+    }
+
+    #[test]
+    fn standard_tags_round_trip() {
+        assert_eq!(tags::TagType::STANDARD.len(), 114);
+        for tag in tags::TagType::STANDARD {
+            assert!(!tag.is_custom());
+            assert_eq!(&tags::TagType::from(&tag.html().to_uppercase()), tag);
+        }
+        assert_eq!(tags::TagType::from("section"), tags::TagType::Section);
+        assert_eq!(tags::TagType::from("tbody"), tags::TagType::Tbody);
+        assert_eq!(
+            tags::TagType::from("my-element"),
+            tags::TagType::Custom("my-element".to_string())
+        );
+    }
+
+    #[test]
+    fn tag_categories() {
+        use tags::TagType;
+        assert!(TagType::Script.is_raw_text() && TagType::Style.is_raw_text());
+        assert!(TagType::Textarea.is_escapable_raw_text() && !TagType::P.is_raw_text());
+        assert!(TagType::Em.is_phrasing() && TagType::Em.is_flow());
+        assert!(TagType::Section.is_flow() && !TagType::Section.is_phrasing());
+        assert!(TagType::Section.is_sectioning() && !TagType::Div.is_sectioning());
+        assert!(TagType::H3.is_heading() && TagType::Hgroup.is_heading());
+        assert!(TagType::Input.is_form_associated() && !TagType::Div.is_form_associated());
+        assert!(!TagType::Head.is_flow() && !TagType::Li.is_flow());
+        assert!(TagType::from("my-element").is_phrasing());
+    }
+
+    #[test]
+    fn raw_text_elements_are_escaped_unless_raw() {
+        let actual_html = "<div><script>if (a < b && c) {}</script><title>a &lt; b</title></div>";
+        let div = html::HtmlTag::new("div")
+            .with_child(
+                html::HtmlTag::new("script").with_raw_body(RawHtml::new("if (a < b && c) {}")),
+            )
+            .with_child(html::HtmlTag::new("title").with_body("a < b"));
+        assert_eq!(div.to_html(), actual_html);
+
+        let script =
+            html::HtmlTag::new("script").with_body("'</script><img src=x onerror=alert(1)>'");
+        assert_eq!(
+            script.to_html(),
+            "<script>'&lt;/script&gt;&lt;img src=x onerror=alert(1)&gt;'</script>"
+        );
+    }
+
+    #[test]
+    fn tag_type_maps() {
+        use std::collections::{BTreeMap, HashSet};
+        use tags::TagType;
+
+        let mut map = BTreeMap::new();
+        map.insert(TagType::Table, 1);
+        map.insert(TagType::Td, 2);
+        map.insert(TagType::Custom("x-a".to_string()), 3);
+        map.insert(TagType::Custom("x-b".to_string()), 4);
+        map.insert(TagType::Custom("p".to_string()), 5);
+        map.insert(TagType::P, 6);
+        assert_eq!(map.len(), 6);
+        assert_eq!(map[&TagType::Table], 1);
+        assert_eq!(map[&TagType::Td], 2);
+        assert_eq!(map[&TagType::Custom("x-b".to_string())], 4);
+        assert!(TagType::P < TagType::Custom("p".to_string()));
+
+        let set: HashSet<TagType> = TagType::STANDARD.iter().cloned().collect();
+        assert_eq!(set.len(), TagType::STANDARD.len());
+    }
+
+    #[test]
+    fn debug_tree() {
+        let div = html::HtmlTag::new("div")
+            .with_class("test")
+            .with_child(
+                html::HtmlTag::new("p")
+                    .with_text("Hello ")
+                    .with_child(html::HtmlTag::new("b").with_body("World")),
+            )
+            .with_child(html::HtmlTag::new("br"));
+        assert_eq!(
+            format!("{:?}", div),
+            "<div class=\"test\">\n├── <p>\n│   ├── \"Hello \"\n│   └── <b>\n│       └── \"World\"\n└── <br>\n"
+        );
+    }
+
+    #[test]
+    fn pretty_html() {
+        let actual_html = "<div id=\"main\">
+    <h1>Title</h1>
+    <p>
+        Some text with a <a href=\"https://example.com\">long link</a>, that has
+        to wrap <span>because it is</span> way too long.
+    </p>
+    <pre>  keep
+    this  </pre>
+    <br>
+</div>";
+        let div = html::HtmlTag::new("div")
+            .with_id("main")
+            .with_child(html::HtmlTag::new("h1").with_body("Title"))
+            .with_child(
+                html::HtmlTag::new("p")
+                    .with_text("Some text  with a ")
+                    .with_child(
+                        html::HtmlTag::new("a")
+                            .with_href("https://example.com")
+                            .with_body("long link"),
+                    )
+                    .with_text(", that has to wrap ")
+                    .with_child(html::HtmlTag::new("span").with_body("because it is"))
+                    .with_text(" way too long."),
+            )
+            .with_child(html::HtmlTag::new("pre").with_body("  keep\n    this  "))
+            .with_child(html::HtmlTag::new("br"));
+        let options = pretty::PrettyOptions::new()
+            .with_indent(pretty::Indent::Spaces(4))
+            .with_max_line_width(80);
+        assert_eq!(div.to_pretty_html(&options), actual_html);
+
+        let card = html::HtmlTag::new("my-card")
+            .with_child(html::HtmlTag::new("h2").with_body("Title"))
+            .with_child(html::HtmlTag::new("my-icon"));
+        let section = html::HtmlTag::new("section")
+            .with_text("Intro")
+            .with_child(card);
+        assert_eq!(
+            section.to_pretty_html(&options),
+            "<section>
+    Intro
+    <my-card>
+        <h2>Title</h2>
+        <my-icon></my-icon>
+    </my-card>
+</section>"
+        );
+
+        let p = html::HtmlTag::new("p")
+            .with_text("Hello ")
+            .with_child(html::HtmlTag::new("my-icon").with_body("*"))
+            .with_text(" world");
+        assert_eq!(
+            p.to_pretty_html(&options),
+            "<p>Hello <my-icon>*</my-icon> world</p>"
+        );
+        let div = html::HtmlTag::new("div")
+            .with_child(html::HtmlTag::new("p").with_body("Text"))
+            .with_child(html::HtmlTag::new("my-icon").with_body("*"));
+        assert_eq!(
+            div.to_pretty_html(&options),
+            "<div>\n    <p>Text</p>\n    <my-icon>*</my-icon>\n</div>"
+        );
+    }
+
+    #[test]
+    fn streaming_rendering() {
+        let actual_html = "<ul class=\"list\"><li>1 &lt; 2</li><li>Item 2</li></ul>";
+        let ul = html::HtmlTag::new("ul")
+            .with_class("list")
+            .with_child(html::HtmlTag::new("li").with_body("1 < 2"))
+            .with_child(html::HtmlTag::new("li").with_body("Item 2"));
+
+        let mut html = String::new();
+        ul.write_html(&mut html).unwrap();
+        assert_eq!(html, actual_html);
+
+        let mut buffer = Vec::new();
+        ul.write_html_io(&mut buffer).unwrap();
+        assert_eq!(buffer, actual_html.as_bytes());
+
+        let mut too_small = [0u8; 10];
+        let err = ul.write_html_io(&mut too_small.as_mut_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn estimated_len_is_exact() {
+        let div = html::HtmlTag::new("div")
+            .with_id("a\"b")
+            .with_class("x y")
+            .with_style("color", "red")
+            .with_boolean_attribute("hidden")
+            .with_child(html::HtmlTag::new("p").with_body("1 < 2 & 3 > 2 — ünïcode"))
+            .with_child(html::HtmlTag::new("br"))
+            .with_child(html::HtmlTag::new("script").with_body("a < b"))
+            .with_comment("x--y")
+            .with_child(RawHtml::new("<b>raw</b>"));
+        assert_eq!(div.estimated_len(), div.to_html().len());
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let source = "<div id=\"main\" class=\"a b\" style=\"color: red;\" data-x=1 hidden>\
+<p>Tea &amp; cake &lt;3 &copy; &#x263A;</p><img src='a.png' alt=\"\"><br/>\
+<!-- note --><script>if (a < b && c) {}</script><textarea>&lt;b&gt;</textarea>\
+<my-icon name=\"x\" /></div>";
+        let tags = html::HtmlTag::parse(source).unwrap();
+        assert_eq!(tags.len(), 1);
+        let div = &tags[0];
+        assert_eq!(div.id.as_deref(), Some("main"));
+        assert!(div.has_class("b"));
+        assert_eq!(div.get_style("color"), Some("red"));
+        assert_eq!(div.attributes.get("data-x"), Some("1"));
+        assert_eq!(div.attributes.get("hidden"), Some(""));
+        assert_eq!(
+            div.children[0].as_element().unwrap().text_content(),
+            "Tea & cake <3 © ☺"
+        );
+        assert_eq!(
+            div.to_html(),
+            "<div id=\"main\" class=\"a b\" style=\"color: red;\" data-x=\"1\" hidden>\
+<p>Tea &amp; cake &lt;3 © ☺</p><img src=\"a.png\" alt=\"\"><br>\
+<!-- note --><script>if (a < b && c) {}</script><textarea>&lt;b&gt;</textarea>\
+<my-icon name=\"x\"></my-icon></div>"
+        );
+
+        let again = html::HtmlTag::parse(&div.to_html()).unwrap();
+        assert_eq!(&again[0], div);
+
+        let tags =
+            html::HtmlTag::parse("<!DOCTYPE html>\n<P CLASS=x>a</p>\n<!-- c -->\n<p>b</p>\n")
+                .unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].tag_type, tags::TagType::P);
+
+        let nodes = node::Node::parse("a <b>b</b> c").unwrap();
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[2].as_text(), Some(" c"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        use parser::ParseErrorKind;
+
+        let cases = [
+            (
+                "<div><p>x</div>",
+                ParseErrorKind::MismatchedEndTag {
+                    expected: "p".to_string(),
+                    found: "div".to_string(),
+                },
+                1,
+                10,
+            ),
+            (
+                "<div>\n  </span>",
+                ParseErrorKind::UnexpectedEndTag("span".to_string()),
+                2,
+                3,
+            ),
+            (
+                "<div>\n<p>",
+                ParseErrorKind::UnclosedTag("p".to_string()),
+                2,
+                1,
+            ),
+            ("<div class=\"x>", ParseErrorKind::UnexpectedEof, 1, 6),
+            ("<!-- open", ParseErrorKind::UnterminatedComment, 1, 1),
+            (
+                "<a href=x\"y>",
+                ParseErrorKind::InvalidAttribute("href".to_string()),
+                1,
+                10,
+            ),
+            ("<p></p>\n  hello", ParseErrorKind::TextOutsideElement, 2, 3),
+        ];
+        for (source, kind, line, column) in cases {
+            let error = html::HtmlTag::parse(source).unwrap_err();
+            assert_eq!(
+                error,
+                parser::ParseError { kind, line, column },
+                "{}",
+                source
+            );
+        }
+        assert!(html::HtmlTag::parse("a < b").is_err());
+        assert_eq!(
+            node::Node::parse("a < b").unwrap(),
+            vec![node::Node::text("a < b")]
+        );
+    }
+
+    /// Writes a node in the format of the html5lib tree construction tests.
+    fn html5lib_tree(node: &node::Node, depth: usize, namespace: &str, lines: &mut Vec<String>) {
+        let indent = format!("| {}", "  ".repeat(depth));
+        match node {
+            node::Node::Element(tag) => {
+                let name = tag.tag_type.as_str();
+                let namespace = match (namespace, name) {
+                    ("html", "svg" | "math") => name,
+                    ("annotation-xml", "svg") => "svg",
+                    ("annotation-xml", _) => "math",
+                    _ => namespace,
+                };
+                match namespace {
+                    "html" => lines.push(format!("{}<{}>", indent, name)),
+                    _ => lines.push(format!("{}<{} {}>", indent, namespace, name)),
+                }
+
+                let mut attributes = tag
+                    .attributes
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.unwrap_or("").to_string()))
+                    .collect::<Vec<_>>();
+                for key in ["id", "class", "style"] {
+                    if let Some(value) = tag.get_attribute(key) {
+                        attributes.push((key.to_string(), value));
+                    }
+                }
+                attributes.sort();
+                for (key, value) in attributes {
+                    lines.push(format!("{}  {}=\"{}\"", indent, key, value));
+                }
+
+                let namespace = match (namespace, name) {
+                    ("svg", "foreignObject" | "desc" | "title") => "html",
+                    ("math", "mi" | "mo" | "mn" | "ms" | "mtext") => "html",
+                    // Where only `svg` starts a new namespace.
+                    ("math", "annotation-xml") => "annotation-xml",
+                    _ => namespace,
+                };
+                for child in &tag.children {
+                    html5lib_tree(child, depth + 1, namespace, lines);
+                }
+            }
+            node::Node::Text(text) => lines.push(format!("{}\"{}\"", indent, text)),
+            node::Node::Comment(comment) => lines.push(format!("{}<!-- {} -->", indent, comment)),
+            // The text of `script` and `style`.
+            node::Node::Raw(html) => lines.push(format!("{}\"{}\"", indent, html)),
+        }
+    }
+
+    /// Runs the vendored subset of the html5lib tree construction tests,
+    /// from https://github.com/html5lib/html5lib-tests.
+    #[test]
+    fn html5lib_tree_construction() {
+        let data = include_str!("../tests/html5lib/tree_construction.dat");
+        let mut count = 0;
+        for case in data.split("#data\n").skip(1) {
+            let (input, rest) = case.split_once("\n#errors\n").unwrap();
+            let (_, rest) = rest.split_once("#document").unwrap();
+            let (fragment, expected) = match rest.strip_prefix("-fragment\n") {
+                Some(rest) => {
+                    let (context, expected) = rest.split_once("\n#document\n").unwrap();
+                    (Some(context), expected)
+                }
+                None => (None, rest.strip_prefix('\n').unwrap()),
+            };
+
+            let mut lines = Vec::new();
+            match fragment {
+                Some(context) => {
+                    for node in parser::parse_fragment(input, context) {
+                        html5lib_tree(&node, 0, "html", &mut lines);
+                    }
+                }
+                None => {
+                    let (doctype, nodes) = parser::parse_document(input);
+                    if let Some(name) = doctype {
+                        lines.push(format!("| <!DOCTYPE {}>", name));
+                    }
+                    for node in nodes {
+                        html5lib_tree(&node, 0, "html", &mut lines);
+                    }
+                }
+            }
+            assert_eq!(
+                lines.join("\n"),
+                expected.trim_end_matches('\n'),
+                "{}",
+                input
+            );
+            count += 1;
+        }
+        assert_eq!(count, 50);
+
+        let html = html::HtmlTag::parse_document("<!DOCTYPE html><!--a--><p>x<!--b-->");
+        assert_eq!(html.pre_content.as_deref(), Some("<!DOCTYPE html><!--a-->"));
+        assert_eq!(
+            html.to_html(),
+            "<!DOCTYPE html><!--a--><html><head></head><body><p>x<!--b--></p></body></html>"
+        );
+    }
+
+    #[test]
+    fn foreign_names_keep_their_case() {
+        let source = "<svg viewBox=\"0 0 10 10\" preserveAspectRatio=\"none\">\
+<clipPath id=\"c\"><rect/></clipPath><foreignObject><p viewBox=\"x\"></p></foreignObject></svg>\
+<math definitionURL=\"u\"><clipPath></clipPath></math>";
+        let expected = "<svg viewBox=\"0 0 10 10\" preserveAspectRatio=\"none\">\
+<clipPath id=\"c\"><rect></rect></clipPath><foreignObject><p viewbox=\"x\"></p></foreignObject></svg>\
+<math definitionURL=\"u\"><clippath></clippath></math>";
+
+        let lenient = html::HtmlTag::parse_lenient(source);
+        let html: String = lenient.iter().map(|tag| tag.to_html()).collect();
+        assert_eq!(html, expected);
+
+        let strict = html::HtmlTag::parse(source).unwrap();
+        let html: String = strict.iter().map(|tag| tag.to_html()).collect();
+        assert_eq!(html, expected);
+        assert_eq!(html::HtmlTag::parse(&html).unwrap(), strict);
+    }
+
+    #[test]
+    fn foreign_style_text_stays_escaped() {
+        for root in ["svg", "math"] {
+            let source = format!(
+                "<{0}><style>&lt;/style&gt;&lt;img src=x onerror=alert(1)&gt;</style></{0}>",
+                root
+            );
+            let lenient = html::HtmlTag::parse_lenient(&source);
+            let html: String = lenient.iter().map(|tag| tag.to_html()).collect();
+            assert_eq!(html, source);
+
+            let strict = html::HtmlTag::parse(&source).unwrap();
+            let html: String = strict.iter().map(|tag| tag.to_html()).collect();
+            assert_eq!(html, source);
+        }
+
+        let lenient = html::HtmlTag::parse_lenient("<style>a > b {}</style>");
+        assert_eq!(lenient[0].to_html(), "<style>a > b {}</style>");
+    }
+
+    #[test]
+    fn parse_style_sheet() {
+        use css::{CssParseError, CssParseErrorKind};
+
+        let sheet = StyleSheet::parse(
+            r#"@charset "utf-8";
+            /* Base styles */
+            :is(h1, h2) > a,
+            .nav   a:hover {
+                COLOR: Red;
+                background: url(data:image/png;base64,iVBOR) no-repeat;
+                content: "} { ; /* not a comment */";
+            }
+            .nav a:hover { color: blue !important; --Brand-Color: #333 }
+            .nav a:hover { color: green; margin: 0
+                auto }
+            @font-face { font-family: "Open Sans"; src: url("/fonts/open-sans.woff2") }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sheet
+                .iter()
+                .map(|rule| rule.selector.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                ":is(h1, h2) > a",
+                ".nav a:hover",
+                ".nav a:hover",
+                ".nav a:hover",
+                "@font-face"
+            ]
+        );
+        let hover: Vec<_> = sheet.iter().skip(1).take(3).collect();
+        assert_eq!(
+            hover[0]["background"],
+            "url(data:image/png;base64,iVBOR) no-repeat"
+        );
+        assert_eq!(hover[0]["content"], "\"} { ; /* not a comment */\"");
+        assert_eq!(hover[1]["color"], "blue !important");
+        assert_eq!(hover[1]["--Brand-Color"], "#333");
+        assert_eq!(hover[2]["color"], "green");
+        assert_eq!(hover[2]["margin"], "0 auto");
+
+        let css = ".a{color:red;}.b{color:blue;}.a{color:green;}@media print{.a{color:red;}}\
+@media print{.b{color:red;}}";
+        let ordered = StyleSheet::parse(css).unwrap();
+        assert_eq!(styles::sanitize_styles(ordered.get_style_sheet()), css);
+        assert_eq!(sheet[":is(h1, h2) > a"]["color"], "Red");
+        assert_eq!(sheet["@font-face"]["font-family"], "\"Open Sans\"");
+
+        let statements = StyleSheet::parse(
+            "@import url(a.css) print;\n@namespace svg url(http://www.w3.org/2000/svg);\n\
+             a { b: c }\n@layer base",
+        )
+        .unwrap();
+        assert_eq!(
+            statements.items().collect::<Vec<_>>(),
+            [
+                &styles::StyleItem::Statement("@import url(a.css) print".to_string()),
+                &styles::StyleItem::Statement(
+                    "@namespace svg url(http://www.w3.org/2000/svg)".to_string()
+                ),
+                &styles::StyleItem::Rule(statements["a"].clone()),
+                &styles::StyleItem::Statement("@layer base".to_string()),
+            ]
+        );
+        assert_eq!(
+            statements.get_style_sheet(),
+            "@import url(a.css) print;\n@namespace svg url(http://www.w3.org/2000/svg);\n\
+             a {\n    b: c;\n}\n@layer base;\n"
+        );
+        assert_eq!(
+            StyleSheet::parse(&statements.get_style_sheet()).unwrap(),
+            statements
+        );
+
+        let cases = [
+            (
+                "a { color: red }\n/* open",
+                CssParseErrorKind::UnterminatedComment,
+                2,
+                1,
+            ),
+            (
+                "a { content: \"open\n}",
+                CssParseErrorKind::UnterminatedString,
+                1,
+                14,
+            ),
+            (
+                "a { color: red }\n}",
+                CssParseErrorKind::UnexpectedChar('}'),
+                2,
+                1,
+            ),
+            ("a {\n  color: red;", CssParseErrorKind::UnexpectedEof, 1, 3),
+            (
+                "a, , b { color: red }",
+                CssParseErrorKind::EmptySelector,
+                1,
+                1,
+            ),
+            (
+                "a { : red }",
+                CssParseErrorKind::InvalidDeclaration(String::new()),
+                1,
+                5,
+            ),
+            (
+                "a { color: }",
+                CssParseErrorKind::InvalidDeclaration("color".to_string()),
+                1,
+                5,
+            ),
+        ];
+        for (css, kind, line, column) in cases {
+            let error = StyleSheet::parse(css).unwrap_err();
+            assert_eq!(error, CssParseError { kind, line, column }, "{}", css);
+        }
+    }
+
+    #[test]
+    fn parse_inline_styles() {
+        use css::{CssParseError, CssParseErrorKind};
+        use styles::{convert_to_styles, parse_styles};
+
+        let class = parse_styles(
+            "Color: red; /* note */ background: url(a;b.png) ; --Gap: 4px;; margin: 0 !important",
+        )
+        .unwrap();
+        assert_eq!(class["color"], "red");
+        assert_eq!(class["background"], "url(a;b.png)");
+        assert_eq!(class["--Gap"], "4px");
+        assert_eq!(class["margin"], "0 !important");
+        assert_eq!(
+            parse_styles(&convert_to_styles(class.clone())).unwrap(),
+            class
+        );
+
+        let error = parse_styles("color: red;\nwidth 10px").unwrap_err();
+        assert_eq!(
+            error,
+            CssParseError {
+                kind: CssParseErrorKind::InvalidDeclaration("width 10px".to_string()),
+                line: 2,
+                column: 1,
+            }
+        );
+        assert!(parse_styles("color: red }").is_err());
+
+        // Attributes drop malformed declarations instead, like browsers.
+        let div = HtmlTag::new("div").with_attribute(
+            "style",
+            "color: red; width 10px; content: \"a;b\"; }; height: 0",
+        );
+        assert_eq!(div.styles().len(), 3);
+        assert_eq!(div.styles()["content"], "\"a;b\"");
+        assert_eq!(div.get_style("height"), Some("0"));
+        let div = HtmlTag::new("div").with_attribute("style", "color: blue; content: \"open");
+        assert_eq!(div.styles().len(), 1);
+        assert_eq!(div.get_style("color"), Some("blue"));
+    }
+
+    #[test]
+    fn style_sheet_at_rules() {
+        let mut sheet = StyleSheet::new();
+        sheet.add_style(".nav a", "color", "blue");
+        sheet
+            .media("screen and (max-width: 600px)", |s| {
+                s.add_style(".nav a", "display", "block");
+                s.container("sidebar (min-width: 20em)", |s| {
+                    s.add_style(".card", "grid-template-columns", "1fr 1fr");
+                });
+            })
+            .layer("base", |s| s.add_style("p", "margin", "0 0 1em"))
+            .media("screen and (max-width: 600px)", |s| {
+                s.add_style(".nav a", "padding", "1em 0");
+            });
+
+        assert_eq!(sheet.len(), 3);
+        let media = sheet
+            .get_at_rule("media", "screen and (max-width: 600px)")
+            .unwrap();
+        assert_eq!(media.rules[".nav a"]["padding"], "1em 0");
+        assert_eq!(
+            sheet.get_style_sheet(),
+            ".nav a {
+    color: blue;
+}
+@media screen and (max-width: 600px) {
+    .nav a {
+        display: block;
+        padding: 1em 0;
+    }
+    @container sidebar (min-width: 20em) {
+        .card {
+            grid-template-columns: 1fr 1fr;
+        }
+    }
+}
+@layer base {
+    p {
+        margin: 0 0 1em;
+    }
+}
+"
+        );
+
+        let div = HtmlTag::new("div").embed_style_sheet(&sheet);
+        assert_eq!(
+            div.pre_content.as_deref(),
+            Some(
+                "<style>.nav a{color:blue;}\
+                 @media screen and (max-width: 600px){.nav a{display:block;padding:1em 0;}\
+                 @container sidebar (min-width: 20em){.card{grid-template-columns:1fr 1fr;}}}\
+                 @layer base{p{margin:0 0 1em;}}</style>"
+            )
+        );
+
+        // Parsing and rendering gives back the same stylesheet.
+        assert_eq!(StyleSheet::parse(&sheet.get_style_sheet()).unwrap(), sheet);
+        let parsed = StyleSheet::parse(
+            "@supports (display: grid) { @media print { .a { color: red } } } @layer { b { x: y } }",
+        )
+        .unwrap();
+        let supports = parsed.get_at_rule("supports", "(display: grid)").unwrap();
+        let print = supports.rules.get_at_rule("media", "print").unwrap();
+        assert_eq!(print.rules[".a"]["color"], "red");
+        assert_eq!(
+            parsed.get_at_rule("layer", "").unwrap().rules["b"]["x"],
+            "y"
+        );
+    }
+
+    #[test]
+    fn style_sheet_keyframes_font_face_custom_properties() {
+        let mut sheet = StyleSheet::new();
+        sheet.add_custom_property(":root", "brand", "#f00");
+        sheet.font_face("Brand \"Sans\"", "url(/brand.woff2)");
+        sheet
+            .font_face("Brand \"Sans\"", "url(/brand-bold.woff2)")
+            .set("font-weight", "700");
+        sheet.keyframes("pulse", |k| {
+            k.add_style("from, to", "opacity", "1");
+            k.add_style("50%", "opacity", "0.5");
+        });
+        sheet.add_style(".badge", "animation", "pulse 2s infinite");
+        sheet.add_style(".badge", "color", "var(--brand)");
+
+        assert_eq!(
+            sheet.get_style_sheet(),
+            r#":root {
+    --brand: #f00;
+}
+@font-face {
+    font-family: "Brand \"Sans\"";
+    src: url(/brand.woff2);
+}
+@font-face {
+    font-family: "Brand \"Sans\"";
+    src: url(/brand-bold.woff2);
+    font-weight: 700;
+}
+@keyframes pulse {
+    from, to {
+        opacity: 1;
+    }
+    50% {
+        opacity: 0.5;
+    }
+}
+.badge {
+    animation: pulse 2s infinite;
+    color: var(--brand);
+}
+"#
+        );
+
+        let parsed = StyleSheet::parse(&sheet.get_style_sheet()).unwrap();
+        assert_eq!(parsed.len(), 5);
+        assert_eq!(parsed[":root"].get_custom_property("brand"), Some("#f00"));
+        let pulse = parsed.get_at_rule("keyframes", "pulse").unwrap();
+        assert_eq!(pulse.rules["to"]["opacity"], "1");
+        assert_eq!(
+            parsed
+                .iter()
+                .filter(|rule| rule.selector == "@font-face")
+                .count(),
+            2
+        );
+
+        let mut div = HtmlTag::new("div")
+            .with_attribute("style", "--Gap: 4px; gap: var(--Gap)")
+            .with_custom_property("--brand", "#00f");
+        assert_eq!(div.get_custom_property("Gap"), Some("4px"));
+        assert_eq!(div.get_custom_property("gap"), None);
+        div.set_custom_property("brand", " #0f0 ");
+        assert_eq!(
+            div.to_html(),
+            "<div style=\"--Gap: 4px;gap: var(--Gap);--brand: #0f0;\"></div>"
+        );
+    }
+
+    #[test]
+    fn typed_style_values() {
+        use styles::{Calc, Color, Length, LengthUnit, Percent, ValueError};
+
+        let mut sheet = StyleSheet::new();
+        sheet.add_custom_property(":root", "gutter", Length::rem(1));
+        sheet.add_style(".grid", "gap", Length::new(0.5, LengthUnit::Vmin));
+        sheet.add_style(".grid", "margin", Length::zero());
+        sheet.add_style(
+            ".grid",
+            "width",
+            (Percent(100.0) - Length::px(240)) * 0.5 + Length::em(-1),
+        );
+        sheet.add_style(
+            ".grid",
+            "height",
+            Length::vh(100) - (Length::px(64) - Percent(10.0)),
+        );
+        sheet.add_style(".grid", "border-color", Color::hex("#ABCDEF80").unwrap());
+        sheet.add_style(".grid", "outline-color", Color::current_color());
+        sheet.add_style(".grid", "opacity", 0.75);
+        let grid = &sheet[".grid"];
+        assert_eq!(sheet[":root"]["--gutter"], "1rem");
+        assert_eq!(grid["gap"], "0.5vmin");
+        assert_eq!(grid["margin"], "0");
+        assert_eq!(grid["width"], "calc((100% - 240px) * 0.5 + -1em)");
+        assert_eq!(grid["height"], "calc(100vh - (64px - 10%))");
+        assert_eq!(grid["border-color"], "#abcdef80");
+        assert_eq!(grid["outline-color"], "currentcolor");
+        assert_eq!(grid["opacity"], "0.75");
+
+        assert_eq!(
+            Calc::from(Length::px(2)) * 3.0 / 4.0,
+            Calc::Div(
+                Box::new(Calc::Mul(Box::new(Calc::Length(Length::px(2))), 3.0)),
+                4.0
+            )
+        );
+        assert_eq!((Length::px(3) / 2.0).to_string(), "1.5px");
+        assert_eq!((Length::zero() * 4.0).to_string(), "0");
+        assert_eq!(
+            (Length::zero() + Length::px(1)).to_string(),
+            "calc(0px + 1px)"
+        );
+        assert_eq!(
+            (Percent(50.0) - Length::zero() * 2.0).to_string(),
+            "calc(50% - 0px)"
+        );
+        assert_eq!(
+            (Percent(10.0) * 2.5 - Length::px(1)).to_string(),
+            "calc(25% - 1px)"
+        );
+        assert_eq!(
+            (Length::em(1) / 0.0).to_string(),
+            Length::em(f64::MAX).to_string()
+        );
+        assert_eq!(Length::px(f64::NEG_INFINITY).value, f64::MIN);
+        assert_eq!(Percent::new(f64::NAN).to_string(), "0%");
+        assert_eq!(Percent(f64::NAN).to_string(), "0%");
+        assert_eq!(
+            (Length::px(1) + Percent(1.0)) * f64::NAN,
+            Calc::Mul(Box::new(Length::px(1) + Percent(1.0)), 0.0)
+        );
+        assert_eq!(
+            Color::rgba(0, 0, 0, f64::NAN).to_string(),
+            "rgba(0, 0, 0, 0)"
+        );
+        assert_eq!(
+            Color::hsl(f64::INFINITY, f64::NAN, 50).to_string(),
+            Color::hsl(f64::MAX, 0, 50).to_string()
+        );
+        assert_eq!(Color::rgba(1, 2, 3, 1.5).to_string(), "rgba(1, 2, 3, 1)");
+        assert_eq!(Color::hsl(-30, 120, 50).to_string(), "hsl(-30, 100%, 50%)");
+        assert_eq!(Color::transparent().to_string(), "transparent");
+        for hex in ["", "#", "#ff", "#ff000", "#fffffff", "#ggg", "##fff"] {
+            let error = Color::hex(hex).unwrap_err();
+            assert_eq!(error, ValueError::InvalidHexColor(hex.to_string()));
+        }
+        assert_eq!(
+            Color::hex("12").unwrap_err().to_string(),
+            "invalid hex color `12`"
+        );
+
+        let div = HtmlTag::new("div")
+            .with_style("padding", Length::px(8))
+            .with_custom_property("accent", Color::rgb(0, 128, 255));
+        assert_eq!(
+            div.to_html(),
+            "<div style=\"padding: 8px;--accent: rgb(0, 128, 255);\"></div>"
+        );
+    }
+
+    #[test]
+    fn validate_style_sheet() {
+        use styles::{Property, ValidationError, ValidationErrorKind};
+
+        let mut sheet = StyleSheet::parse(
+            r#".a {
+                DISPLAY: Inline Flex;
+                --Theme-Color: whatever;
+                -moz-osx-font-smoothing: grayscale;
+                font-weight: 700;
+                vertical-align: -2px;
+                text-overflow: "…  …";
+                position: inherit !important;
+                overflow: hidden auto;
+            }
+            @font-face { font-family: x; font-display: swap }
+            @supports (display: grid) {
+                @media print {
+                    .b { font-wieght: bold; float: center; clear: var(--c) }
+                }
+            }
+            @keyframes fade { to { opacity: 0; visibility: hiden } }"#,
+        )
+        .unwrap();
+        sheet.add_style(".c", Property::BoxSizing, "border");
+        sheet.add_style(".c", Property::from("-webkit-"), "x");
+
+        let errors = sheet.validate().unwrap_err();
+        let at_rules = vec![
+            "@supports (display: grid)".to_string(),
+            "@media print".to_string(),
+        ];
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError {
+                    kind: ValidationErrorKind::UnknownProperty("font-wieght".to_string()),
+                    selector: ".b".to_string(),
+                    at_rules: at_rules.clone(),
+                },
+                ValidationError {
+                    kind: ValidationErrorKind::InvalidKeyword {
+                        property: "float".to_string(),
+                        keyword: "center".to_string(),
+                    },
+                    selector: ".b".to_string(),
+                    at_rules,
+                },
+                ValidationError {
+                    kind: ValidationErrorKind::InvalidKeyword {
+                        property: "visibility".to_string(),
+                        keyword: "hiden".to_string(),
+                    },
+                    selector: "to".to_string(),
+                    at_rules: vec!["@keyframes fade".to_string()],
+                },
+                ValidationError {
+                    kind: ValidationErrorKind::InvalidKeyword {
+                        property: "box-sizing".to_string(),
+                        keyword: "border".to_string(),
+                    },
+                    selector: ".c".to_string(),
+                    at_rules: vec![],
+                },
+                ValidationError {
+                    kind: ValidationErrorKind::UnknownProperty("-webkit-".to_string()),
+                    selector: ".c".to_string(),
+                    at_rules: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "invalid keyword `center` for `float` in `.b` in `@media print` in `@supports (display: grid)`"
+        );
+
+        assert!(StyleSheet::parse(".a { color: red; display: grid }")
+            .unwrap()
+            .validate()
+            .is_ok());
+        let modern = StyleSheet::parse(
+            r#".a {
+                display: -webkit-box;
+                position: -webkit-sticky;
+                text-align: -webkit-center;
+                white-space: collapse;
+                overflow: overlay;
+                text-wrap-mode: nowrap;
+                field-sizing: content;
+                view-transition-name: hero;
+                anchor-name: --tip;
+                animation-timeline: scroll();
+            }
+            circle { stop-color: red; text-anchor: middle; dominant-baseline: central; clip-rule: evenodd; marker: url(#m) }"#,
+        )
+        .unwrap();
+        assert_eq!(modern.validate(), Ok(()));
+        assert!(StyleSheet::parse(".a { display: -webkit- }")
+            .unwrap()
+            .validate()
+            .is_err());
+
+        let div = HtmlTag::new("div")
+            .with_style(Property::Display, "none")
+            .with_style(Property::from("--gap"), "1px");
+        assert_eq!(div.get_style("display"), Some("none"));
+        assert_eq!(Property::from(" --Gap ").as_str(), "--Gap");
+        assert_eq!(
+            Property::from("COLOUR"),
+            Property::Custom("colour".to_string())
+        );
+    }
+
+    #[test]
+    fn selectors() {
+        use styles::{AttributeOperator, Combinator, Selector, SimpleSelector, Specificity};
+
+        let parsed = Selector::parse(
+            "ul#menu > li.item:nth-child(2n + 1 of .a, #b) ~ a[data-x|=\"e\\\"n\" i]:has(> img)::before",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "ul#menu > li.item:nth-child(2n + 1 of .a, #b) ~ a[data-x|=\"e\\\"n\" i]:has(> img)::before"
+        );
+        assert_eq!(parsed.specificity(), Specificity(2, 3, 5));
+        assert_eq!(parsed.compounds[1].combinator, Some(Combinator::Child));
+        assert_eq!(
+            parsed.compounds[2].combinator,
+            Some(Combinator::SubsequentSibling)
+        );
+
+        let built = Selector::class("item")
+            .with_tag("li")
+            .with_attribute_value("lang", AttributeOperator::DashMatch, "en")
+            .next_sibling(Selector::universal().with_pseudo_element("after"));
+        assert_eq!(built.to_string(), "li.item[lang|=\"en\"] + *::after");
+        assert_eq!(built, Selector::parse("li.item[lang|=en]+*:after").unwrap());
+        assert_eq!(
+            built.compounds[0].selectors[0],
+            SimpleSelector::Type("li".to_string())
+        );
+
+        assert_eq!(Selector::class("1st").to_string(), ".\\31 st");
+        assert_eq!(Selector::parse(".\\31 st").unwrap(), Selector::class("1st"));
+        assert_eq!(
+            Selector::parse(":where(#a, .b) *").unwrap().specificity(),
+            Specificity(0, 0, 0)
+        );
+
+        let error = Selector::parse("a, b").unwrap_err();
+        assert_eq!(error.to_string(), "unexpected `,` at line 1, column 2");
+        assert!(Selector::parse("a >").is_err());
+        assert!(Selector::parse("a[href").is_err());
+        assert!(Selector::parse(":not(.a").is_err());
+
+        let mut sheet = StyleSheet::new();
+        sheet.add_style(Selector::id("main"), "color", "red");
+        assert_eq!(sheet["#main"]["color"], "red");
+    }
+
+    #[test]
+    fn compute_styles() {
+        let sheet = StyleSheet::parse(
+            "li { color: black; padding: 0 }
+             ul > li:nth-child(odd) { color: red }
+             #menu li.active { color: green !important }
+             li:not(.active) + li { margin: 1px }
+             ul:has(> li[data-state^=\"open\" i]) { display: block }
+             li::before { content: \"x\" }
+             li:hover { color: pink }
+             @media print { li { color: white } }
+             li:first-of-type, li:last-child { border: none }",
+        )
+        .unwrap();
+
+        let menu = HtmlTag::new("ul")
+            .with_id("menu")
+            .with_child(HtmlTag::new("li").with_style("color", "blue"))
+            .with_text(" ")
+            .with_child(
+                HtmlTag::new("li")
+                    .with_class("active")
+                    .with_style("color", "yellow")
+                    .with_attribute("data-state", "Opened"),
+            )
+            .with_child(HtmlTag::new("li").with_style("padding", "2px !important"));
+        let styles = sheet.compute_styles(&menu);
+
+        assert_eq!(styles.len(), 4);
+        assert_eq!(
+            styles.iter().map(|s| s.path.clone()).collect::<Vec<_>>(),
+            [vec![], vec![0], vec![2], vec![3]]
+        );
+        assert_eq!(styles.get(&menu).unwrap()["display"], "block");
+
+        let first = styles.get_path(&[0]).unwrap();
+        assert_eq!(first["color"], "blue");
+        assert_eq!(first["border"], "none");
+        assert!(!first.contains_key("margin"));
+        assert!(!first.contains_key("content"));
+
+        let second = styles.get_path(&[2]).unwrap();
+        assert_eq!(second["color"], "green");
+        assert_eq!(second["margin"], "1px");
+        assert!(!second.contains_key("border"));
+
+        let third = menu.children[3].as_element().unwrap();
+        let third = styles.get(third).unwrap();
+        assert_eq!(third["color"], "red");
+        assert_eq!(third["padding"], "2px");
+        assert_eq!(third["border"], "none");
+        assert!(!third.contains_key("margin"));
+
+        assert!(styles.get(&menu.clone()).is_none());
+
+        // A repeated selector wins over a rule of equal specificity that
+        // comes before it in the source.
+        let sheet =
+            StyleSheet::parse(".a { color: red } .b { color: blue } .a { color: green }").unwrap();
+        let p = HtmlTag::new("p").with_class("a b");
+        assert_eq!(sheet.compute_styles(&p).get(&p).unwrap()["color"], "green");
+
+        // Coefficients that overflow never match, instead of panicking.
+        let sheet = StyleSheet::parse(
+            "li:nth-child(n-9223372036854775808) { color: red }
+             li:nth-child(-n+9223372036854775807) { color: blue }
+             li:nth-last-child(-9223372036854775808n+2) { color: green }
+             li:nth-child(9223372036854775808) { color: pink }",
+        )
+        .unwrap();
+        let ul = HtmlTag::new("ul").with_child(HtmlTag::new("li"));
+        let li = ul.children[0].as_element().unwrap();
+        assert_eq!(sheet.compute_styles(&ul).get(li).unwrap()["color"], "blue");
+    }
 }
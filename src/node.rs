@@ -0,0 +1,196 @@
+use std::fmt::{Display, Write};
+
+use crate::{
+    escape::{write_escaped_text, RawHtml},
+    html::HtmlTag,
+    parser::{self, ParseError},
+};
+
+/// A single piece of content inside a `HtmlTag`.
+///
+/// The children of a `HtmlTag` are an ordered list of nodes, which
+/// makes it possible to mix text and elements freely.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::HtmlTag;
+///
+/// let p = HtmlTag::new("p")
+///     .with_text("Hello ")
+///     .with_child(HtmlTag::new("strong").with_body("world"))
+///     .with_text(", welcome");
+///
+/// assert_eq!(p.to_html(), "<p>Hello <strong>world</strong>, welcome</p>");
+/// ```
+///
+/// Text nodes are escaped when rendered, `Raw` nodes are not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Text(String),
+    Element(HtmlTag),
+    Comment(String),
+    Raw(RawHtml),
+}
+
+impl Node {
+    /// Creates a new text node.
+    pub fn text(text: &str) -> Node {
+        Node::Text(text.to_string())
+    }
+
+    /// Creates a new comment node.
+    pub fn comment(comment: &str) -> Node {
+        Node::Comment(comment.to_string())
+    }
+
+    /// Creates a new raw node from trusted markup.
+    pub fn raw(html: RawHtml) -> Node {
+        Node::Raw(html)
+    }
+
+    /// Parses a fragment of HTML into a list of nodes.
+    ///
+    /// Unlike `HtmlTag::parse`, this keeps the text and comments at
+    /// the top level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::Node;
+    ///
+    /// let nodes = Node::parse("Hello <b>World</b><!-- note -->").unwrap();
+    ///
+    /// assert_eq!(nodes.len(), 3);
+    /// assert_eq!(nodes[0].as_text(), Some("Hello "));
+    /// assert_eq!(nodes[2], Node::comment(" note "));
+    /// ```
+    pub fn parse(html: &str) -> Result<Vec<Node>, ParseError> {
+        parser::parse_nodes(html)
+    }
+
+    /// Parses a fragment of HTML the way browsers do, see
+    /// `HtmlTag::parse_lenient`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::Node;
+    ///
+    /// let nodes = Node::parse_lenient("<li>One<li>Two");
+    ///
+    /// assert_eq!(nodes.iter().map(Node::to_html).collect::<String>(), "<li>One</li><li>Two</li>");
+    /// ```
+    pub fn parse_lenient(html: &str) -> Vec<Node> {
+        parser::parse_fragment(html, "body")
+    }
+
+    /// Returns the element if this node is one.
+    pub fn as_element(&self) -> Option<&HtmlTag> {
+        match self {
+            Node::Element(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable element if this node is one.
+    pub fn as_element_mut(&mut self) -> Option<&mut HtmlTag> {
+        match self {
+            Node::Element(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// Returns the text if this node is a text node.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Node::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Converts the current `Node` to a HTML string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::node::Node;
+    ///
+    /// assert_eq!(Node::text("a < b").to_html(), "a &lt; b");
+    /// assert_eq!(Node::comment("note").to_html(), "<!--note-->");
+    /// ```
+    pub fn to_html(&self) -> String {
+        self.render(false)
+    }
+
+    /// Writes the current `Node` as HTML into the given writer.
+    pub fn write_html<W: Write>(&self, w: &mut W) -> std::fmt::Result {
+        self.write(w, false)
+    }
+
+    pub(crate) fn write<W: Write>(&self, w: &mut W, xhtml: bool) -> std::fmt::Result {
+        match self {
+            Node::Text(text) => write_escaped_text(w, text),
+            Node::Element(tag) => tag.write(w, xhtml),
+            Node::Comment(comment) => {
+                w.write_str("<!--")?;
+                // A leading `>` or `->` would close the comment right away.
+                if comment.starts_with('>') || comment.starts_with("->") {
+                    w.write_char(' ')?;
+                }
+                // Every `--` gets a space between its dashes, so runs of
+                // dashes can never form `-->` or `--!>`.
+                let mut rest = comment.as_str();
+                while let Some(i) = rest.find("--") {
+                    w.write_str(&rest[..=i])?;
+                    w.write_char(' ')?;
+                    rest = &rest[i + 1..];
+                }
+                w.write_str(rest)?;
+                // A trailing `-` would run into the closing `-->`.
+                if comment.ends_with('-') {
+                    w.write_char(' ')?;
+                }
+                w.write_str("-->")
+            }
+            Node::Raw(html) => w.write_str(html.as_str()),
+        }
+    }
+
+    pub(crate) fn render(&self, xhtml: bool) -> String {
+        let mut html = String::new();
+        self.write(&mut html, xhtml)
+            .expect("writing to a String never fails");
+        html
+    }
+}
+
+impl From<HtmlTag> for Node {
+    fn from(tag: HtmlTag) -> Self {
+        Node::Element(tag)
+    }
+}
+
+impl From<&str> for Node {
+    fn from(text: &str) -> Self {
+        Node::text(text)
+    }
+}
+
+impl From<String> for Node {
+    fn from(text: String) -> Self {
+        Node::Text(text)
+    }
+}
+
+impl From<RawHtml> for Node {
+    fn from(html: RawHtml) -> Self {
+        Node::Raw(html)
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_html(f)
+    }
+}
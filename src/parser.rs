@@ -0,0 +1,1112 @@
+use std::fmt::Display;
+
+use crate::{attributes::Attributes, escape::RawHtml, html::HtmlTag, node::Node, tags::TagType};
+
+mod tree_builder;
+
+pub(crate) use tree_builder::{parse_document, parse_fragment};
+
+/// The kind of error that occurred while parsing HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended in the middle of a tag, comment or attribute.
+    UnexpectedEof,
+    /// A tag name was expected but not found.
+    InvalidTagName,
+    /// An attribute had an invalid name or unquoted value.
+    InvalidAttribute(String),
+    /// A comment was opened with `<!--` but never closed.
+    UnterminatedComment,
+    /// An end tag that does not match any open element.
+    UnexpectedEndTag(String),
+    /// An end tag that closes an element while another one,
+    /// opened inside of it, is still open.
+    MismatchedEndTag { expected: String, found: String },
+    /// An element that was never closed.
+    UnclosedTag(String),
+    /// Text outside of any element, see `HtmlTag::parse`.
+    TextOutsideElement,
+}
+
+/// An error that occurred while parsing HTML, along with the
+/// position in the input where it occurred.
+///
+/// Lines and columns both start at 1, columns are counted
+/// in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, input: &str, offset: usize) -> ParseError {
+        let (line, column) = line_column(input, offset);
+        ParseError { kind, line, column }
+    }
+}
+
+/// Converts a byte offset into a 1 based line and column.
+pub(crate) fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset.min(input.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnexpectedEof => write!(f, "unexpected end of input")?,
+            ParseErrorKind::InvalidTagName => write!(f, "invalid tag name")?,
+            ParseErrorKind::InvalidAttribute(name) => write!(f, "invalid attribute `{}`", name)?,
+            ParseErrorKind::UnterminatedComment => write!(f, "unterminated comment")?,
+            ParseErrorKind::UnexpectedEndTag(name) => write!(f, "unexpected end tag </{}>", name)?,
+            ParseErrorKind::MismatchedEndTag { expected, found } => {
+                write!(f, "expected </{}> but found </{}>", expected, found)?
+            }
+            ParseErrorKind::UnclosedTag(name) => write!(f, "unclosed tag <{}>", name)?,
+            ParseErrorKind::TextOutsideElement => write!(f, "text outside of any element")?,
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A single token of HTML, along with the byte offset it starts at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    StartTag {
+        name: String,
+        attributes: Vec<(String, Option<String>)>,
+        self_closing: bool,
+        offset: usize,
+    },
+    EndTag {
+        name: String,
+        offset: usize,
+    },
+    Text {
+        text: String,
+        offset: usize,
+    },
+    Comment(String),
+    Doctype(String),
+}
+
+/// Splits HTML into tokens.
+///
+/// The content of raw text elements, `script` and `style`, as well as
+/// escapable raw text elements, `textarea` and `title`, is returned as
+/// a single text token, as it is not parsed as HTML.
+///
+/// In lenient mode the tokenizer never fails, it recovers from errors
+/// the way the WHATWG tokenizer does, and it is up to the tree builder
+/// to switch to raw text with `set_raw_text`.
+pub(crate) struct Tokenizer<'a> {
+    input: &'a str,
+    pos: usize,
+    raw_text_end: Option<String>,
+    plaintext: bool,
+    lenient: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub(crate) fn new(input: &'a str) -> Tokenizer<'a> {
+        Tokenizer {
+            input,
+            pos: 0,
+            raw_text_end: None,
+            plaintext: false,
+            lenient: false,
+        }
+    }
+
+    pub(crate) fn lenient(input: &'a str) -> Tokenizer<'a> {
+        Tokenizer {
+            lenient: true,
+            ..Tokenizer::new(input)
+        }
+    }
+
+    /// Reads everything up to the end tag of the given element as text.
+    pub(crate) fn set_raw_text(&mut self, name: &str) {
+        self.raw_text_end = Some(name.to_string());
+    }
+
+    /// Reads the rest of the input as text, for `plaintext`.
+    pub(crate) fn set_plaintext(&mut self) {
+        self.plaintext = true;
+    }
+
+    fn error(&self, kind: ParseErrorKind, offset: usize) -> ParseError {
+        ParseError::new(kind, self.input, offset)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_ascii_whitespace() {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// Skips the rest of the input, in lenient mode an unfinished tag
+    /// at the end of the input is dropped.
+    fn eof(&mut self, kind: ParseErrorKind, offset: usize) -> Result<Option<Token>, ParseError> {
+        if !self.lenient {
+            return Err(self.error(kind, offset));
+        }
+        self.pos = self.input.len();
+        Ok(None)
+    }
+
+    pub(crate) fn next_token(&mut self) -> Result<Option<Token>, ParseError> {
+        if let Some(name) = self.raw_text_end.take() {
+            return self.raw_text(&name).map(Some);
+        }
+
+        if self.pos >= self.input.len() {
+            return Ok(None);
+        }
+
+        let start = self.pos;
+        let rest = self.rest();
+        if self.plaintext {
+            self.pos = self.input.len();
+            return Ok(Some(Token::Text {
+                text: rest.to_string(),
+                offset: start,
+            }));
+        }
+        if rest.starts_with("<!--") {
+            return self.comment();
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            return self.bogus_comment();
+        }
+        if rest.starts_with("</") {
+            return self.end_tag();
+        }
+        if rest.starts_with('<')
+            && rest[1..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic())
+        {
+            return self.start_tag();
+        }
+
+        Ok(Some(self.text()))
+    }
+
+    fn text(&mut self) -> Token {
+        let start = self.pos;
+        // A `<` that does not start a tag is just text.
+        let skip = if self.rest().starts_with('<') { 1 } else { 0 };
+        let end = self.rest()[skip..]
+            .find('<')
+            .map(|i| self.pos + skip + i)
+            .unwrap_or(self.input.len());
+        self.pos = end;
+        Token::Text {
+            text: decode(&self.input[start..end], self.lenient, false),
+            offset: start,
+        }
+    }
+
+    fn comment(&mut self) -> Result<Option<Token>, ParseError> {
+        let start = self.pos;
+        let body = &self.input[start + 4..];
+        if self.lenient {
+            // `<!-->` and `<!--->` are empty comments.
+            for empty in [">", "->"] {
+                if body.starts_with(empty) {
+                    self.pos = start + 4 + empty.len();
+                    return Ok(Some(Token::Comment(String::new())));
+                }
+            }
+            let end = [body.find("-->"), body.find("--!>")]
+                .into_iter()
+                .flatten()
+                .min();
+            return Ok(Some(match end {
+                Some(end) => {
+                    let close = if body[end..].starts_with("-->") { 3 } else { 4 };
+                    self.pos = start + 4 + end + close;
+                    Token::Comment(body[..end].to_string())
+                }
+                None => {
+                    self.pos = self.input.len();
+                    Token::Comment(body.trim_end_matches('-').to_string())
+                }
+            }));
+        }
+        match body.find("-->") {
+            Some(end) => {
+                self.pos = start + 4 + end + 3;
+                Ok(Some(Token::Comment(body[..end].to_string())))
+            }
+            None => Err(self.error(ParseErrorKind::UnterminatedComment, start)),
+        }
+    }
+
+    /// Reads a doctype, or anything else that starts with `<!` or `<?`,
+    /// which is treated as a comment.
+    fn bogus_comment(&mut self) -> Result<Option<Token>, ParseError> {
+        let start = self.pos;
+        let rest = self.rest();
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None if self.lenient => rest.len(),
+            None => return Err(self.error(ParseErrorKind::UnexpectedEof, start)),
+        };
+        self.pos = (start + end + 1).min(self.input.len());
+
+        let body = &rest[2..end];
+        if rest.starts_with("<!") && body.to_ascii_lowercase().starts_with("doctype") {
+            let name = body[7..]
+                .split_ascii_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            return Ok(Some(Token::Doctype(name)));
+        }
+        // The `?` of a processing instruction is part of the comment.
+        let body = if rest.starts_with("<?") {
+            &rest[1..end]
+        } else {
+            body
+        };
+        Ok(Some(Token::Comment(body.to_string())))
+    }
+
+    fn tag_name(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_whitespace() || c == '/' || c == '>' {
+                break;
+            }
+            if !self.lenient && (c == '<' || c == '"' || c == '\'' || c == '=') {
+                return Err(self.error(ParseErrorKind::InvalidTagName, self.pos));
+            }
+            self.pos += c.len_utf8();
+        }
+        if start == self.pos {
+            return Err(self.error(ParseErrorKind::InvalidTagName, start));
+        }
+        Ok(self.input[start..self.pos].to_ascii_lowercase())
+    }
+
+    fn end_tag(&mut self) -> Result<Option<Token>, ParseError> {
+        let start = self.pos;
+        if self.lenient {
+            match self.rest()[2..].chars().next() {
+                // `</>` is skipped entirely.
+                Some('>') => {
+                    self.pos += 3;
+                    return self.next_token();
+                }
+                Some(c) if c.is_ascii_alphabetic() => {}
+                Some(_) => {
+                    // Anything else, like `</3>`, is a comment.
+                    let rest = &self.rest()[2..];
+                    let end = rest.find('>').unwrap_or(rest.len());
+                    self.pos = (start + 2 + end + 1).min(self.input.len());
+                    return Ok(Some(Token::Comment(rest[..end].to_string())));
+                }
+                None => return Ok(Some(self.text())),
+            }
+        }
+
+        self.pos += 2;
+        let name = self.tag_name()?;
+        self.skip_whitespace();
+        match self.peek() {
+            Some('>') => {
+                self.pos += 1;
+                Ok(Some(Token::EndTag {
+                    name,
+                    offset: start,
+                }))
+            }
+            // Attributes on end tags are ignored.
+            Some(_) if self.lenient => match self.rest().find('>') {
+                Some(end) => {
+                    self.pos += end + 1;
+                    Ok(Some(Token::EndTag {
+                        name,
+                        offset: start,
+                    }))
+                }
+                None => self.eof(ParseErrorKind::UnexpectedEof, start),
+            },
+            Some(_) => Err(self.error(ParseErrorKind::InvalidTagName, self.pos)),
+            None => self.eof(ParseErrorKind::UnexpectedEof, start),
+        }
+    }
+
+    fn start_tag(&mut self) -> Result<Option<Token>, ParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let name = self.tag_name()?;
+        let mut attributes: Vec<(String, Option<String>)> = Vec::new();
+        let mut self_closing = false;
+
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return self.eof(ParseErrorKind::UnexpectedEof, start),
+                Some('>') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('/') if self.rest().starts_with("/>") => {
+                    self.pos += 2;
+                    self_closing = true;
+                    break;
+                }
+                Some('/') if self.lenient => self.pos += 1,
+                Some(_) => {
+                    let Some((key, value)) = self.attribute(start)? else {
+                        return self.eof(ParseErrorKind::UnexpectedEof, start);
+                    };
+                    // Like browsers, the first of duplicate attributes wins.
+                    if !attributes.iter().any(|(k, _)| *k == key) {
+                        attributes.push((key, value));
+                    }
+                }
+            }
+        }
+
+        let tag_type = TagType::from(&name);
+        if !self.lenient
+            && !self_closing
+            && (tag_type.is_raw_text() || tag_type.is_escapable_raw_text())
+        {
+            self.raw_text_end = Some(name.clone());
+        }
+
+        Ok(Some(Token::StartTag {
+            name,
+            attributes,
+            self_closing,
+            offset: start,
+        }))
+    }
+
+    /// Reads a single attribute, returning `None` if the input ends
+    /// before the attribute does.
+    fn attribute(
+        &mut self,
+        tag_start: usize,
+    ) -> Result<Option<(String, Option<String>)>, ParseError> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_whitespace() || c == '/' || c == '>' || (c == '=' && self.pos > start) {
+                break;
+            }
+            if !self.lenient && (c == '"' || c == '\'' || c == '<') {
+                let name = self.input[start..self.pos + c.len_utf8()].to_string();
+                return Err(self.error(ParseErrorKind::InvalidAttribute(name), start));
+            }
+            self.pos += c.len_utf8();
+        }
+        let name = self.input[start..self.pos].to_ascii_lowercase();
+        if name.is_empty() {
+            return Err(self.error(ParseErrorKind::InvalidAttribute(name), start));
+        }
+
+        self.skip_whitespace();
+        if self.peek() != Some('=') {
+            return Ok(Some((name, None)));
+        }
+        self.pos += 1;
+        self.skip_whitespace();
+
+        match self.peek() {
+            None => self
+                .eof(ParseErrorKind::UnexpectedEof, tag_start)
+                .map(|_| None),
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                match self.rest().find(quote) {
+                    Some(end) => {
+                        let value = decode(&self.rest()[..end], self.lenient, true);
+                        self.pos += end + 1;
+                        Ok(Some((name, Some(value))))
+                    }
+                    None => self.eof(ParseErrorKind::UnexpectedEof, start).map(|_| None),
+                }
+            }
+            // `<a href=>` has an empty value.
+            Some('>') if self.lenient => Ok(Some((name, Some(String::new())))),
+            Some(_) => {
+                let value_start = self.pos;
+                while let Some(c) = self.peek() {
+                    if c.is_ascii_whitespace() || c == '>' {
+                        break;
+                    }
+                    if !self.lenient && matches!(c, '"' | '\'' | '<' | '=' | '`') {
+                        return Err(self.error(ParseErrorKind::InvalidAttribute(name), self.pos));
+                    }
+                    self.pos += c.len_utf8();
+                }
+                let value = decode(&self.input[value_start..self.pos], self.lenient, true);
+                Ok(Some((name, Some(value))))
+            }
+        }
+    }
+
+    fn raw_text(&mut self, name: &str) -> Result<Token, ParseError> {
+        let start = self.pos;
+        let rest = self.rest();
+        let lower = rest.to_ascii_lowercase();
+        let closing = format!("</{}", name);
+        let mut end = rest.len();
+        let mut search = 0;
+        while let Some(i) = lower[search..].find(&closing) {
+            let found = search + i;
+            let after = lower[found + closing.len()..].chars().next();
+            if after.is_none_or(|c| c.is_ascii_whitespace() || c == '/' || c == '>') {
+                end = found;
+                break;
+            }
+            search = found + closing.len();
+        }
+        // Without an end tag, the text runs until the end of the input.
+        self.pos = start + end;
+
+        let raw = &rest[..end];
+        let text = if TagType::from(name).is_escapable_raw_text() {
+            decode(raw, self.lenient, false)
+        } else {
+            raw.to_string()
+        };
+        Ok(Token::Text {
+            text,
+            offset: start,
+        })
+    }
+}
+
+/// The named character references that are decoded.
+///
+/// This covers the references that show up in practice, any other
+/// reference is kept as it is.
+const ENTITIES: &[(&str, &str)] = &[
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("quot", "\""),
+    ("apos", "'"),
+    ("AMP", "&"),
+    ("LT", "<"),
+    ("GT", ">"),
+    ("QUOT", "\""),
+    ("COPY", "©"),
+    ("REG", "®"),
+    ("not", "¬"),
+    ("nbsp", "\u{a0}"),
+    ("copy", "©"),
+    ("reg", "®"),
+    ("trade", "™"),
+    ("hellip", "…"),
+    ("mdash", "—"),
+    ("ndash", "–"),
+    ("lsquo", "‘"),
+    ("rsquo", "’"),
+    ("sbquo", "‚"),
+    ("ldquo", "“"),
+    ("rdquo", "”"),
+    ("bdquo", "„"),
+    ("laquo", "«"),
+    ("raquo", "»"),
+    ("lsaquo", "‹"),
+    ("rsaquo", "›"),
+    ("bull", "•"),
+    ("middot", "·"),
+    ("deg", "°"),
+    ("plusmn", "±"),
+    ("times", "×"),
+    ("divide", "÷"),
+    ("minus", "−"),
+    ("frac12", "½"),
+    ("frac14", "¼"),
+    ("frac34", "¾"),
+    ("sup2", "²"),
+    ("sup3", "³"),
+    ("micro", "µ"),
+    ("euro", "€"),
+    ("pound", "£"),
+    ("yen", "¥"),
+    ("cent", "¢"),
+    ("sect", "§"),
+    ("para", "¶"),
+    ("dagger", "†"),
+    ("Dagger", "‡"),
+    ("permil", "‰"),
+    ("prime", "′"),
+    ("Prime", "″"),
+    ("iexcl", "¡"),
+    ("iquest", "¿"),
+    ("shy", "\u{ad}"),
+    ("ensp", "\u{2002}"),
+    ("emsp", "\u{2003}"),
+    ("thinsp", "\u{2009}"),
+    ("zwnj", "\u{200c}"),
+    ("zwj", "\u{200d}"),
+    ("larr", "←"),
+    ("uarr", "↑"),
+    ("rarr", "→"),
+    ("darr", "↓"),
+    ("harr", "↔"),
+    ("hearts", "♥"),
+    ("check", "✓"),
+    ("infin", "∞"),
+    ("ne", "≠"),
+    ("le", "≤"),
+    ("ge", "≥"),
+    ("asymp", "≈"),
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("pi", "π"),
+    ("sigma", "σ"),
+    ("omega", "ω"),
+    ("Agrave", "À"),
+    ("Aacute", "Á"),
+    ("Acirc", "Â"),
+    ("Atilde", "Ã"),
+    ("Auml", "Ä"),
+    ("Aring", "Å"),
+    ("AElig", "Æ"),
+    ("Ccedil", "Ç"),
+    ("Egrave", "È"),
+    ("Eacute", "É"),
+    ("Ecirc", "Ê"),
+    ("Euml", "Ë"),
+    ("Iacute", "Í"),
+    ("Ntilde", "Ñ"),
+    ("Oacute", "Ó"),
+    ("Ouml", "Ö"),
+    ("Oslash", "Ø"),
+    ("Uacute", "Ú"),
+    ("Uuml", "Ü"),
+    ("szlig", "ß"),
+    ("agrave", "à"),
+    ("aacute", "á"),
+    ("acirc", "â"),
+    ("atilde", "ã"),
+    ("auml", "ä"),
+    ("aring", "å"),
+    ("aelig", "æ"),
+    ("ccedil", "ç"),
+    ("egrave", "è"),
+    ("eacute", "é"),
+    ("ecirc", "ê"),
+    ("euml", "ë"),
+    ("igrave", "ì"),
+    ("iacute", "í"),
+    ("icirc", "î"),
+    ("iuml", "ï"),
+    ("ntilde", "ñ"),
+    ("ograve", "ò"),
+    ("oacute", "ó"),
+    ("ocirc", "ô"),
+    ("otilde", "õ"),
+    ("ouml", "ö"),
+    ("oslash", "ø"),
+    ("ugrave", "ù"),
+    ("uacute", "ú"),
+    ("ucirc", "û"),
+    ("uuml", "ü"),
+    ("yacute", "ý"),
+    ("yuml", "ÿ"),
+];
+
+/// The named references that browsers also decode without the
+/// trailing semicolon, for compatibility with old content.
+const LEGACY_ENTITIES: &[&str] = &[
+    "AElig", "AMP", "Aacute", "Acirc", "Agrave", "Aring", "Atilde", "Auml", "COPY", "Ccedil",
+    "Eacute", "Ecirc", "Egrave", "Euml", "GT", "Iacute", "LT", "Ntilde", "Oacute", "Oslash",
+    "Ouml", "QUOT", "REG", "Uacute", "Uuml", "aacute", "acirc", "aelig", "agrave", "amp", "aring",
+    "atilde", "auml", "ccedil", "cent", "copy", "deg", "divide", "eacute", "ecirc", "egrave",
+    "euml", "frac12", "frac14", "frac34", "gt", "iacute", "icirc", "iexcl", "igrave", "iquest",
+    "iuml", "laquo", "lt", "micro", "middot", "nbsp", "not", "ntilde", "oacute", "ocirc", "ograve",
+    "oslash", "otilde", "ouml", "para", "plusmn", "pound", "quot", "raquo", "reg", "sect", "shy",
+    "sup2", "sup3", "szlig", "times", "uacute", "ucirc", "ugrave", "uuml", "yacute", "yen", "yuml",
+];
+
+/// What numeric references to the C1 control range decode to, since
+/// old content used them for the windows-1252 characters.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+/// Decodes the character references in the given text.
+///
+/// Numeric references, like `&#169;` or `&#xA9;`, and the named ones
+/// in `ENTITIES` are decoded, anything else is kept as it is.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::parser::decode_entities;
+///
+/// assert_eq!(decode_entities("a &lt; b &amp;&amp; &#x263A; &unknown;"), "a < b && ☺ &unknown;");
+/// ```
+pub fn decode_entities(text: &str) -> String {
+    decode(text, false, false)
+}
+
+/// Decodes the character references in the given text, in lenient
+/// mode the semicolon can be left out, like browsers allow.
+fn decode(text: &str, lenient: bool, attribute: bool) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        decoded.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let reference = if lenient {
+            decode_lenient_reference(rest, attribute)
+        } else {
+            decode_reference(rest)
+        };
+        match reference {
+            Some((c, len)) => {
+                decoded.push_str(&c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Decodes the reference at the start of the text, returning the
+/// decoded text and the length of the reference.
+fn decode_reference(text: &str) -> Option<(String, usize)> {
+    let end = text[1..].find(';')? + 1;
+    let reference = &text[1..end];
+    if let Some(number) = reference.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse::<u32>().ok()?,
+        };
+        return Some((numeric_reference(code).to_string(), end + 1));
+    }
+    ENTITIES
+        .iter()
+        .find(|(name, _)| *name == reference)
+        .map(|(_, value)| (value.to_string(), end + 1))
+}
+
+/// Like `decode_reference`, but following the rules of browsers for
+/// references without a semicolon.
+fn decode_lenient_reference(text: &str, attribute: bool) -> Option<(String, usize)> {
+    let body = &text[1..];
+    if let Some(number) = body.strip_prefix('#') {
+        let (hex, digits) = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => (true, hex),
+            None => (false, number),
+        };
+        let len = digits
+            .find(|c: char| {
+                !(if hex {
+                    c.is_ascii_hexdigit()
+                } else {
+                    c.is_ascii_digit()
+                })
+            })
+            .unwrap_or(digits.len());
+        if len == 0 {
+            return None;
+        }
+        let code =
+            u32::from_str_radix(&digits[..len], if hex { 16 } else { 10 }).unwrap_or(u32::MAX);
+        let mut end = 1 + (number.len() - digits.len()) + len + 1;
+        if digits[len..].starts_with(';') {
+            end += 1;
+        }
+        return Some((numeric_reference(code).to_string(), end));
+    }
+
+    let (name, value) = ENTITIES
+        .iter()
+        .filter(|(name, _)| body.starts_with(name))
+        .filter(|(name, _)| body[name.len()..].starts_with(';') || LEGACY_ENTITIES.contains(name))
+        .max_by_key(|(name, _)| name.len())?;
+    let after = &body[name.len()..];
+    if after.starts_with(';') {
+        return Some((value.to_string(), name.len() + 2));
+    }
+    // In attributes, `&copy=` or `&copyright` are kept, since they are
+    // likely part of a URL.
+    if attribute && after.starts_with(|c: char| c == '=' || c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some((value.to_string(), name.len() + 1))
+}
+
+/// Returns the character for a numeric reference, replacing the ones
+/// that are not allowed.
+fn numeric_reference(code: u32) -> char {
+    match code {
+        0 => '\u{fffd}',
+        0x80..=0x9f => WINDOWS_1252[(code - 0x80) as usize],
+        _ => char::from_u32(code).unwrap_or('\u{fffd}'),
+    }
+}
+
+/// Turns the text inside `script` and `style` into `Raw` nodes, since
+/// it is not parsed as HTML and must not be escaped when rendered.
+///
+/// This is only done for HTML elements. The content of a `style` in
+/// SVG or MathML is parsed as usual, so its text must stay escaped.
+pub(crate) fn keep_raw_text(tag: &mut HtmlTag, namespace: Namespace) {
+    if namespace == Namespace::Html && tag.tag_type.is_raw_text() {
+        for child in &mut tag.children {
+            if let Node::Text(text) = child {
+                *child = Node::Raw(RawHtml::new(text));
+            }
+        }
+    }
+}
+
+/// Creates a `HtmlTag` from the name and attributes of a start tag.
+pub(crate) fn create_element(name: &str, attributes: Vec<(String, Option<String>)>) -> HtmlTag {
+    let mut tag = HtmlTag::new(name);
+    for (key, value) in attributes {
+        tag.set_attribute(&key, value.as_deref());
+    }
+    tag
+}
+
+/// The SVG elements whose names are not all lowercase.
+const SVG_TAG_NAMES: &[&str] = &[
+    "altGlyph",
+    "altGlyphDef",
+    "altGlyphItem",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "clipPath",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "foreignObject",
+    "glyphRef",
+    "linearGradient",
+    "radialGradient",
+    "textPath",
+];
+
+/// The SVG attributes whose names are not all lowercase.
+const SVG_ATTRIBUTES: &[&str] = &[
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseProfile",
+    "calcMode",
+    "clipPathUnits",
+    "diffuseConstant",
+    "edgeMode",
+    "filterUnits",
+    "glyphRef",
+    "gradientTransform",
+    "gradientUnits",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "limitingConeAngle",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "maskContentUnits",
+    "maskUnits",
+    "numOctaves",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "requiredExtensions",
+    "requiredFeatures",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "textLength",
+    "viewBox",
+    "viewTarget",
+    "xChannelSelector",
+    "yChannelSelector",
+    "zoomAndPan",
+];
+
+/// The MathML attributes whose names are not all lowercase.
+const MATHML_ATTRIBUTES: &[&str] = &["definitionURL"];
+
+/// The namespace of an element, which decides the case of its name
+/// and the names of its attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Namespace {
+    Html,
+    Svg,
+    MathMl,
+}
+
+/// Restores the case of the names of SVG and MathML elements and their
+/// attributes, like `foreignObject` and `viewBox`, which the tokenizer
+/// lowercases along with every other name.
+pub(crate) fn adjust_foreign_names(tag: &mut HtmlTag, namespace: Namespace) {
+    let (names, attributes): (&[&str], &[&str]) = match namespace {
+        Namespace::Html => return,
+        Namespace::Svg => (SVG_TAG_NAMES, SVG_ATTRIBUTES),
+        Namespace::MathMl => (&[], MATHML_ATTRIBUTES),
+    };
+    let adjusted = |name: &str| {
+        attributes
+            .iter()
+            .find(|adjusted| adjusted.eq_ignore_ascii_case(name))
+            .copied()
+    };
+
+    if let Some(name) = names
+        .iter()
+        .find(|adjusted| adjusted.eq_ignore_ascii_case(tag.tag_type.as_str()))
+    {
+        tag.tag_type = TagType::Custom(name.to_string());
+    }
+    if tag
+        .attributes
+        .iter()
+        .any(|(key, _)| adjusted(key).is_some())
+    {
+        let entries: Vec<(String, Option<String>)> = tag
+            .attributes
+            .iter()
+            .map(|(key, value)| {
+                let key = adjusted(key).unwrap_or(key);
+                (key.to_string(), value.map(|v| v.to_string()))
+            })
+            .collect();
+        tag.attributes = Attributes::new();
+        for (key, value) in entries {
+            tag.attributes.set(&key, value.as_deref());
+        }
+    }
+}
+
+impl Namespace {
+    /// Returns the namespace of an element with the given name, nested
+    /// in the given element, if any, and its namespace.
+    fn of(name: &str, parent: Option<(&HtmlTag, Namespace)>) -> Namespace {
+        match (name, parent) {
+            ("svg", _) => Namespace::Svg,
+            ("math", _) => Namespace::MathMl,
+            // HTML content can be nested in these.
+            (_, Some((tag, Namespace::Svg)))
+                if !matches!(tag.tag_type.as_str(), "foreignobject" | "desc" | "title") =>
+            {
+                Namespace::Svg
+            }
+            (_, Some((tag, Namespace::MathMl)))
+                if !matches!(tag.tag_type.as_str(), "mi" | "mo" | "mn" | "ms" | "mtext") =>
+            {
+                Namespace::MathMl
+            }
+            _ => Namespace::Html,
+        }
+    }
+}
+
+/// Appends a node to the children of the innermost open element,
+/// or to the top level nodes if there is none.
+fn append(stack: &mut [(HtmlTag, usize, Namespace)], nodes: &mut Vec<Node>, node: Node) {
+    let children = match stack.last_mut() {
+        Some((tag, _, _)) => &mut tag.children,
+        None => nodes,
+    };
+    match (children.last_mut(), node) {
+        (Some(Node::Text(last)), Node::Text(text)) => last.push_str(&text),
+        (_, node) => children.push(node),
+    }
+}
+
+/// Builds the tree for the given HTML, returning the top level nodes
+/// and the offset of the first text outside of any element.
+fn build(input: &str) -> Result<(Vec<Node>, Option<usize>), ParseError> {
+    let mut tokenizer = Tokenizer::new(input);
+    let mut stack: Vec<(HtmlTag, usize, Namespace)> = Vec::new();
+    let mut nodes = Vec::new();
+    let mut stray_text = None;
+
+    while let Some(token) = tokenizer.next_token()? {
+        match token {
+            Token::StartTag {
+                name,
+                attributes,
+                self_closing,
+                offset,
+            } => {
+                let parent = stack.last().map(|(tag, _, namespace)| (tag, *namespace));
+                let namespace = Namespace::of(&name, parent);
+                let mut tag = create_element(&name, attributes);
+                if namespace != Namespace::Html {
+                    // Only HTML elements have raw text content.
+                    tokenizer.raw_text_end = None;
+                }
+                if self_closing || tag.tag_type.is_void() {
+                    adjust_foreign_names(&mut tag, namespace);
+                    append(&mut stack, &mut nodes, Node::Element(tag));
+                } else {
+                    // The names are adjusted once the element is closed,
+                    // since end tags are matched by their lowercase name.
+                    stack.push((tag, offset, namespace));
+                }
+            }
+            Token::EndTag { name, offset } => {
+                let position = stack
+                    .iter()
+                    .rposition(|(tag, _, _)| tag.tag_type.as_str() == name);
+                match position {
+                    Some(i) if i + 1 == stack.len() => {
+                        let (mut tag, _, namespace) = stack.pop().expect("the stack is not empty");
+                        keep_raw_text(&mut tag, namespace);
+                        adjust_foreign_names(&mut tag, namespace);
+                        append(&mut stack, &mut nodes, Node::Element(tag));
+                    }
+                    Some(_) => {
+                        let expected = stack
+                            .last()
+                            .map(|(tag, _, _)| tag.tag_type.html())
+                            .unwrap_or_default();
+                        return Err(ParseError::new(
+                            ParseErrorKind::MismatchedEndTag {
+                                expected,
+                                found: name,
+                            },
+                            input,
+                            offset,
+                        ));
+                    }
+                    None => {
+                        return Err(ParseError::new(
+                            ParseErrorKind::UnexpectedEndTag(name),
+                            input,
+                            offset,
+                        ))
+                    }
+                }
+            }
+            Token::Text { text, offset } => {
+                if stack.is_empty() && stray_text.is_none() && !text.trim().is_empty() {
+                    stray_text = Some(offset + text.len() - text.trim_start().len());
+                }
+                append(&mut stack, &mut nodes, Node::Text(text));
+            }
+            Token::Comment(comment) => append(&mut stack, &mut nodes, Node::Comment(comment)),
+            Token::Doctype(_) => {}
+        }
+    }
+
+    if let Some((tag, offset, _)) = stack.pop() {
+        return Err(ParseError::new(
+            ParseErrorKind::UnclosedTag(tag.tag_type.html()),
+            input,
+            offset,
+        ));
+    }
+
+    Ok((nodes, stray_text))
+}
+
+/// Parses HTML into a list of nodes, see `Node::parse`.
+pub(crate) fn parse_nodes(input: &str) -> Result<Vec<Node>, ParseError> {
+    build(input).map(|(nodes, _)| nodes)
+}
+
+/// Parses HTML into the elements at its top level, see `HtmlTag::parse`.
+pub(crate) fn parse_elements(input: &str) -> Result<Vec<HtmlTag>, ParseError> {
+    let (nodes, stray_text) = build(input)?;
+    if let Some(offset) = stray_text {
+        return Err(ParseError::new(
+            ParseErrorKind::TextOutsideElement,
+            input,
+            offset,
+        ));
+    }
+
+    Ok(nodes
+        .into_iter()
+        .filter_map(|node| match node {
+            Node::Element(tag) => Some(tag),
+            _ => None,
+        })
+        .collect())
+}
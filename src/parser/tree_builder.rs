@@ -0,0 +1,1938 @@
+//! Tree construction following the WHATWG HTML standard.
+//!
+//! This implements the insertion modes that matter for documents and
+//! fragments in the wild, including implied end tags, implicit table
+//! sections, foster parenting and the adoption agency algorithm for
+//! misnested formatting elements. Frames and the contents of
+//! `template` are not treated specially.
+
+use super::{adjust_foreign_names, create_element, keep_raw_text, Namespace, Token, Tokenizer};
+use crate::{html::HtmlTag, node::Node};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Initial,
+    BeforeHtml,
+    BeforeHead,
+    InHead,
+    AfterHead,
+    InBody,
+    Text,
+    InTable,
+    InCaption,
+    InColumnGroup,
+    InTableBody,
+    InRow,
+    InCell,
+    InSelect,
+    InSelectInTable,
+    AfterBody,
+    AfterAfterBody,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Default,
+    ListItem,
+    Button,
+    Table,
+    Select,
+}
+
+enum Data {
+    Document,
+    Element(HtmlTag),
+    Text(String),
+    Comment(String),
+}
+
+/// A node in the tree that is being built, nodes refer to each other
+/// by their index, since the algorithms move them around freely.
+struct Entry {
+    data: Data,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    namespace: Namespace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Formatting {
+    Marker,
+    Element(usize),
+}
+
+const DOCUMENT: usize = 0;
+
+const SPECIAL: &[&str] = &[
+    "address",
+    "applet",
+    "area",
+    "article",
+    "aside",
+    "base",
+    "basefont",
+    "bgsound",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "caption",
+    "center",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dir",
+    "div",
+    "dl",
+    "dt",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "frame",
+    "frameset",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "iframe",
+    "img",
+    "input",
+    "keygen",
+    "li",
+    "link",
+    "listing",
+    "main",
+    "marquee",
+    "menu",
+    "meta",
+    "nav",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "ol",
+    "p",
+    "param",
+    "plaintext",
+    "pre",
+    "script",
+    "search",
+    "section",
+    "select",
+    "source",
+    "style",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "track",
+    "ul",
+    "wbr",
+    "xmp",
+];
+
+/// The foreign elements that HTML content can be nested in.
+const INTEGRATION_POINTS: &[&str] = &[
+    "mi",
+    "mo",
+    "mn",
+    "ms",
+    "mtext",
+    "annotation-xml",
+    "foreignobject",
+    "desc",
+    "title",
+];
+
+const IMPLIED_END_TAGS: &[&str] = &[
+    "dd", "dt", "li", "optgroup", "option", "p", "rb", "rp", "rt", "rtc",
+];
+
+const HEADINGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+const FORMATTING: &[&str] = &[
+    "a", "b", "big", "code", "em", "font", "i", "nobr", "s", "small", "strike", "strong", "tt", "u",
+];
+
+/// The HTML elements that end foreign content when they show up in it.
+const BREAKOUT: &[&str] = &[
+    "b",
+    "big",
+    "blockquote",
+    "body",
+    "br",
+    "center",
+    "code",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "listing",
+    "menu",
+    "meta",
+    "nobr",
+    "ol",
+    "p",
+    "pre",
+    "ruby",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "strike",
+    "sub",
+    "sup",
+    "table",
+    "tt",
+    "u",
+    "ul",
+    "var",
+];
+
+const TABLE_SECTIONS: &[&str] = &["tbody", "tfoot", "thead"];
+
+const TABLE_CONTEXT: &[&str] = &["table", "tbody", "tfoot", "thead", "tr"];
+
+fn split_whitespace(text: &str) -> (&str, &str) {
+    let i = text
+        .find(|c: char| !c.is_ascii_whitespace())
+        .unwrap_or(text.len());
+    text.split_at(i)
+}
+
+fn text_token(text: &str, offset: usize) -> Token {
+    Token::Text {
+        text: text.to_string(),
+        offset,
+    }
+}
+
+fn start_tag(name: &str) -> Token {
+    Token::StartTag {
+        name: name.to_string(),
+        attributes: Vec::new(),
+        self_closing: false,
+        offset: 0,
+    }
+}
+
+struct TreeBuilder<'a> {
+    tokenizer: Tokenizer<'a>,
+    nodes: Vec<Entry>,
+    open: Vec<usize>,
+    formatting: Vec<Formatting>,
+    mode: Mode,
+    original_mode: Mode,
+    head: Option<usize>,
+    form: Option<usize>,
+    context: Option<&'a str>,
+    foster_parenting: bool,
+    skip_newline: bool,
+    doctype: Option<String>,
+    quirks: bool,
+}
+
+impl<'a> TreeBuilder<'a> {
+    fn new(input: &'a str) -> TreeBuilder<'a> {
+        TreeBuilder {
+            tokenizer: Tokenizer::lenient(input),
+            nodes: vec![Entry {
+                data: Data::Document,
+                parent: None,
+                children: Vec::new(),
+                namespace: Namespace::Html,
+            }],
+            open: Vec::new(),
+            formatting: Vec::new(),
+            mode: Mode::Initial,
+            original_mode: Mode::Initial,
+            head: None,
+            form: None,
+            context: None,
+            foster_parenting: false,
+            skip_newline: false,
+            doctype: None,
+            quirks: false,
+        }
+    }
+
+    fn run(&mut self) {
+        while let Some(token) = self
+            .tokenizer
+            .next_token()
+            .expect("the lenient tokenizer never fails")
+        {
+            let token = match (std::mem::take(&mut self.skip_newline), token) {
+                (true, Token::Text { text, offset }) if text.starts_with('\n') => {
+                    text_token(&text[1..], offset)
+                }
+                (_, token) => token,
+            };
+            self.process(token);
+        }
+    }
+
+    // Tree manipulation
+
+    fn create(&mut self, data: Data, namespace: Namespace) -> usize {
+        self.nodes.push(Entry {
+            data,
+            parent: None,
+            children: Vec::new(),
+            namespace,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn tag(&self, id: usize) -> Option<&HtmlTag> {
+        match &self.nodes[id].data {
+            Data::Element(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    fn tag_mut(&mut self, id: usize) -> Option<&mut HtmlTag> {
+        match &mut self.nodes[id].data {
+            Data::Element(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    fn name(&self, id: usize) -> &str {
+        self.tag(id).map(|tag| tag.tag_type.as_str()).unwrap_or("")
+    }
+
+    /// Returns whether the node is a SVG or MathML element.
+    fn is_foreign(&self, id: usize) -> bool {
+        self.nodes[id].namespace != Namespace::Html
+    }
+
+    /// Returns whether the node is a HTML element with one of the names.
+    fn is(&self, id: usize, names: &[&str]) -> bool {
+        !self.is_foreign(id) && names.contains(&self.name(id))
+    }
+
+    fn is_special(&self, id: usize) -> bool {
+        if self.is_foreign(id) {
+            INTEGRATION_POINTS.contains(&self.name(id))
+        } else {
+            SPECIAL.contains(&self.name(id))
+        }
+    }
+
+    fn current(&self) -> usize {
+        *self.open.last().expect("there is always an open element")
+    }
+
+    fn detach(&mut self, id: usize) {
+        if let Some(parent) = self.nodes[id].parent.take() {
+            self.nodes[parent].children.retain(|&child| child != id);
+        }
+    }
+
+    fn insert_at(&mut self, (parent, before): (usize, Option<usize>), id: usize) {
+        self.detach(id);
+        let children = &mut self.nodes[parent].children;
+        match before.and_then(|before| children.iter().position(|&c| c == before)) {
+            Some(i) => children.insert(i, id),
+            None => children.push(id),
+        }
+        self.nodes[id].parent = Some(parent);
+    }
+
+    /// Returns the parent, and the sibling to insert before, for a new
+    /// node, taking foster parenting into account.
+    fn appropriate_place(&self, target: Option<usize>) -> (usize, Option<usize>) {
+        let target = target.unwrap_or_else(|| self.current());
+        if !self.foster_parenting || !self.is(target, TABLE_CONTEXT) {
+            return (target, None);
+        }
+        match self.open.iter().rposition(|&id| self.is(id, &["table"])) {
+            Some(i) => match self.nodes[self.open[i]].parent {
+                Some(parent) => (parent, Some(self.open[i])),
+                None => (self.open[i - 1], None),
+            },
+            None => (self.open[0], None),
+        }
+    }
+
+    /// Inserts a HTML element, or a `svg` or `math` element when
+    /// `foreign` is set.
+    fn insert_element(&mut self, token: Token, foreign: bool) -> usize {
+        let namespace = match &token {
+            Token::StartTag { name, .. } if foreign && name == "math" => Namespace::MathMl,
+            _ if foreign => Namespace::Svg,
+            _ => Namespace::Html,
+        };
+        self.insert_element_in(token, namespace)
+    }
+
+    fn insert_element_in(&mut self, token: Token, namespace: Namespace) -> usize {
+        let Token::StartTag {
+            name, attributes, ..
+        } = token
+        else {
+            unreachable!("elements are only created for start tags")
+        };
+        let id = self.create(Data::Element(create_element(&name, attributes)), namespace);
+        let place = self.appropriate_place(None);
+        self.insert_at(place, id);
+        self.open.push(id);
+        id
+    }
+
+    fn insert_foreign(&mut self, token: Token) {
+        let self_closing = matches!(token, Token::StartTag { self_closing, .. } if self_closing);
+        // Inside foreign content, elements are in the namespace of the
+        // element they are nested in, even `svg` and `math`.
+        let namespace = self.nodes[self.current()].namespace;
+        self.insert_element_in(token, namespace);
+        if self_closing {
+            self.open.pop();
+        }
+    }
+
+    /// Inserts an element whose content is read as text.
+    fn insert_raw_text(&mut self, token: Token) {
+        let id = self.insert_element(token, false);
+        let name = self.name(id).to_string();
+        self.tokenizer.set_raw_text(&name);
+        self.original_mode = self.mode;
+        self.mode = Mode::Text;
+    }
+
+    fn insert_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let (parent, before) = self.appropriate_place(None);
+        if parent == DOCUMENT {
+            return;
+        }
+        let children = &self.nodes[parent].children;
+        let previous = match before {
+            Some(before) => children
+                .iter()
+                .position(|&c| c == before)
+                .and_then(|i| i.checked_sub(1))
+                .map(|i| children[i]),
+            None => children.last().copied(),
+        };
+        if let Some(previous) = previous {
+            if let Data::Text(existing) = &mut self.nodes[previous].data {
+                existing.push_str(text);
+                return;
+            }
+        }
+        let id = self.create(Data::Text(text.to_string()), Namespace::Html);
+        self.insert_at((parent, before), id);
+    }
+
+    fn insert_comment(&mut self, comment: String, parent: Option<usize>) {
+        let place = match parent {
+            Some(parent) => (parent, None),
+            None => self.appropriate_place(None),
+        };
+        let id = self.create(Data::Comment(comment), Namespace::Html);
+        self.insert_at(place, id);
+    }
+
+    fn add_missing_attributes(&mut self, id: usize, attributes: Vec<(String, Option<String>)>) {
+        if let Some(tag) = self.tag_mut(id) {
+            for (key, value) in attributes {
+                if !tag.has_attribute(&key) {
+                    tag.set_attribute(&key, value.as_deref());
+                }
+            }
+        }
+    }
+
+    // The stack of open elements
+
+    fn in_scope(&self, names: &[&str], scope: Scope) -> bool {
+        self.in_scope_by(|id| self.is(id, names), scope)
+    }
+
+    fn in_scope_by(&self, target: impl Fn(usize) -> bool, scope: Scope) -> bool {
+        for &id in self.open.iter().rev() {
+            if target(id) {
+                return true;
+            }
+            let name = self.name(id);
+            let boundary = if self.is_foreign(id) {
+                scope == Scope::Select
+                    || (scope != Scope::Table && INTEGRATION_POINTS.contains(&name))
+            } else {
+                match scope {
+                    Scope::Select => !matches!(name, "optgroup" | "option"),
+                    Scope::Table => matches!(name, "html" | "table" | "template"),
+                    _ => {
+                        matches!(
+                            name,
+                            "applet"
+                                | "caption"
+                                | "html"
+                                | "table"
+                                | "td"
+                                | "th"
+                                | "marquee"
+                                | "object"
+                                | "template"
+                        ) || (scope == Scope::ListItem && matches!(name, "ol" | "ul"))
+                            || (scope == Scope::Button && name == "button")
+                    }
+                }
+            };
+            if boundary {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn pop_until(&mut self, names: &[&str]) {
+        while let Some(id) = self.open.pop() {
+            if self.is(id, names) {
+                break;
+            }
+        }
+    }
+
+    fn remove_open(&mut self, id: usize) {
+        self.open.retain(|&open| open != id);
+    }
+
+    fn generate_implied_end_tags(&mut self, except: Option<&str>) {
+        while let Some(&id) = self.open.last() {
+            if !self.is(id, IMPLIED_END_TAGS) || Some(self.name(id)) == except {
+                break;
+            }
+            self.open.pop();
+        }
+    }
+
+    fn close_p(&mut self) {
+        self.generate_implied_end_tags(Some("p"));
+        self.pop_until(&["p"]);
+    }
+
+    fn close_p_in_button_scope(&mut self) {
+        if self.in_scope(&["p"], Scope::Button) {
+            self.close_p();
+        }
+    }
+
+    fn clear_to_context(&mut self, names: &[&str]) {
+        while !self.is(self.current(), names) {
+            self.open.pop();
+        }
+    }
+
+    fn reset_insertion_mode(&mut self) {
+        for (i, &id) in self.open.iter().enumerate().rev() {
+            let last = i == 0;
+            let name = match self.context {
+                Some(context) if last => context,
+                _ => self.name(id),
+            };
+            self.mode = match name {
+                "select" => {
+                    let in_table = self.open[..i]
+                        .iter()
+                        .rev()
+                        .take_while(|&&id| !self.is(id, &["template"]))
+                        .any(|&id| self.is(id, &["table"]));
+                    if in_table {
+                        Mode::InSelectInTable
+                    } else {
+                        Mode::InSelect
+                    }
+                }
+                "td" | "th" if !last => Mode::InCell,
+                "tr" => Mode::InRow,
+                "tbody" | "thead" | "tfoot" => Mode::InTableBody,
+                "caption" => Mode::InCaption,
+                "colgroup" => Mode::InColumnGroup,
+                "table" => Mode::InTable,
+                "head" if !last => Mode::InHead,
+                "body" => Mode::InBody,
+                "html" if self.head.is_none() => Mode::BeforeHead,
+                "html" => Mode::AfterHead,
+                _ if last => Mode::InBody,
+                _ => continue,
+            };
+            return;
+        }
+    }
+
+    // The list of active formatting elements
+
+    fn formatting_position(&self, id: usize) -> Option<usize> {
+        self.formatting
+            .iter()
+            .position(|&entry| entry == Formatting::Element(id))
+    }
+
+    /// Returns the last formatting element with the given name after
+    /// the last marker.
+    fn formatting_element(&self, name: &str) -> Option<usize> {
+        self.formatting
+            .iter()
+            .rev()
+            .take_while(|&&entry| entry != Formatting::Marker)
+            .find_map(|&entry| match entry {
+                Formatting::Element(id) if self.name(id) == name => Some(id),
+                _ => None,
+            })
+    }
+
+    fn push_formatting(&mut self, id: usize) {
+        // Only three identical elements are kept after the last marker.
+        let same = self
+            .formatting
+            .iter()
+            .enumerate()
+            .rev()
+            .take_while(|(_, &entry)| entry != Formatting::Marker)
+            .filter(|(_, &entry)| match entry {
+                Formatting::Element(other) => self.tag(other) == self.tag(id),
+                Formatting::Marker => false,
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if same.len() >= 3 {
+            self.formatting.remove(same[same.len() - 1]);
+        }
+        self.formatting.push(Formatting::Element(id));
+    }
+
+    fn clear_formatting_to_marker(&mut self) {
+        while let Some(entry) = self.formatting.pop() {
+            if entry == Formatting::Marker {
+                break;
+            }
+        }
+    }
+
+    fn clone_element(&mut self, id: usize) -> usize {
+        let tag = self.tag(id).cloned().expect("only elements are cloned");
+        let namespace = self.nodes[id].namespace;
+        self.create(Data::Element(tag), namespace)
+    }
+
+    fn reconstruct_formatting(&mut self) {
+        let is_open = |this: &Self, entry: Formatting| match entry {
+            Formatting::Marker => true,
+            Formatting::Element(id) => this.open.contains(&id),
+        };
+        match self.formatting.last() {
+            Some(&entry) if !is_open(self, entry) => {}
+            _ => return,
+        }
+
+        let mut i = self.formatting.len() - 1;
+        while i > 0 {
+            i -= 1;
+            if is_open(self, self.formatting[i]) {
+                i += 1;
+                break;
+            }
+        }
+        for i in i..self.formatting.len() {
+            let Formatting::Element(id) = self.formatting[i] else {
+                continue;
+            };
+            let new = self.clone_element(id);
+            let place = self.appropriate_place(None);
+            self.insert_at(place, new);
+            self.open.push(new);
+            self.formatting[i] = Formatting::Element(new);
+        }
+    }
+
+    /// Fixes up misnested formatting elements, like `<b><p></b></p>`.
+    fn adoption_agency(&mut self, subject: &str) {
+        let current = self.current();
+        if self.is(current, &[subject]) && self.formatting_position(current).is_none() {
+            self.open.pop();
+            return;
+        }
+
+        for _ in 0..8 {
+            let Some(element) = self.formatting_element(subject) else {
+                self.any_other_end_tag(subject);
+                return;
+            };
+            let Some(element_index) = self.open.iter().position(|&id| id == element) else {
+                self.formatting
+                    .retain(|&e| e != Formatting::Element(element));
+                return;
+            };
+            if !self.in_scope_by(|id| id == element, Scope::Default) {
+                return;
+            }
+            let furthest_block = self.open[element_index + 1..]
+                .iter()
+                .copied()
+                .find(|&id| self.is_special(id));
+            let Some(furthest_block) = furthest_block else {
+                self.open.truncate(element_index);
+                self.formatting
+                    .retain(|&e| e != Formatting::Element(element));
+                return;
+            };
+
+            let common_ancestor = self.open[element_index - 1];
+            let mut bookmark = self
+                .formatting_position(element)
+                .expect("the element is in the list");
+            let mut node_index = self
+                .open
+                .iter()
+                .position(|&id| id == furthest_block)
+                .expect("the block is open");
+            let mut last_node = furthest_block;
+            let mut counter = 0;
+            loop {
+                counter += 1;
+                node_index -= 1;
+                let node = self.open[node_index];
+                if node == element {
+                    break;
+                }
+                let mut position = self.formatting_position(node);
+                if counter > 3 {
+                    if let Some(i) = position.take() {
+                        self.formatting.remove(i);
+                        if i < bookmark {
+                            bookmark -= 1;
+                        }
+                    }
+                }
+                let Some(position) = position else {
+                    self.open.remove(node_index);
+                    continue;
+                };
+                let new = self.clone_element(node);
+                self.formatting[position] = Formatting::Element(new);
+                self.open[node_index] = new;
+                if last_node == furthest_block {
+                    bookmark = position + 1;
+                }
+                self.insert_at((new, None), last_node);
+                last_node = new;
+            }
+
+            let place = self.appropriate_place(Some(common_ancestor));
+            self.insert_at(place, last_node);
+
+            let new = self.clone_element(element);
+            let children = std::mem::take(&mut self.nodes[furthest_block].children);
+            for &child in &children {
+                self.nodes[child].parent = Some(new);
+            }
+            self.nodes[new].children = children;
+            self.insert_at((furthest_block, None), new);
+
+            let position = self
+                .formatting_position(element)
+                .expect("the element is in the list");
+            self.formatting.remove(position);
+            if position < bookmark {
+                bookmark -= 1;
+            }
+            self.formatting.insert(
+                bookmark.min(self.formatting.len()),
+                Formatting::Element(new),
+            );
+
+            self.remove_open(element);
+            let block_index = self
+                .open
+                .iter()
+                .position(|&id| id == furthest_block)
+                .expect("the block is open");
+            self.open.insert(block_index + 1, new);
+        }
+    }
+
+    fn any_other_end_tag(&mut self, name: &str) {
+        for i in (0..self.open.len()).rev() {
+            let id = self.open[i];
+            if self.is(id, &[name]) {
+                self.generate_implied_end_tags(Some(name));
+                self.open.truncate(i);
+                return;
+            }
+            if self.is_special(id) {
+                return;
+            }
+        }
+    }
+
+    // Token dispatch
+
+    fn process(&mut self, token: Token) {
+        if self.in_foreign_content(&token) {
+            self.foreign_content(token);
+        } else {
+            self.process_html(token);
+        }
+    }
+
+    fn process_html(&mut self, token: Token) {
+        match self.mode {
+            Mode::Initial => self.initial(token),
+            Mode::BeforeHtml => self.before_html(token),
+            Mode::BeforeHead => self.before_head(token),
+            Mode::InHead => self.in_head(token),
+            Mode::AfterHead => self.after_head(token),
+            Mode::InBody => self.in_body(token),
+            Mode::Text => self.text(token),
+            Mode::InTable => self.in_table(token),
+            Mode::InCaption => self.in_caption(token),
+            Mode::InColumnGroup => self.in_column_group(token),
+            Mode::InTableBody => self.in_table_body(token),
+            Mode::InRow => self.in_row(token),
+            Mode::InCell => self.in_cell(token),
+            Mode::InSelect | Mode::InSelectInTable => self.in_select(token),
+            Mode::AfterBody => self.after_body(token),
+            Mode::AfterAfterBody => self.after_after_body(token),
+        }
+    }
+
+    fn in_foreign_content(&self, token: &Token) -> bool {
+        let Some(&current) = self.open.last() else {
+            return false;
+        };
+        if !self.is_foreign(current) {
+            return false;
+        }
+        let integration_point = INTEGRATION_POINTS.contains(&self.name(current));
+        !(integration_point && matches!(token, Token::StartTag { .. } | Token::Text { .. }))
+    }
+
+    fn foreign_content(&mut self, token: Token) {
+        match token {
+            Token::Text { text, .. } => self.insert_text(&text),
+            Token::Comment(comment) => self.insert_comment(comment, None),
+            Token::Doctype(_) => {}
+            Token::StartTag {
+                ref name,
+                ref attributes,
+                ..
+            } if BREAKOUT.contains(&name.as_str())
+                || (name == "font"
+                    && attributes
+                        .iter()
+                        .any(|(key, _)| matches!(key.as_str(), "color" | "face" | "size"))) =>
+            {
+                while self.is_foreign(self.current())
+                    && !INTEGRATION_POINTS.contains(&self.name(self.current()))
+                {
+                    self.open.pop();
+                }
+                self.process_html(token);
+            }
+            Token::StartTag { .. } => self.insert_foreign(token),
+            Token::EndTag { ref name, .. } => {
+                for i in (0..self.open.len()).rev() {
+                    let id = self.open[i];
+                    if !self.is_foreign(id) {
+                        self.process_html(token);
+                        return;
+                    }
+                    if self.name(id) == name {
+                        self.open.truncate(i);
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    // Insertion modes
+
+    fn initial(&mut self, token: Token) {
+        match token {
+            Token::Text { text, offset } => {
+                let (_, rest) = split_whitespace(&text);
+                if !rest.is_empty() {
+                    self.quirks = true;
+                    self.mode = Mode::BeforeHtml;
+                    self.process(text_token(rest, offset));
+                }
+            }
+            Token::Comment(comment) => self.insert_comment(comment, Some(DOCUMENT)),
+            Token::Doctype(name) => {
+                // Only `<!DOCTYPE html>` gets the standards mode.
+                self.quirks = name != "html";
+                self.doctype = Some(name);
+                self.mode = Mode::BeforeHtml;
+            }
+            token => {
+                self.quirks = true;
+                self.mode = Mode::BeforeHtml;
+                self.process(token);
+            }
+        }
+    }
+
+    fn before_html(&mut self, token: Token) {
+        let token = match token {
+            Token::Text { text, offset } => {
+                let (_, rest) = split_whitespace(&text);
+                if rest.is_empty() {
+                    return;
+                }
+                text_token(rest, offset)
+            }
+            Token::Comment(comment) => return self.insert_comment(comment, Some(DOCUMENT)),
+            Token::Doctype(_) => return,
+            Token::StartTag { ref name, .. } if name == "html" => {
+                let id = self.insert_element_into(token, DOCUMENT);
+                self.open.push(id);
+                self.mode = Mode::BeforeHead;
+                return;
+            }
+            Token::EndTag { ref name, .. }
+                if !matches!(name.as_str(), "head" | "body" | "html" | "br") =>
+            {
+                return
+            }
+            token => token,
+        };
+        let id = self.insert_element_into(start_tag("html"), DOCUMENT);
+        self.open.push(id);
+        self.mode = Mode::BeforeHead;
+        self.process(token);
+    }
+
+    fn insert_element_into(&mut self, token: Token, parent: usize) -> usize {
+        let Token::StartTag {
+            name, attributes, ..
+        } = token
+        else {
+            unreachable!("elements are only created for start tags")
+        };
+        let id = self.create(
+            Data::Element(create_element(&name, attributes)),
+            Namespace::Html,
+        );
+        self.insert_at((parent, None), id);
+        id
+    }
+
+    fn before_head(&mut self, token: Token) {
+        let token = match token {
+            Token::Text { text, offset } => {
+                let (_, rest) = split_whitespace(&text);
+                if rest.is_empty() {
+                    return;
+                }
+                text_token(rest, offset)
+            }
+            Token::Comment(comment) => return self.insert_comment(comment, None),
+            Token::Doctype(_) => return,
+            Token::StartTag { ref name, .. } if name == "html" => return self.in_body(token),
+            Token::StartTag { ref name, .. } if name == "head" => {
+                self.head = Some(self.insert_element(token, false));
+                self.mode = Mode::InHead;
+                return;
+            }
+            Token::EndTag { ref name, .. }
+                if !matches!(name.as_str(), "head" | "body" | "html" | "br") =>
+            {
+                return
+            }
+            token => token,
+        };
+        self.head = Some(self.insert_element(start_tag("head"), false));
+        self.mode = Mode::InHead;
+        self.process(token);
+    }
+
+    fn in_head(&mut self, token: Token) {
+        match token {
+            Token::Text { ref text, offset } => {
+                let (whitespace, rest) = split_whitespace(text);
+                self.insert_text(whitespace);
+                if rest.is_empty() {
+                    return;
+                }
+                let rest = text_token(rest, offset);
+                self.open.pop();
+                self.mode = Mode::AfterHead;
+                self.process(rest);
+            }
+            Token::Comment(comment) => self.insert_comment(comment, None),
+            Token::Doctype(_) => {}
+            Token::StartTag { ref name, .. } => match name.as_str() {
+                "html" => self.in_body(token),
+                "base" | "basefont" | "bgsound" | "link" | "meta" => {
+                    self.insert_element(token, false);
+                    self.open.pop();
+                }
+                "title" | "noscript" | "noframes" | "style" | "script" => {
+                    self.insert_raw_text(token)
+                }
+                "template" => {
+                    self.insert_element(token, false);
+                }
+                "head" => {}
+                _ => {
+                    self.open.pop();
+                    self.mode = Mode::AfterHead;
+                    self.process(token);
+                }
+            },
+            Token::EndTag { ref name, .. } => match name.as_str() {
+                "head" => {
+                    self.open.pop();
+                    self.mode = Mode::AfterHead;
+                }
+                "template" if self.open.iter().any(|&id| self.is(id, &["template"])) => {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(&["template"]);
+                }
+                "body" | "html" | "br" => {
+                    self.open.pop();
+                    self.mode = Mode::AfterHead;
+                    self.process(token);
+                }
+                _ => {}
+            },
+        }
+    }
+
+    fn after_head(&mut self, token: Token) {
+        match token {
+            Token::Text { ref text, offset } => {
+                let (whitespace, rest) = split_whitespace(text);
+                self.insert_text(whitespace);
+                if !rest.is_empty() {
+                    self.insert_element(start_tag("body"), false);
+                    self.mode = Mode::InBody;
+                    self.process(text_token(rest, offset));
+                }
+                return;
+            }
+            Token::Comment(comment) => return self.insert_comment(comment, None),
+            Token::Doctype(_) => return,
+            Token::StartTag { ref name, .. } => match name.as_str() {
+                "html" => return self.in_body(token),
+                "body" | "frameset" => {
+                    self.insert_element(token, false);
+                    self.mode = Mode::InBody;
+                    return;
+                }
+                "base" | "basefont" | "bgsound" | "link" | "meta" | "noframes" | "script"
+                | "style" | "template" | "title" => {
+                    let head = self.head.expect("the head was created before");
+                    self.open.push(head);
+                    self.in_head(token);
+                    self.remove_open(head);
+                    return;
+                }
+                "head" => return,
+                _ => {}
+            },
+            Token::EndTag { ref name, .. } => match name.as_str() {
+                "template" => return self.in_head(token),
+                "body" | "html" | "br" => {}
+                _ => return,
+            },
+        }
+        self.insert_element(start_tag("body"), false);
+        self.mode = Mode::InBody;
+        self.process(token);
+    }
+
+    fn in_body(&mut self, token: Token) {
+        match token {
+            Token::Text { text, .. } => {
+                self.reconstruct_formatting();
+                self.insert_text(&text);
+            }
+            Token::Comment(comment) => self.insert_comment(comment, None),
+            Token::Doctype(_) => {}
+            Token::StartTag { .. } => self.in_body_start_tag(token),
+            Token::EndTag { .. } => self.in_body_end_tag(token),
+        }
+    }
+
+    fn in_body_start_tag(&mut self, token: Token) {
+        let Token::StartTag {
+            ref name,
+            self_closing,
+            ..
+        } = token
+        else {
+            return;
+        };
+        match name.as_str() {
+            "html" => {
+                if let Token::StartTag { attributes, .. } = token {
+                    self.add_missing_attributes(self.open[0], attributes);
+                }
+            }
+            "base" | "basefont" | "bgsound" | "link" | "meta" | "noframes" | "script" | "style"
+            | "template" | "title" => self.in_head(token),
+            "body" => {
+                if self.open.len() > 1 && self.is(self.open[1], &["body"]) {
+                    if let Token::StartTag { attributes, .. } = token {
+                        self.add_missing_attributes(self.open[1], attributes);
+                    }
+                }
+            }
+            "frameset" => {}
+            "address" | "article" | "aside" | "blockquote" | "center" | "details" | "dialog"
+            | "dir" | "div" | "dl" | "fieldset" | "figcaption" | "figure" | "footer" | "header"
+            | "hgroup" | "main" | "menu" | "nav" | "ol" | "p" | "search" | "section"
+            | "summary" | "ul" => {
+                self.close_p_in_button_scope();
+                self.insert_element(token, false);
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.close_p_in_button_scope();
+                if self.is(self.current(), HEADINGS) {
+                    self.open.pop();
+                }
+                self.insert_element(token, false);
+            }
+            "pre" | "listing" => {
+                self.close_p_in_button_scope();
+                self.insert_element(token, false);
+                self.skip_newline = true;
+            }
+            "form" => {
+                if self.form.is_none() {
+                    self.close_p_in_button_scope();
+                    self.form = Some(self.insert_element(token, false));
+                }
+            }
+            "li" | "dd" | "dt" => {
+                let names: &[&str] = if name == "li" { &["li"] } else { &["dd", "dt"] };
+                for i in (0..self.open.len()).rev() {
+                    let id = self.open[i];
+                    if self.is(id, names) {
+                        let name = self.name(id).to_string();
+                        self.generate_implied_end_tags(Some(&name));
+                        self.pop_until(&[&name]);
+                        break;
+                    }
+                    if self.is_special(id) && !self.is(id, &["address", "div", "p"]) {
+                        break;
+                    }
+                }
+                self.close_p_in_button_scope();
+                self.insert_element(token, false);
+            }
+            "plaintext" => {
+                self.close_p_in_button_scope();
+                self.insert_element(token, false);
+                self.tokenizer.set_plaintext();
+            }
+            "button" => {
+                if self.in_scope(&["button"], Scope::Default) {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(&["button"]);
+                }
+                self.reconstruct_formatting();
+                self.insert_element(token, false);
+            }
+            "a" => {
+                if let Some(existing) = self.formatting_element("a") {
+                    self.adoption_agency("a");
+                    self.formatting
+                        .retain(|&e| e != Formatting::Element(existing));
+                    self.remove_open(existing);
+                }
+                self.reconstruct_formatting();
+                let id = self.insert_element(token, false);
+                self.push_formatting(id);
+            }
+            "nobr" => {
+                self.reconstruct_formatting();
+                if self.in_scope(&["nobr"], Scope::Default) {
+                    self.adoption_agency("nobr");
+                    self.reconstruct_formatting();
+                }
+                let id = self.insert_element(token, false);
+                self.push_formatting(id);
+            }
+            name if FORMATTING.contains(&name) => {
+                self.reconstruct_formatting();
+                let id = self.insert_element(token, false);
+                self.push_formatting(id);
+            }
+            "applet" | "marquee" | "object" => {
+                self.reconstruct_formatting();
+                self.insert_element(token, false);
+                self.formatting.push(Formatting::Marker);
+            }
+            "table" => {
+                if !self.quirks {
+                    self.close_p_in_button_scope();
+                }
+                self.insert_element(token, false);
+                self.mode = Mode::InTable;
+            }
+            "area" | "br" | "embed" | "img" | "keygen" | "wbr" | "input" => {
+                self.reconstruct_formatting();
+                self.insert_element(token, false);
+                self.open.pop();
+            }
+            "param" | "source" | "track" => {
+                self.insert_element(token, false);
+                self.open.pop();
+            }
+            "hr" => {
+                self.close_p_in_button_scope();
+                self.insert_element(token, false);
+                self.open.pop();
+            }
+            "image" => {
+                let Token::StartTag {
+                    attributes,
+                    self_closing,
+                    offset,
+                    ..
+                } = token
+                else {
+                    return;
+                };
+                self.process(Token::StartTag {
+                    name: "img".to_string(),
+                    attributes,
+                    self_closing,
+                    offset,
+                });
+            }
+            "textarea" => {
+                self.insert_raw_text(token);
+                self.skip_newline = true;
+            }
+            "xmp" => {
+                self.close_p_in_button_scope();
+                self.reconstruct_formatting();
+                self.insert_raw_text(token);
+            }
+            "iframe" | "noembed" | "noscript" => self.insert_raw_text(token),
+            "select" => {
+                self.reconstruct_formatting();
+                self.insert_element(token, false);
+                self.mode = match self.mode {
+                    Mode::InTable
+                    | Mode::InCaption
+                    | Mode::InTableBody
+                    | Mode::InRow
+                    | Mode::InCell => Mode::InSelectInTable,
+                    _ => Mode::InSelect,
+                };
+            }
+            "optgroup" | "option" => {
+                if self.is(self.current(), &["option"]) {
+                    self.open.pop();
+                }
+                self.reconstruct_formatting();
+                self.insert_element(token, false);
+            }
+            "rb" | "rtc" | "rp" | "rt" => {
+                if self.in_scope(&["ruby"], Scope::Default) {
+                    let except = if matches!(name.as_str(), "rp" | "rt") {
+                        Some("rtc")
+                    } else {
+                        None
+                    };
+                    self.generate_implied_end_tags(except);
+                }
+                self.insert_element(token, false);
+            }
+            "math" | "svg" => {
+                self.reconstruct_formatting();
+                self.insert_element(token, true);
+                if self_closing {
+                    self.open.pop();
+                }
+            }
+            "caption" | "col" | "colgroup" | "frame" | "head" | "tbody" | "td" | "tfoot" | "th"
+            | "thead" | "tr" => {}
+            _ => {
+                self.reconstruct_formatting();
+                self.insert_element(token, false);
+            }
+        }
+    }
+
+    fn in_body_end_tag(&mut self, token: Token) {
+        let Token::EndTag { ref name, .. } = token else {
+            return;
+        };
+        match name.as_str() {
+            "template" => self.in_head(token),
+            "body" | "html" => {
+                if self.in_scope(&["body"], Scope::Default) {
+                    self.mode = Mode::AfterBody;
+                    if name == "html" {
+                        self.process(token);
+                    }
+                }
+            }
+            "address" | "article" | "aside" | "blockquote" | "button" | "center" | "details"
+            | "dialog" | "dir" | "div" | "dl" | "fieldset" | "figcaption" | "figure" | "footer"
+            | "header" | "hgroup" | "listing" | "main" | "menu" | "nav" | "ol" | "pre"
+            | "search" | "section" | "summary" | "ul" => {
+                if self.in_scope(&[name], Scope::Default) {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(&[name]);
+                }
+            }
+            "form" => {
+                let Some(form) = self.form.take() else {
+                    return;
+                };
+                if self.in_scope_by(|id| id == form, Scope::Default) {
+                    self.generate_implied_end_tags(None);
+                    self.remove_open(form);
+                }
+            }
+            "p" => {
+                if !self.in_scope(&["p"], Scope::Button) {
+                    self.insert_element(start_tag("p"), false);
+                }
+                self.close_p();
+            }
+            "li" => {
+                if self.in_scope(&["li"], Scope::ListItem) {
+                    self.generate_implied_end_tags(Some("li"));
+                    self.pop_until(&["li"]);
+                }
+            }
+            "dd" | "dt" => {
+                if self.in_scope(&[name], Scope::Default) {
+                    self.generate_implied_end_tags(Some(name));
+                    self.pop_until(&[name]);
+                }
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                if self.in_scope(HEADINGS, Scope::Default) {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(HEADINGS);
+                }
+            }
+            name if FORMATTING.contains(&name) => self.adoption_agency(name),
+            "applet" | "marquee" | "object" => {
+                if self.in_scope(&[name], Scope::Default) {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(&[name]);
+                    self.clear_formatting_to_marker();
+                }
+            }
+            "br" => self.in_body_start_tag(start_tag("br")),
+            name => self.any_other_end_tag(name),
+        }
+    }
+
+    fn text(&mut self, token: Token) {
+        match token {
+            Token::Text { text, .. } => self.insert_text(&text),
+            Token::EndTag { .. } => {
+                self.open.pop();
+                self.mode = self.original_mode;
+            }
+            token => {
+                self.open.pop();
+                self.mode = self.original_mode;
+                self.process(token);
+            }
+        }
+    }
+
+    /// Processes the token as if it were in the body, moving anything
+    /// that ends up inside of a table in front of it.
+    fn foster(&mut self, token: Token) {
+        self.foster_parenting = true;
+        self.in_body(token);
+        self.foster_parenting = false;
+    }
+
+    fn in_table(&mut self, token: Token) {
+        match token {
+            Token::Text { ref text, .. } => {
+                if self.is(self.current(), TABLE_CONTEXT) && text.trim_ascii().is_empty() {
+                    self.insert_text(text);
+                } else {
+                    self.foster(token);
+                }
+            }
+            Token::Comment(comment) => self.insert_comment(comment, None),
+            Token::Doctype(_) => {}
+            Token::StartTag {
+                ref name,
+                ref attributes,
+                ..
+            } => match name.as_str() {
+                "caption" => {
+                    self.clear_to_context(&["table", "template", "html"]);
+                    self.formatting.push(Formatting::Marker);
+                    self.insert_element(token, false);
+                    self.mode = Mode::InCaption;
+                }
+                "colgroup" => {
+                    self.clear_to_context(&["table", "template", "html"]);
+                    self.insert_element(token, false);
+                    self.mode = Mode::InColumnGroup;
+                }
+                "col" => {
+                    self.clear_to_context(&["table", "template", "html"]);
+                    self.insert_element(start_tag("colgroup"), false);
+                    self.mode = Mode::InColumnGroup;
+                    self.process(token);
+                }
+                "tbody" | "tfoot" | "thead" => {
+                    self.clear_to_context(&["table", "template", "html"]);
+                    self.insert_element(token, false);
+                    self.mode = Mode::InTableBody;
+                }
+                "td" | "th" | "tr" => {
+                    self.clear_to_context(&["table", "template", "html"]);
+                    self.insert_element(start_tag("tbody"), false);
+                    self.mode = Mode::InTableBody;
+                    self.process(token);
+                }
+                "table" => {
+                    if self.in_scope(&["table"], Scope::Table) {
+                        self.pop_until(&["table"]);
+                        self.reset_insertion_mode();
+                        self.process(token);
+                    }
+                }
+                "style" | "script" | "template" => self.in_head(token),
+                "input"
+                    if attributes.iter().any(|(key, value)| {
+                        key == "type"
+                            && value
+                                .as_deref()
+                                .is_some_and(|v| v.eq_ignore_ascii_case("hidden"))
+                    }) =>
+                {
+                    self.insert_element(token, false);
+                    self.open.pop();
+                }
+                "form" => {
+                    if self.form.is_none() {
+                        self.form = Some(self.insert_element(token, false));
+                        self.open.pop();
+                    }
+                }
+                _ => self.foster(token),
+            },
+            Token::EndTag { ref name, .. } => match name.as_str() {
+                "table" => {
+                    if self.in_scope(&["table"], Scope::Table) {
+                        self.pop_until(&["table"]);
+                        self.reset_insertion_mode();
+                    }
+                }
+                "body" | "caption" | "col" | "colgroup" | "html" | "tbody" | "td" | "tfoot"
+                | "th" | "thead" | "tr" => {}
+                "template" => self.in_head(token),
+                _ => self.foster(token),
+            },
+        }
+    }
+
+    fn close_caption(&mut self) {
+        self.generate_implied_end_tags(None);
+        self.pop_until(&["caption"]);
+        self.clear_formatting_to_marker();
+        self.mode = Mode::InTable;
+    }
+
+    fn in_caption(&mut self, token: Token) {
+        match &token {
+            Token::EndTag { name, .. } if name == "caption" => {
+                if self.in_scope(&["caption"], Scope::Table) {
+                    self.close_caption();
+                }
+            }
+            Token::StartTag { name, .. }
+                if matches!(
+                    name.as_str(),
+                    "caption"
+                        | "col"
+                        | "colgroup"
+                        | "tbody"
+                        | "td"
+                        | "tfoot"
+                        | "th"
+                        | "thead"
+                        | "tr"
+                ) =>
+            {
+                if self.in_scope(&["caption"], Scope::Table) {
+                    self.close_caption();
+                    self.process(token);
+                }
+            }
+            Token::EndTag { name, .. } if name == "table" => {
+                if self.in_scope(&["caption"], Scope::Table) {
+                    self.close_caption();
+                    self.process(token);
+                }
+            }
+            Token::EndTag { name, .. }
+                if matches!(
+                    name.as_str(),
+                    "body"
+                        | "col"
+                        | "colgroup"
+                        | "html"
+                        | "tbody"
+                        | "td"
+                        | "tfoot"
+                        | "th"
+                        | "thead"
+                        | "tr"
+                ) => {}
+            _ => self.in_body(token),
+        }
+    }
+
+    fn in_column_group(&mut self, token: Token) {
+        match token {
+            Token::Text { ref text, offset } => {
+                let (whitespace, rest) = split_whitespace(text);
+                self.insert_text(whitespace);
+                if !rest.is_empty() {
+                    self.in_column_group(text_token(rest, offset));
+                }
+                return;
+            }
+            Token::Comment(comment) => return self.insert_comment(comment, None),
+            Token::Doctype(_) => return,
+            Token::StartTag { ref name, .. } => match name.as_str() {
+                "html" => return self.in_body(token),
+                "col" => {
+                    self.insert_element(token, false);
+                    self.open.pop();
+                    return;
+                }
+                "template" => return self.in_head(token),
+                _ => {}
+            },
+            Token::EndTag { ref name, .. } => match name.as_str() {
+                "colgroup" => {
+                    if self.is(self.current(), &["colgroup"]) {
+                        self.open.pop();
+                        self.mode = Mode::InTable;
+                    }
+                    return;
+                }
+                "col" => return,
+                "template" => return self.in_head(token),
+                _ => {}
+            },
+        }
+        if self.is(self.current(), &["colgroup"]) {
+            self.open.pop();
+            self.mode = Mode::InTable;
+            self.process(token);
+        }
+    }
+
+    fn in_table_body(&mut self, token: Token) {
+        let section_context = ["tbody", "tfoot", "thead", "template", "html"];
+        match &token {
+            Token::StartTag { name, .. } if name == "tr" => {
+                self.clear_to_context(&section_context);
+                self.insert_element(token, false);
+                self.mode = Mode::InRow;
+            }
+            Token::StartTag { name, .. } if matches!(name.as_str(), "th" | "td") => {
+                self.clear_to_context(&section_context);
+                self.insert_element(start_tag("tr"), false);
+                self.mode = Mode::InRow;
+                self.process(token);
+            }
+            Token::EndTag { name, .. } if TABLE_SECTIONS.contains(&name.as_str()) => {
+                if self.in_scope(&[name], Scope::Table) {
+                    self.clear_to_context(&section_context);
+                    self.open.pop();
+                    self.mode = Mode::InTable;
+                }
+            }
+            Token::StartTag { name, .. }
+                if matches!(
+                    name.as_str(),
+                    "caption" | "col" | "colgroup" | "tbody" | "tfoot" | "thead"
+                ) =>
+            {
+                self.close_table_section(token)
+            }
+            Token::EndTag { name, .. } if name == "table" => self.close_table_section(token),
+            Token::EndTag { name, .. }
+                if matches!(
+                    name.as_str(),
+                    "body" | "caption" | "col" | "colgroup" | "html" | "td" | "th" | "tr"
+                ) => {}
+            _ => self.in_table(token),
+        }
+    }
+
+    fn close_table_section(&mut self, token: Token) {
+        if self.in_scope(TABLE_SECTIONS, Scope::Table) {
+            self.clear_to_context(&["tbody", "tfoot", "thead", "template", "html"]);
+            self.open.pop();
+            self.mode = Mode::InTable;
+            self.process(token);
+        }
+    }
+
+    fn in_row(&mut self, token: Token) {
+        match &token {
+            Token::StartTag { name, .. } if matches!(name.as_str(), "th" | "td") => {
+                self.clear_to_context(&["tr", "template", "html"]);
+                self.insert_element(token, false);
+                self.mode = Mode::InCell;
+                self.formatting.push(Formatting::Marker);
+            }
+            Token::EndTag { name, .. } if name == "tr" => {
+                if self.in_scope(&["tr"], Scope::Table) {
+                    self.clear_to_context(&["tr", "template", "html"]);
+                    self.open.pop();
+                    self.mode = Mode::InTableBody;
+                }
+            }
+            Token::StartTag { name, .. }
+                if matches!(
+                    name.as_str(),
+                    "caption" | "col" | "colgroup" | "tbody" | "tfoot" | "thead" | "tr"
+                ) =>
+            {
+                self.close_row(token)
+            }
+            Token::EndTag { name, .. } if name == "table" => self.close_row(token),
+            Token::EndTag { name, .. } if TABLE_SECTIONS.contains(&name.as_str()) => {
+                if self.in_scope(&[name], Scope::Table) {
+                    self.close_row(token);
+                }
+            }
+            Token::EndTag { name, .. }
+                if matches!(
+                    name.as_str(),
+                    "body" | "caption" | "col" | "colgroup" | "html" | "td" | "th"
+                ) => {}
+            _ => self.in_table(token),
+        }
+    }
+
+    fn close_row(&mut self, token: Token) {
+        if self.in_scope(&["tr"], Scope::Table) {
+            self.clear_to_context(&["tr", "template", "html"]);
+            self.open.pop();
+            self.mode = Mode::InTableBody;
+            self.process(token);
+        }
+    }
+
+    fn close_cell(&mut self) {
+        self.generate_implied_end_tags(None);
+        self.pop_until(&["td", "th"]);
+        self.clear_formatting_to_marker();
+        self.mode = Mode::InRow;
+    }
+
+    fn in_cell(&mut self, token: Token) {
+        match &token {
+            Token::EndTag { name, .. } if matches!(name.as_str(), "td" | "th") => {
+                if self.in_scope(&[name], Scope::Table) {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(&[name]);
+                    self.clear_formatting_to_marker();
+                    self.mode = Mode::InRow;
+                }
+            }
+            Token::StartTag { name, .. }
+                if matches!(
+                    name.as_str(),
+                    "caption"
+                        | "col"
+                        | "colgroup"
+                        | "tbody"
+                        | "td"
+                        | "tfoot"
+                        | "th"
+                        | "thead"
+                        | "tr"
+                ) =>
+            {
+                if self.in_scope(&["td", "th"], Scope::Table) {
+                    self.close_cell();
+                    self.process(token);
+                }
+            }
+            Token::EndTag { name, .. }
+                if matches!(
+                    name.as_str(),
+                    "body" | "caption" | "col" | "colgroup" | "html"
+                ) => {}
+            Token::EndTag { name, .. }
+                if matches!(name.as_str(), "table" | "tbody" | "tfoot" | "thead" | "tr") =>
+            {
+                if self.in_scope(&[name], Scope::Table) {
+                    self.close_cell();
+                    self.process(token);
+                }
+            }
+            _ => self.in_body(token),
+        }
+    }
+
+    fn close_select(&mut self) {
+        self.pop_until(&["select"]);
+        self.reset_insertion_mode();
+    }
+
+    fn in_select(&mut self, token: Token) {
+        let table_tags = [
+            "caption", "table", "tbody", "tfoot", "thead", "tr", "td", "th",
+        ];
+        match token {
+            Token::Text { text, .. } => self.insert_text(&text),
+            Token::Comment(comment) => self.insert_comment(comment, None),
+            Token::Doctype(_) => {}
+            Token::StartTag { ref name, .. } => match name.as_str() {
+                "html" => self.in_body(token),
+                "option" => {
+                    if self.is(self.current(), &["option"]) {
+                        self.open.pop();
+                    }
+                    self.insert_element(token, false);
+                }
+                "optgroup" | "hr" => {
+                    let hr = name == "hr";
+                    if self.is(self.current(), &["option"]) {
+                        self.open.pop();
+                    }
+                    if self.is(self.current(), &["optgroup"]) {
+                        self.open.pop();
+                    }
+                    self.insert_element(token, false);
+                    if hr {
+                        self.open.pop();
+                    }
+                }
+                "select" if self.in_scope(&["select"], Scope::Select) => self.close_select(),
+                "input" | "keygen" | "textarea" if self.in_scope(&["select"], Scope::Select) => {
+                    self.close_select();
+                    self.process(token);
+                }
+                "script" | "template" => self.in_head(token),
+                name if self.mode == Mode::InSelectInTable && table_tags.contains(&name) => {
+                    self.close_select();
+                    self.process(token);
+                }
+                _ => {}
+            },
+            Token::EndTag { ref name, .. } => match name.as_str() {
+                "optgroup" => {
+                    let len = self.open.len();
+                    if self.is(self.current(), &["option"])
+                        && len > 1
+                        && self.is(self.open[len - 2], &["optgroup"])
+                    {
+                        self.open.pop();
+                    }
+                    if self.is(self.current(), &["optgroup"]) {
+                        self.open.pop();
+                    }
+                }
+                "option" if self.is(self.current(), &["option"]) => {
+                    self.open.pop();
+                }
+                "select" if self.in_scope(&["select"], Scope::Select) => self.close_select(),
+                "template" => self.in_head(token),
+                name if self.mode == Mode::InSelectInTable
+                    && table_tags.contains(&name)
+                    && self.in_scope(&[name], Scope::Table) =>
+                {
+                    self.close_select();
+                    self.process(token);
+                }
+                _ => {}
+            },
+        }
+    }
+
+    fn after_body(&mut self, token: Token) {
+        match token {
+            Token::Text { ref text, offset } => {
+                let (whitespace, rest) = split_whitespace(text);
+                self.in_body(text_token(whitespace, offset));
+                if !rest.is_empty() {
+                    self.mode = Mode::InBody;
+                    self.process(text_token(rest, offset));
+                }
+            }
+            Token::Comment(comment) => self.insert_comment(comment, Some(self.open[0])),
+            Token::Doctype(_) => {}
+            Token::StartTag { ref name, .. } if name == "html" => self.in_body(token),
+            Token::EndTag { ref name, .. } if name == "html" => self.mode = Mode::AfterAfterBody,
+            token => {
+                self.mode = Mode::InBody;
+                self.process(token);
+            }
+        }
+    }
+
+    fn after_after_body(&mut self, token: Token) {
+        match token {
+            Token::Text { ref text, offset } => {
+                let (whitespace, rest) = split_whitespace(text);
+                self.in_body(text_token(whitespace, offset));
+                if !rest.is_empty() {
+                    self.mode = Mode::InBody;
+                    self.process(text_token(rest, offset));
+                }
+            }
+            Token::Comment(comment) => self.insert_comment(comment, Some(DOCUMENT)),
+            Token::Doctype(_) => {}
+            Token::StartTag { ref name, .. } if name == "html" => self.in_body(token),
+            token => {
+                self.mode = Mode::InBody;
+                self.process(token);
+            }
+        }
+    }
+
+    /// Handles the end of the input, which still creates the `html`,
+    /// `head` and `body` elements if they are missing.
+    fn finish(&mut self) {
+        loop {
+            match self.mode {
+                Mode::Initial => {
+                    self.quirks = true;
+                    self.mode = Mode::BeforeHtml;
+                }
+                Mode::BeforeHtml => {
+                    let id = self.insert_element_into(start_tag("html"), DOCUMENT);
+                    self.open.push(id);
+                    self.mode = Mode::BeforeHead;
+                }
+                Mode::BeforeHead => {
+                    self.head = Some(self.insert_element(start_tag("head"), false));
+                    self.mode = Mode::InHead;
+                }
+                Mode::InHead => {
+                    self.open.pop();
+                    self.mode = Mode::AfterHead;
+                }
+                Mode::AfterHead => {
+                    self.insert_element(start_tag("body"), false);
+                    self.mode = Mode::InBody;
+                }
+                Mode::Text => {
+                    self.open.pop();
+                    self.mode = self.original_mode;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    // Results
+
+    fn to_node(&self, id: usize) -> Node {
+        match &self.nodes[id].data {
+            Data::Element(tag) => {
+                let mut tag = tag.clone();
+                tag.children = self.nodes[id]
+                    .children
+                    .iter()
+                    .map(|&child| self.to_node(child))
+                    .collect();
+                let namespace = self.nodes[id].namespace;
+                keep_raw_text(&mut tag, namespace);
+                adjust_foreign_names(&mut tag, namespace);
+                Node::Element(tag)
+            }
+            Data::Text(text) => Node::Text(text.clone()),
+            Data::Comment(comment) => Node::Comment(comment.clone()),
+            Data::Document => unreachable!("the document is never a child"),
+        }
+    }
+
+    fn children(&self, id: usize) -> Vec<Node> {
+        self.nodes[id]
+            .children
+            .iter()
+            .map(|&child| self.to_node(child))
+            .collect()
+    }
+}
+
+/// Normalizes newlines, like browsers do before tokenizing.
+fn preprocess(input: &str) -> String {
+    input.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Parses a whole document, returning the name of the doctype, if any,
+/// and the nodes at the top level of the document.
+pub(crate) fn parse_document(input: &str) -> (Option<String>, Vec<Node>) {
+    let input = preprocess(input);
+    let mut builder = TreeBuilder::new(&input);
+    builder.run();
+    builder.finish();
+    (builder.doctype.take(), builder.children(DOCUMENT))
+}
+
+/// Parses a fragment as if it were the content of the given element.
+pub(crate) fn parse_fragment(input: &str, context: &str) -> Vec<Node> {
+    let input = preprocess(input);
+    let mut builder = TreeBuilder::new(&input);
+    let html = builder.insert_element_into(start_tag("html"), DOCUMENT);
+    builder.open.push(html);
+    builder.context = Some(context);
+    match context {
+        "title" | "textarea" | "style" | "xmp" | "iframe" | "noembed" | "noframes" | "script"
+        | "noscript" => builder.tokenizer.set_raw_text(context),
+        "plaintext" => builder.tokenizer.set_plaintext(),
+        _ => {}
+    }
+    builder.reset_insertion_mode();
+    builder.run();
+    builder.children(html)
+}
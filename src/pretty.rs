@@ -0,0 +1,232 @@
+use crate::{escape::escape_text, html::HtmlTag, node::Node, tags::TagType};
+
+/// The indentation used for each level of nesting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    Spaces(usize),
+    Tabs,
+}
+
+/// Options for `HtmlTag::to_pretty_html`.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::HtmlTag;
+/// use html_tag::pretty::{Indent, PrettyOptions};
+///
+/// let options = PrettyOptions::new().with_indent(Indent::Tabs);
+/// let div = HtmlTag::new("div").with_child(HtmlTag::new("p").with_body("Hello World"));
+///
+/// assert_eq!(div.to_pretty_html(&options), "<div>\n\t<p>Hello World</p>\n</div>");
+/// ```
+///
+/// The defaults are two spaces of indentation and a maximum line
+/// width of 80 characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyOptions {
+    pub indent: Indent,
+    pub max_line_width: usize,
+    pub xhtml: bool,
+}
+
+impl PrettyOptions {
+    /// Creates the default `PrettyOptions`.
+    pub fn new() -> PrettyOptions {
+        PrettyOptions {
+            indent: Indent::Spaces(2),
+            max_line_width: 80,
+            xhtml: false,
+        }
+    }
+
+    /// Chaining method for setting the indentation
+    pub fn with_indent(mut self, indent: Indent) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Chaining method for setting the maximum line width
+    ///
+    /// Lines are only ever broken at whitespace that is already
+    /// present in text, so a line can still end up longer.
+    pub fn with_max_line_width(mut self, max_line_width: usize) -> Self {
+        self.max_line_width = max_line_width;
+        self
+    }
+
+    /// Chaining method for self closing void elements, like `to_xhtml`
+    pub fn with_xhtml(mut self, xhtml: bool) -> Self {
+        self.xhtml = xhtml;
+        self
+    }
+
+    fn indentation(&self, depth: usize) -> String {
+        match self.indent {
+            Indent::Spaces(width) => " ".repeat(width * depth),
+            Indent::Tabs => "\t".repeat(depth),
+        }
+    }
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        PrettyOptions::new()
+    }
+}
+
+/// Elements whose content must be kept exactly as it is, since
+/// whitespace inside them is significant.
+fn is_whitespace_sensitive(tag_type: &TagType) -> bool {
+    matches!(tag_type, TagType::Pre | TagType::Textarea) || tag_type.is_raw_text()
+}
+
+/// Returns whether the children of an element are laid out on lines
+/// of their own.
+///
+/// Custom elements count as phrasing content, but when they wrap
+/// blocks they are laid out as blocks themselves, like a `div`.
+fn is_block(tag: &HtmlTag) -> bool {
+    match &tag.tag_type {
+        TagType::Custom(_) => tag.children.iter().any(|child| !is_inline(child, false)),
+        tag_type => !tag_type.is_phrasing(),
+    }
+}
+
+/// Returns whether a node is kept on the line of the text around it.
+///
+/// Custom elements are only put on a line of their own when their
+/// siblings already are, so the whitespace inside text is unchanged.
+fn is_inline(node: &Node, blocks: bool) -> bool {
+    match node {
+        Node::Element(tag) => {
+            tag.pre_content.is_none()
+                && !is_block(tag)
+                && !(blocks && matches!(tag.tag_type, TagType::Custom(_)))
+        }
+        _ => true,
+    }
+}
+
+fn width(line: &str) -> usize {
+    line.chars().count()
+}
+
+/// Splits a run of inline nodes into words, at the whitespace inside
+/// text nodes. Inline elements are never split, and are glued to the
+/// text around them when there is no whitespace in between.
+fn inline_words(nodes: &[&Node], xhtml: bool) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut space = true;
+    for node in nodes {
+        let pieces = match node {
+            Node::Text(text) => escape_text(text)
+                .split(|c: char| c.is_ascii_whitespace())
+                .map(|s| s.to_string())
+                .collect(),
+            _ => vec![node.render(xhtml)],
+        };
+        for (i, piece) in pieces.into_iter().enumerate() {
+            if i > 0 {
+                space = true;
+            }
+            if piece.is_empty() {
+                continue;
+            }
+            match words.last_mut() {
+                Some(last) if !space => last.push_str(&piece),
+                _ => words.push(piece),
+            }
+            space = false;
+        }
+    }
+    words
+}
+
+fn fill_lines(words: Vec<String>, indent: &str, options: &PrettyOptions, lines: &mut Vec<String>) {
+    let mut line = String::new();
+    for word in words {
+        if !line.is_empty()
+            && width(indent) + width(&line) + 1 + width(&word) > options.max_line_width
+        {
+            lines.push(format!("{}{}", indent, line));
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(format!("{}{}", indent, line));
+    }
+}
+
+pub(crate) fn format_tag(
+    tag: &HtmlTag,
+    options: &PrettyOptions,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    let indent = options.indentation(depth);
+
+    if let Some(pre_content) = &tag.pre_content {
+        for line in pre_content.lines().filter(|l| !l.trim().is_empty()) {
+            lines.push(format!("{}{}", indent, line));
+        }
+    }
+
+    if tag.tag_type.is_void() || !is_block(tag) || is_whitespace_sensitive(&tag.tag_type) {
+        lines.push(format!("{}{}", indent, tag.render_element(options.xhtml)));
+        return;
+    }
+
+    let mut opening_tag = String::new();
+    tag.write_opening_tag(&mut opening_tag)
+        .expect("writing to a String never fails");
+    opening_tag.push('>');
+    let closing_tag = format!("</{}>", tag.tag_type.as_str());
+
+    let blocks = tag.children.iter().any(|child| !is_inline(child, false));
+    if !blocks {
+        let children = tag.children.iter().collect::<Vec<_>>();
+        let line = format!(
+            "{}{}{}{}",
+            indent,
+            opening_tag,
+            inline_words(&children, options.xhtml).join(" "),
+            closing_tag
+        );
+        if width(&line) <= options.max_line_width {
+            lines.push(line);
+            return;
+        }
+    }
+
+    lines.push(format!("{}{}", indent, opening_tag));
+    let child_indent = options.indentation(depth + 1);
+    let mut run = Vec::new();
+    for child in &tag.children {
+        if is_inline(child, blocks) {
+            run.push(child);
+            continue;
+        }
+        fill_lines(
+            inline_words(&run, options.xhtml),
+            &child_indent,
+            options,
+            lines,
+        );
+        run.clear();
+        if let Node::Element(child) = child {
+            format_tag(child, options, depth + 1, lines);
+        }
+    }
+    fill_lines(
+        inline_words(&run, options.xhtml),
+        &child_indent,
+        options,
+        lines,
+    );
+    lines.push(format!("{}{}", indent, closing_tag));
+}
@@ -1,32 +1,904 @@
 use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::ops::Index;
 
-pub type StyleSheet = BTreeMap<String, BTreeMap<String, String>>;
-pub type Class = BTreeMap<String, String>;
+use crate::css::{self, Block, CssParseError, Declaration, Rule};
+use crate::html::HtmlTag;
+
+mod cascade;
+mod property;
+mod selector;
+mod values;
+
+pub use cascade::{ComputedStyle, ComputedStyles};
+pub use property::{Property, ValidationError, ValidationErrorKind};
+pub use selector::{
+    AttributeOperator, AttributeSelector, Combinator, Compound, PseudoClass, Selector,
+    SimpleSelector, Specificity,
+};
+pub use values::{Calc, Color, Length, LengthUnit, Percent, ValueError};
+
+/// An ordered map of CSS declarations, like the inline styles of an
+/// `HtmlTag`.
+///
+/// Declarations are kept in the order they were inserted, and inserting
+/// a property that is already present moves it to the end, so that a
+/// shorthand and its longhands are rendered in the order they were set.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::Class;
+///
+/// let mut class = Class::new();
+/// class.insert("background-color", "blue");
+/// class.insert("background", "none");
+///
+/// assert_eq!(class["background"], "none");
+/// assert_eq!(
+///     class.iter().collect::<Vec<_>>(),
+///     [("background-color", "blue"), ("background", "none")]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Class {
+    declarations: Vec<(String, String)>,
+}
+
+impl Class {
+    /// Creates a new, empty `Class`.
+    pub fn new() -> Class {
+        Class {
+            declarations: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the given property.
+    pub fn get(&self, property: &str) -> Option<&String> {
+        self.declarations
+            .iter()
+            .find(|(p, _)| p == property)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns whether the given property is present.
+    pub fn contains_key(&self, property: &str) -> bool {
+        self.get(property).is_some()
+    }
+
+    /// Sets the given property, moving it to the end if it was already
+    /// present.
+    ///
+    /// Returns the previous value, if the property was present.
+    pub fn insert(
+        &mut self,
+        property: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        let property = property.into();
+        let previous = self.remove(&property);
+        self.declarations.push((property, value.into()));
+        previous
+    }
+
+    /// Removes the given property, returning its value if it was present.
+    pub fn remove(&mut self, property: &str) -> Option<String> {
+        let i = self.declarations.iter().position(|(p, _)| p == property)?;
+        Some(self.declarations.remove(i).1)
+    }
+
+    /// Removes all the declarations.
+    pub fn clear(&mut self) {
+        self.declarations.clear();
+    }
+
+    /// Returns the number of declarations.
+    pub fn len(&self) -> usize {
+        self.declarations.len()
+    }
+
+    /// Returns whether there are no declarations.
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+
+    /// Iterates over the declarations in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.declarations
+            .iter()
+            .map(|(p, v)| (p.as_str(), v.as_str()))
+    }
+}
+
+impl Index<&str> for Class {
+    type Output = String;
+
+    /// Returns the value of the given property.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property is not present.
+    fn index(&self, property: &str) -> &String {
+        self.get(property)
+            .unwrap_or_else(|| panic!("no property `{}`", property))
+    }
+}
+
+impl<'a> IntoIterator for &'a Class {
+    type Item = (&'a str, &'a str);
+    type IntoIter = std::iter::Map<
+        std::slice::Iter<'a, (String, String)>,
+        fn(&'a (String, String)) -> (&'a str, &'a str),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.declarations
+            .iter()
+            .map(|(p, v)| (p.as_str(), v.as_str()))
+    }
+}
+
+impl IntoIterator for Class {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.declarations.into_iter()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Class {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Class {
+        let mut class = Class::new();
+        class.extend(iter);
+        class
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for Class {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (property, value) in iter {
+            self.insert(property, value);
+        }
+    }
+}
+
+/// A stylesheet, keeping its rules and their declarations in the order
+/// they were added, so that the cascade is preserved when rendered.
+///
+/// Rules are built through the `Style` trait, where adding to a selector
+/// that already has a rule adds to that rule.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::{Style, StyleSheet};
+///
+/// let mut sheet = StyleSheet::new();
+/// sheet.add_style(".btn", "background-color", "blue");
+/// sheet.add_style(".btn", "background", "none");
+/// sheet.add_style(".btn:hover", "color", "red");
+/// sheet.add_style(".btn", "color", "white");
+///
+/// assert_eq!(
+///     sheet.get_style_sheet(),
+///     ".btn {\n    background-color: blue;\n    background: none;\n    color: white;\n}\n\
+///      .btn:hover {\n    color: red;\n}\n"
+/// );
+/// assert_eq!(sheet[".btn"]["background"], "none");
+/// ```
+///
+/// Rules can also be nested in at-rules, like `@media` or `@supports`.
+///
+/// ```
+/// use html_tag::{Style, StyleSheet};
+///
+/// let mut sheet = StyleSheet::new();
+/// sheet.add_style(".nav", "display", "flex");
+/// sheet.media("(max-width: 600px)", |s| {
+///     s.add_style(".nav", "display", "none");
+///     s.supports("(display: grid)", |s| s.add_style(".menu", "display", "grid"));
+/// });
+///
+/// assert_eq!(
+///     sheet.get_style_sheet(),
+///     ".nav {\n    display: flex;\n}\n\
+///      @media (max-width: 600px) {\n    .nav {\n        display: none;\n    }\n\
+///      \x20   @supports (display: grid) {\n        .menu {\n            display: grid;\n        }\n    }\n}\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StyleSheet {
+    items: Vec<StyleItem>,
+}
+
+/// An entry of a `StyleSheet`, either a rule, an at-rule with nested
+/// rules, or a statement at-rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleItem {
+    Rule(StyleRule),
+    AtRule(AtRule),
+    /// An at-rule without a block, like `@import url(a.css)`, as it
+    /// was written but without the `;`.
+    Statement(String),
+}
+
+/// An at-rule with nested rules, like `@media (max-width: 600px) { ... }`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AtRule {
+    /// The name, without the `@`, like `media`.
+    pub name: String,
+    /// Everything between the name and the block, like a media query.
+    pub prelude: String,
+    pub rules: StyleSheet,
+}
+
+impl AtRule {
+    /// Returns the at-rule without its block, like `@media print`.
+    pub fn header(&self) -> String {
+        if self.prelude.is_empty() {
+            format!("@{}", self.name)
+        } else {
+            format!("@{} {}", self.name, self.prelude)
+        }
+    }
+}
+
+impl StyleSheet {
+    /// Returns the first rule for the given selector.
+    pub fn get(&self, selector: &str) -> Option<&StyleRule> {
+        self.iter().find(|rule| rule.selector == selector)
+    }
+
+    /// Returns the rule for the given selector, adding an empty one
+    /// at the end if there is none yet.
+    pub fn rule_mut(&mut self, selector: &str) -> &mut StyleRule {
+        let index = match self
+            .items
+            .iter()
+            .position(|item| matches!(item, StyleItem::Rule(rule) if rule.selector == selector))
+        {
+            Some(index) => index,
+            None => {
+                self.items.push(StyleItem::Rule(StyleRule::new(selector)));
+                self.items.len() - 1
+            }
+        };
+        match &mut self.items[index] {
+            StyleItem::Rule(rule) => rule,
+            StyleItem::AtRule(_) | StyleItem::Statement(_) => unreachable!(),
+        }
+    }
+
+    /// Returns whether there is a rule for the given selector.
+    pub fn contains(&self, selector: &str) -> bool {
+        self.get(selector).is_some()
+    }
+
+    /// Removes the rule for the given selector.
+    ///
+    /// Returns the removed rule, if there was one.
+    pub fn remove(&mut self, selector: &str) -> Option<StyleRule> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| matches!(item, StyleItem::Rule(rule) if rule.selector == selector))?;
+        match self.items.remove(index) {
+            StyleItem::Rule(rule) => Some(rule),
+            StyleItem::AtRule(_) | StyleItem::Statement(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the at-rule with the given name and prelude.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{Style, StyleSheet};
+    ///
+    /// let sheet = StyleSheet::parse("@media print { a { color: black } }").unwrap();
+    ///
+    /// assert_eq!(sheet.get_at_rule("media", "print").unwrap().rules["a"]["color"], "black");
+    /// ```
+    pub fn get_at_rule(&self, name: &str, prelude: &str) -> Option<&AtRule> {
+        self.at_rules()
+            .find(|at_rule| at_rule.name == name && at_rule.prelude == prelude)
+    }
+
+    /// Returns the at-rule with the given name and prelude, adding an
+    /// empty one at the end if there is none yet.
+    pub fn at_rule_mut(&mut self, name: &str, prelude: &str) -> &mut AtRule {
+        let (name, prelude) = (name.trim_start_matches('@'), prelude.trim());
+        let found = self.items.iter().position(|item| match item {
+            StyleItem::AtRule(at_rule) => at_rule.name == name && at_rule.prelude == prelude,
+            StyleItem::Rule(_) | StyleItem::Statement(_) => false,
+        });
+        let index = match found {
+            Some(index) => index,
+            None => {
+                self.items.push(StyleItem::AtRule(AtRule {
+                    name: name.to_string(),
+                    prelude: prelude.to_string(),
+                    rules: StyleSheet::default(),
+                }));
+                self.items.len() - 1
+            }
+        };
+        match &mut self.items[index] {
+            StyleItem::AtRule(at_rule) => at_rule,
+            StyleItem::Rule(_) | StyleItem::Statement(_) => unreachable!(),
+        }
+    }
+
+    /// Adds rules nested in an at-rule, like `@media print`, which are
+    /// built by the given function.
+    ///
+    /// Using the same at-rule again adds to the rules it already has.
+    pub fn add_at_rule<F>(&mut self, name: &str, prelude: &str, build: F) -> &mut Self
+    where
+        F: FnOnce(&mut StyleSheet),
+    {
+        build(&mut self.at_rule_mut(name, prelude).rules);
+        self
+    }
+
+    /// Adds rules that only apply to the given media query.
+    pub fn media<F: FnOnce(&mut StyleSheet)>(&mut self, query: &str, build: F) -> &mut Self {
+        self.add_at_rule("media", query, build)
+    }
+
+    /// Adds rules that only apply when the given feature query holds.
+    pub fn supports<F: FnOnce(&mut StyleSheet)>(&mut self, condition: &str, build: F) -> &mut Self {
+        self.add_at_rule("supports", condition, build)
+    }
+
+    /// Adds rules that only apply to the given container query.
+    pub fn container<F: FnOnce(&mut StyleSheet)>(&mut self, query: &str, build: F) -> &mut Self {
+        self.add_at_rule("container", query, build)
+    }
+
+    /// Adds rules to the given cascade layer, or to an anonymous one
+    /// if the name is empty.
+    pub fn layer<F: FnOnce(&mut StyleSheet)>(&mut self, name: &str, build: F) -> &mut Self {
+        self.add_at_rule("layer", name, build)
+    }
+
+    /// Adds a `@keyframes` animation with the given name, whose
+    /// keyframes, like `from`, `50%` or `to`, are built by the given
+    /// function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{Style, StyleSheet};
+    ///
+    /// let mut sheet = StyleSheet::new();
+    /// sheet.keyframes("spin", |k| {
+    ///     k.add_style("from", "transform", "rotate(0deg)");
+    ///     k.add_style("to", "transform", "rotate(360deg)");
+    /// });
+    /// sheet.add_style(".loader", "animation", "spin 1s linear infinite");
+    ///
+    /// assert!(sheet.get_style_sheet().starts_with("@keyframes spin {\n    from {\n"));
+    /// ```
+    pub fn keyframes<F: FnOnce(&mut StyleSheet)>(&mut self, name: &str, build: F) -> &mut Self {
+        self.add_at_rule("keyframes", name, build)
+    }
+
+    /// Adds a `@font-face` rule for the given family and sources,
+    /// returning it so that descriptors like `font-weight` can be set.
+    ///
+    /// The family is quoted, while the sources are kept as they are.
+    /// Unlike other rules, every call adds a new `@font-face`, as a
+    /// family usually has one for each weight and style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{Style, StyleSheet};
+    ///
+    /// let mut sheet = StyleSheet::new();
+    /// sheet.font_face("Open Sans", "url(\"/open-sans.woff2\") format(\"woff2\")");
+    /// sheet
+    ///     .font_face("Open Sans", "url(\"/open-sans-bold.woff2\") format(\"woff2\")")
+    ///     .set("font-weight", "700");
+    ///
+    /// assert_eq!(sheet.len(), 2);
+    /// assert_eq!(sheet["@font-face"]["font-family"], "\"Open Sans\"");
+    /// ```
+    pub fn font_face(&mut self, family: &str, src: &str) -> &mut StyleRule {
+        let mut rule = StyleRule::new("@font-face");
+        rule.set("font-family", quote(family));
+        rule.set("src", src);
+        self.push_rule(rule)
+    }
+
+    /// Sets a custom property on the rule for the given selector,
+    /// usually `:root`, adding the leading `--` to the name if it
+    /// is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{Style, StyleSheet};
+    ///
+    /// let mut sheet = StyleSheet::new();
+    /// sheet
+    ///     .add_custom_property(":root", "brand", "#f00")
+    ///     .add_custom_property(".dark", "--brand", "#900");
+    /// sheet.add_style(".btn", "color", "var(--brand)");
+    ///
+    /// assert_eq!(sheet[":root"]["--brand"], "#f00");
+    /// assert_eq!(sheet[".dark"].get_custom_property("brand"), Some("#900"));
+    /// ```
+    pub fn add_custom_property(
+        &mut self,
+        selector: &str,
+        name: &str,
+        value: impl Display,
+    ) -> &mut Self {
+        self.rule_mut(selector).set_custom_property(name, value);
+        self
+    }
+
+    /// Adds a rule at the end, even if there is one for its selector.
+    fn push_rule(&mut self, rule: StyleRule) -> &mut StyleRule {
+        self.items.push(StyleItem::Rule(rule));
+        match self.items.last_mut() {
+            Some(StyleItem::Rule(rule)) => rule,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the number of entries, rules and at-rules.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterates over the rules at the top level in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &StyleRule> {
+        self.items.iter().filter_map(|item| match item {
+            StyleItem::Rule(rule) => Some(rule),
+            StyleItem::AtRule(_) | StyleItem::Statement(_) => None,
+        })
+    }
+
+    /// Iterates over the at-rules at the top level in insertion order.
+    pub fn at_rules(&self) -> impl Iterator<Item = &AtRule> {
+        self.items.iter().filter_map(|item| match item {
+            StyleItem::AtRule(at_rule) => Some(at_rule),
+            StyleItem::Rule(_) | StyleItem::Statement(_) => None,
+        })
+    }
+
+    /// Iterates over all the entries in insertion order.
+    pub fn items(&self) -> impl Iterator<Item = &StyleItem> {
+        self.items.iter()
+    }
+
+    /// Checks every declaration for a property that is not known, see
+    /// `Property::is_known`, or a keyword that the property does not
+    /// accept, see `Property::keywords`.
+    ///
+    /// Only the declarations of rules are checked, including the ones in
+    /// `@media` and `@keyframes`, but not the descriptors of at-rules
+    /// like `@font-face`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{Style, StyleSheet};
+    ///
+    /// let sheet = StyleSheet::parse(
+    ///     ".card { colour: red; display: flexx; -webkit-line-clamp: 2 }
+    ///      @media print { .card { display: none !important; position: var(--pos) } }",
+    /// )
+    /// .unwrap();
+    ///
+    /// let errors = sheet.validate().unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].to_string(), "unknown property `colour` in `.card`");
+    /// assert_eq!(errors[1].to_string(), "invalid keyword `flexx` for `display` in `.card`");
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        self.validate_into(&mut Vec::new(), &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Applies the stylesheet to a tree of `HtmlTag`s, returning the
+    /// declarations that apply to each element.
+    ///
+    /// Like in browsers, an `!important` declaration wins over a normal
+    /// one, then inline styles win over the stylesheet, then the most
+    /// specific selector wins, and then the rule that comes last.
+    ///
+    /// These are the cascaded values only, nothing is inherited from the
+    /// parent. Rules inside at-rules, like `@media`, are skipped, since
+    /// they depend on where the document is shown, as are states like
+    /// `:hover` and rules whose selector can not be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{HtmlTag, Style, StyleSheet};
+    ///
+    /// let sheet = StyleSheet::parse(
+    ///     "p { color: black; margin: 0 }
+    ///      .note p:first-child { color: gray }
+    ///      p { color: blue; font-size: 14px !important }",
+    /// )
+    /// .unwrap();
+    ///
+    /// let div = HtmlTag::new("div")
+    ///     .with_class("note")
+    ///     .with_child(HtmlTag::new("p").with_style("font-size", "20px"))
+    ///     .with_child(HtmlTag::new("p").with_style("margin", "4px"));
+    ///
+    /// let styles = sheet.compute_styles(&div);
+    /// let first = styles.get_path(&[0]).unwrap();
+    /// let second = styles.get_path(&[1]).unwrap();
+    ///
+    /// assert_eq!(first["color"], "gray");
+    /// assert_eq!(first["font-size"], "14px");
+    /// assert_eq!(second["color"], "blue");
+    /// assert_eq!(second["margin"], "4px");
+    /// assert!(styles.get(&div).unwrap().is_empty());
+    /// ```
+    pub fn compute_styles<'a>(&self, root: &'a HtmlTag) -> ComputedStyles<'a> {
+        cascade::compute_styles(self, root)
+    }
+
+    fn validate_into(&self, at_rules: &mut Vec<String>, errors: &mut Vec<ValidationError>) {
+        for item in &self.items {
+            match item {
+                StyleItem::Rule(rule) if rule.selector.starts_with('@') => {}
+                StyleItem::Statement(_) => {}
+                StyleItem::Rule(rule) => {
+                    for (property, value) in rule.iter() {
+                        if let Some(kind) = property::check_declaration(property, value) {
+                            errors.push(ValidationError {
+                                kind,
+                                selector: rule.selector.clone(),
+                                at_rules: at_rules.clone(),
+                            });
+                        }
+                    }
+                }
+                StyleItem::AtRule(at_rule) => {
+                    at_rules.push(at_rule.header());
+                    at_rule.rules.validate_into(at_rules, errors);
+                    at_rules.pop();
+                }
+            }
+        }
+    }
+
+    fn write_style_sheet(&self, styles: &mut String, depth: usize) {
+        let indent = "    ".repeat(depth);
+        for item in &self.items {
+            match item {
+                StyleItem::Rule(rule) => {
+                    styles.push_str(&format!("{}{} {{\n", indent, rule.selector));
+                    for (property, value) in rule.iter() {
+                        styles.push_str(&format!("{}    {}: {};\n", indent, property, value));
+                    }
+                }
+                StyleItem::AtRule(at_rule) => {
+                    styles.push_str(&format!("{}{} {{\n", indent, at_rule.header()));
+                    at_rule.rules.write_style_sheet(styles, depth + 1);
+                }
+                StyleItem::Statement(statement) => {
+                    styles.push_str(&format!("{}{};\n", indent, statement));
+                    continue;
+                }
+            }
+            styles.push_str(&format!("{}}}\n", indent));
+        }
+    }
+
+    /// Parses a stylesheet, like the content of a `.css` file.
+    ///
+    /// Comments are skipped, selector lists are split into a rule for
+    /// each selector, and an `!important` stays part of the value, where
+    /// it also wins over later values for the same property in the rule.
+    ///
+    /// Unlike `add_style`, a selector that appears again gets a rule of
+    /// its own, so that the rules stay in source order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::{Style, StyleSheet};
+    ///
+    /// let sheet = StyleSheet::parse(
+    ///     "/* base */ h1, .title { color: red !important; font-family: \"Times New Roman\" }
+    ///      .title { color: blue; margin: 0 auto; }",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(sheet[".title"]["color"], "red !important");
+    /// assert_eq!(sheet["h1"]["font-family"], "\"Times New Roman\"");
+    ///
+    /// let titles: Vec<_> = sheet.iter().filter(|rule| rule.selector == ".title").collect();
+    /// assert_eq!(titles.len(), 2);
+    /// assert_eq!(titles[1]["margin"], "0 auto");
+    /// ```
+    ///
+    /// At-rules with nested rules, like `@media` or `@keyframes`, become
+    /// an `AtRule`, while every at-rule with declarations, like
+    /// `@font-face` or `@page`, becomes a rule with its name and prelude
+    /// as the selector. Statements, like `@import` or `@namespace`, are
+    /// kept in place as a `StyleItem::Statement`, except for `@charset`,
+    /// which is skipped. Malformed CSS is an error.
+    ///
+    /// ```
+    /// use html_tag::{Style, StyleSheet};
+    ///
+    /// let sheet = StyleSheet::parse("@import url(\"base.css\");\na { color: red }").unwrap();
+    /// assert_eq!(
+    ///     sheet.get_style_sheet(),
+    ///     "@import url(\"base.css\");\na {\n    color: red;\n}\n"
+    /// );
+    ///
+    /// let error = StyleSheet::parse("a {\n  color red;\n}").unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "invalid declaration `color red` at line 2, column 3");
+    /// ```
+    pub fn parse(css: &str) -> Result<StyleSheet, CssParseError> {
+        let mut sheet = StyleSheet::new();
+        sheet.add_rules(css::parse_rules(css)?);
+        Ok(sheet)
+    }
+
+    fn add_rules(&mut self, rules: Vec<Rule>) {
+        for rule in rules {
+            match rule {
+                Rule::Style {
+                    selectors,
+                    declarations,
+                } => {
+                    // Every occurrence keeps its own rule, so the source
+                    // order, and with it the cascade, is preserved.
+                    for selector in selectors {
+                        self.push_rule(StyleRule::new(&selector))
+                            .add_declarations(&declarations);
+                    }
+                }
+                Rule::At {
+                    name,
+                    prelude,
+                    block: Some(Block::Declarations(declarations)),
+                    ..
+                } => {
+                    // Like `@font-face`, these do not override each other.
+                    let selector = format!("@{} {}", name, prelude);
+                    self.push_rule(StyleRule::new(selector.trim_end()))
+                        .add_declarations(&declarations);
+                }
+                Rule::At {
+                    name,
+                    prelude,
+                    block: Some(Block::Rules(rules)),
+                    ..
+                } => {
+                    let mut at_rule = AtRule {
+                        name,
+                        prelude,
+                        rules: StyleSheet::default(),
+                    };
+                    at_rule.rules.add_rules(rules);
+                    self.items.push(StyleItem::AtRule(at_rule));
+                }
+                // The encoding of a string that is already decoded.
+                Rule::At { name, .. } if name == "charset" => {}
+                Rule::At {
+                    name,
+                    prelude,
+                    block: None,
+                } => {
+                    let statement = format!("@{} {}", name, prelude);
+                    self.items
+                        .push(StyleItem::Statement(statement.trim_end().to_string()));
+                }
+            }
+        }
+    }
+}
+
+impl Index<&str> for StyleSheet {
+    type Output = StyleRule;
+
+    /// Returns the first rule for the given selector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no rule for the selector.
+    fn index(&self, selector: &str) -> &StyleRule {
+        self.get(selector)
+            .unwrap_or_else(|| panic!("no rule for selector `{}`", selector))
+    }
+}
+
+/// A rule of a `StyleSheet`, a selector with its declarations in the
+/// order they were added.
+///
+/// Setting a property that is already present moves it to the end, as
+/// the later of two declarations wins, like `background` over an earlier
+/// `background-color`.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::StyleRule;
+///
+/// let mut rule = StyleRule::new("a");
+/// rule.set("color", "red");
+/// rule.set("margin", "0 auto");
+/// rule.set("color", "blue");
+///
+/// assert_eq!(rule.get("color"), Some("blue"));
+/// assert_eq!(rule.iter().collect::<Vec<_>>(), [("margin", "0 auto"), ("color", "blue")]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StyleRule {
+    pub selector: String,
+    declarations: Vec<(String, String)>,
+}
+
+impl StyleRule {
+    /// Creates a new, empty rule for the given selector.
+    pub fn new(selector: &str) -> StyleRule {
+        StyleRule {
+            selector: selector.to_string(),
+            declarations: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the given property.
+    pub fn get(&self, property: &str) -> Option<&str> {
+        self.declarations
+            .iter()
+            .find(|(p, _)| p == property)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns whether the given property is present.
+    pub fn contains(&self, property: &str) -> bool {
+        self.get(property).is_some()
+    }
+
+    /// Sets the given property, moving it to the end if it was
+    /// already present.
+    pub fn set(&mut self, property: impl AsRef<str>, value: impl Display) {
+        let property = property.as_ref();
+        self.remove(property);
+        self.declarations
+            .push((property.to_string(), value.to_string()));
+    }
+
+    /// Removes the given property.
+    ///
+    /// Returns whether the property was present.
+    pub fn remove(&mut self, property: &str) -> bool {
+        let len = self.declarations.len();
+        self.declarations.retain(|(p, _)| p != property);
+        self.declarations.len() != len
+    }
+
+    /// Returns the number of declarations.
+    pub fn len(&self) -> usize {
+        self.declarations.len()
+    }
+
+    /// Returns whether there are no declarations.
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+
+    /// Iterates over the declarations in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.declarations
+            .iter()
+            .map(|(p, v)| (p.as_str(), v.as_str()))
+    }
+
+    /// Parses the selector of the rule, which can be a list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::styles::{Specificity, StyleRule};
+    ///
+    /// let rule = StyleRule::new("#nav a, a.active");
+    /// let specificities: Vec<_> = rule.selectors().unwrap().iter().map(|s| s.specificity()).collect();
+    ///
+    /// assert_eq!(specificities, [Specificity(1, 0, 1), Specificity(0, 1, 1)]);
+    /// ```
+    pub fn selectors(&self) -> Result<Vec<Selector>, CssParseError> {
+        Selector::parse_list(&self.selector)
+    }
+
+    /// Returns the declarations as a `Class`.
+    pub fn to_class(&self) -> Class {
+        self.declarations.iter().cloned().collect()
+    }
+
+    /// Sets a custom property, adding the leading `--` to the name if
+    /// it is missing.
+    pub fn set_custom_property(&mut self, name: &str, value: impl Display) {
+        self.set(custom_property_name(name), value);
+    }
+
+    /// Returns the value of a custom property, where the leading `--`
+    /// of the name is optional.
+    pub fn get_custom_property(&self, name: &str) -> Option<&str> {
+        self.get(&custom_property_name(name))
+    }
+
+    /// Adds parsed declarations, where an `!important` value is only
+    /// replaced by another `!important` value.
+    fn add_declarations(&mut self, declarations: &[Declaration]) {
+        for declaration in declarations {
+            let important = self
+                .get(&declaration.property)
+                .is_some_and(|value| value.ends_with("!important"));
+            if !important || declaration.important {
+                self.set(&declaration.property, declaration.full_value());
+            }
+        }
+    }
+}
+
+impl Index<&str> for StyleRule {
+    type Output = String;
+
+    /// Returns the value of the given property.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property is not present.
+    fn index(&self, property: &str) -> &String {
+        self.declarations
+            .iter()
+            .find(|(p, _)| p == property)
+            .map(|(_, v)| v)
+            .unwrap_or_else(|| panic!("no property `{}` in `{}`", property, self.selector))
+    }
+}
 
 pub trait Style {
     fn new() -> Self;
     fn get_style_sheet(&self) -> String;
     fn get_with_tag(&self) -> String;
-    fn add_style(&mut self, selector: &str, property: &str, value: &str);
-    fn add_class(&mut self, selector: &str, properties: BTreeMap<String, String>);
-    fn with_style(&mut self, selector: &str, property: &str, value: &str) -> Self;
-    fn with_class(&mut self, selector: &str, properties: BTreeMap<String, String>) -> Self;
+    fn add_style(&mut self, selector: impl Display, property: impl AsRef<str>, value: impl Display);
+    fn add_class(&mut self, selector: impl Display, properties: BTreeMap<String, String>);
+    fn with_style(
+        &mut self,
+        selector: impl Display,
+        property: impl AsRef<str>,
+        value: impl Display,
+    ) -> Self;
+    fn with_class(&mut self, selector: impl Display, properties: BTreeMap<String, String>) -> Self;
 }
 
 impl Style for StyleSheet {
     fn new() -> Self {
-        BTreeMap::new()
+        StyleSheet::default()
     }
 
     fn get_style_sheet(&self) -> String {
         let mut final_styles = String::new();
-        for (selector, properties) in self {
-            final_styles.push_str(&format!("{} {{\n", selector));
-            for (property, value) in properties {
-                final_styles.push_str(&format!("    {}: {};\n", property, value));
-            }
-            final_styles.push_str("}\n");
-        }
+        self.write_style_sheet(&mut final_styles, 0);
         final_styles
     }
 
@@ -37,42 +909,53 @@ impl Style for StyleSheet {
         final_styles
     }
 
-    fn add_style(&mut self, selector: &str, property: &str, value: &str) {
-        if self.contains_key(selector) {
-            self.get_mut(selector)
-                .unwrap()
-                .insert(property.to_string(), value.to_string());
-        } else {
-            let mut new_style = BTreeMap::new();
-            new_style.insert(property.to_string(), value.to_string());
-            self.insert(selector.to_string(), new_style);
-        }
+    fn add_style(
+        &mut self,
+        selector: impl Display,
+        property: impl AsRef<str>,
+        value: impl Display,
+    ) {
+        self.rule_mut(&selector.to_string()).set(property, value);
     }
 
-    fn add_class(&mut self, selector: &str, properties: BTreeMap<String, String>) {
-        if self.contains_key(selector) {
-            let current_properties = self.get_mut(selector).unwrap();
-            for (property, value) in properties {
-                current_properties.insert(property, value);
-            }
-        } else {
-            self.insert(selector.to_string(), properties);
+    fn add_class(&mut self, selector: impl Display, properties: BTreeMap<String, String>) {
+        let rule = self.rule_mut(&selector.to_string());
+        for (property, value) in properties {
+            rule.set(&property, &value);
         }
     }
 
-    fn with_style(&mut self, selector: &str, property: &str, value: &str) -> Self {
+    fn with_style(
+        &mut self,
+        selector: impl Display,
+        property: impl AsRef<str>,
+        value: impl Display,
+    ) -> Self {
         let mut new_style = self.clone();
         new_style.add_style(selector, property, value);
         new_style
     }
 
-    fn with_class(&mut self, selector: &str, properties: BTreeMap<String, String>) -> Self {
+    fn with_class(&mut self, selector: impl Display, properties: BTreeMap<String, String>) -> Self {
         let mut new_style = self.clone();
         new_style.add_class(selector, properties);
         new_style
     }
 }
 
+/// Adds parsed declarations to a class, where an `!important` value
+/// is only replaced by another `!important` value.
+fn add_declarations(class: &mut Class, declarations: &[Declaration]) {
+    for declaration in declarations {
+        let important = class
+            .get(&declaration.property)
+            .is_some_and(|value| value.ends_with("!important"));
+        if !important || declaration.important {
+            class.insert(declaration.property.clone(), declaration.full_value());
+        }
+    }
+}
+
 pub fn convert_to_styles(class: Class) -> String {
     let mut styles = String::new();
     for (property, value) in class {
@@ -81,6 +964,81 @@ pub fn convert_to_styles(class: Class) -> String {
     styles
 }
 
+/// Parses a list of declarations, like the value of a `style` attribute,
+/// into a `Class`. This is the inverse of `convert_to_styles`.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::{convert_to_styles, parse_styles};
+///
+/// let class = parse_styles("color: red; margin: 0 auto !important;").unwrap();
+///
+/// assert_eq!(class["margin"], "0 auto !important");
+/// assert_eq!(parse_styles(&convert_to_styles(class.clone())).unwrap(), class);
+/// assert!(parse_styles("color red").is_err());
+/// ```
+pub fn parse_styles(styles: &str) -> Result<Class, CssParseError> {
+    let mut class = Class::new();
+    add_declarations(&mut class, &css::parse_declarations(styles)?);
+    Ok(class)
+}
+
+/// Parses a list of declarations like browsers do for `style` attributes,
+/// dropping the ones that are malformed.
+pub(crate) fn parse_styles_lenient(styles: &str) -> Class {
+    let mut class = Class::new();
+    add_declarations(&mut class, &css::parse_declarations_lenient(styles));
+    class
+}
+
+/// Returns the name of a custom property, with the leading `--` added
+/// if it is missing.
+pub(crate) fn custom_property_name(name: &str) -> String {
+    let name = name.trim();
+    if name.starts_with("--") {
+        name.to_string()
+    } else {
+        format!("--{}", name)
+    }
+}
+
+/// Quotes a value as a CSS string.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\a "),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Minifies a stylesheet, removing comments and any whitespace that
+/// does not change its meaning.
+///
+/// Strings, escapes, selector combinators and values with more than
+/// one part, like `margin: 0 auto`, are kept intact.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::sanitize_styles;
+///
+/// let css = "/* nav */\n.nav a > span ,\n.nav  a:hover {\n    font-family: \"Times  New Roman\", serif;\n    margin : 0 auto ;\n}\n";
+///
+/// assert_eq!(
+///     sanitize_styles(css.to_string()),
+///     ".nav a>span,.nav a:hover{font-family:\"Times  New Roman\",serif;margin:0 auto;}"
+/// );
+/// ```
 pub fn sanitize_styles(styles: String) -> String {
-    styles.replace(['\n', '\t'], "").replace(' ', "")
+    css::minify(&styles)
 }
@@ -0,0 +1,513 @@
+//! Matching the rules of a `StyleSheet` against a tree of `HtmlTag`s,
+//! to find the declarations that apply to each element.
+
+use std::collections::BTreeMap;
+
+use crate::css::split_important;
+use crate::html::HtmlTag;
+use crate::node::Node;
+
+use super::{
+    AttributeOperator, AttributeSelector, Class, Combinator, PseudoClass, Selector, SimpleSelector,
+    Specificity, StyleSheet,
+};
+
+/// The declarations that apply to a single element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputedStyle<'a> {
+    pub element: &'a HtmlTag,
+    /// The indexes into `children` that lead from the root to the
+    /// element, which is empty for the root itself.
+    pub path: Vec<usize>,
+    /// The winning value of every property, without `!important`.
+    pub declarations: Class,
+}
+
+/// The declarations that apply to every element of a tree, in document
+/// order, see `StyleSheet::compute_styles`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputedStyles<'a> {
+    elements: Vec<ComputedStyle<'a>>,
+}
+
+impl<'a> ComputedStyles<'a> {
+    /// Returns the declarations of the given element, which has to be
+    /// part of the tree the styles were computed for, rather than a
+    /// copy of it.
+    pub fn get(&self, element: &HtmlTag) -> Option<&Class> {
+        self.elements
+            .iter()
+            .find(|style| std::ptr::eq(style.element, element))
+            .map(|style| &style.declarations)
+    }
+
+    /// Returns the declarations of the element at the given path, see
+    /// `ComputedStyle::path`.
+    pub fn get_path(&self, path: &[usize]) -> Option<&Class> {
+        self.elements
+            .iter()
+            .find(|style| style.path == path)
+            .map(|style| &style.declarations)
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns whether there are no elements, which never happens for
+    /// styles computed from a tree.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Iterates over the elements in document order.
+    pub fn iter(&self) -> impl Iterator<Item = &ComputedStyle<'a>> {
+        self.elements.iter()
+    }
+}
+
+/// Where a declaration stands in the cascade, where a later field only
+/// matters when the earlier ones are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Precedence {
+    important: bool,
+    inline: bool,
+    specificity: Specificity,
+    /// The index of the rule in the stylesheet, which follows the
+    /// source order for parsed stylesheets, where every occurrence of a
+    /// selector has a rule of its own.
+    order: usize,
+}
+
+pub(crate) fn compute_styles<'a>(sheet: &StyleSheet, root: &'a HtmlTag) -> ComputedStyles<'a> {
+    let tree = Tree::new(root);
+
+    // Like browsers, rules with a selector that can not be parsed are
+    // dropped.
+    let rules: Vec<_> = sheet
+        .iter()
+        .filter(|rule| !rule.selector.starts_with('@'))
+        .filter_map(|rule| Some((rule.selectors().ok()?, rule)))
+        .collect();
+
+    let elements = (0..tree.elements.len())
+        .map(|element| {
+            let mut cascaded = BTreeMap::new();
+
+            for (order, (selectors, rule)) in rules.iter().enumerate() {
+                // The most specific selector of a list that matches.
+                let specificity = selectors
+                    .iter()
+                    .filter(|selector| tree.matches(selector, element, None))
+                    .map(Selector::specificity)
+                    .max();
+                if let Some(specificity) = specificity {
+                    for (property, value) in rule.iter() {
+                        let important = has_important(value);
+                        let precedence = Precedence {
+                            important,
+                            inline: false,
+                            specificity,
+                            order,
+                        };
+                        apply(&mut cascaded, property, value, precedence);
+                    }
+                }
+            }
+
+            let tag = tree.elements[element].tag;
+            for (property, value) in &tag.inline_styles {
+                let precedence = Precedence {
+                    important: has_important(value),
+                    inline: true,
+                    specificity: Specificity::default(),
+                    order: 0,
+                };
+                apply(&mut cascaded, property, value, precedence);
+            }
+
+            ComputedStyle {
+                element: tag,
+                path: tree.elements[element].path.clone(),
+                declarations: cascaded
+                    .into_iter()
+                    .map(|(property, (_, value))| (property.to_string(), split_important(value).0))
+                    .collect(),
+            }
+        })
+        .collect();
+    ComputedStyles { elements }
+}
+
+/// Keeps the declaration if it wins over the one for the same property
+/// so far, where a later declaration wins when they are equal.
+fn apply<'b>(
+    cascaded: &mut BTreeMap<&'b str, (Precedence, &'b str)>,
+    property: &'b str,
+    value: &'b str,
+    precedence: Precedence,
+) {
+    match cascaded.get(property) {
+        Some((current, _)) if *current > precedence => {}
+        _ => {
+            cascaded.insert(property, (precedence, value));
+        }
+    }
+}
+
+fn has_important(value: &str) -> bool {
+    split_important(value).1
+}
+
+struct Element<'a> {
+    tag: &'a HtmlTag,
+    path: Vec<usize>,
+    parent: Option<usize>,
+    /// The elements among the children, leaving out text and comments.
+    children: Vec<usize>,
+    /// The position in the `children` of the parent.
+    position: usize,
+}
+
+/// The elements of a tree in document order, linked to their parents
+/// and children for matching.
+struct Tree<'a> {
+    elements: Vec<Element<'a>>,
+    roots: Vec<usize>,
+}
+
+impl<'a> Tree<'a> {
+    fn new(root: &'a HtmlTag) -> Tree<'a> {
+        let mut tree = Tree {
+            elements: Vec::new(),
+            roots: vec![0],
+        };
+        tree.add(root, Vec::new(), None, 0);
+        tree
+    }
+
+    fn add(&mut self, tag: &'a HtmlTag, path: Vec<usize>, parent: Option<usize>, position: usize) {
+        let index = self.elements.len();
+        self.elements.push(Element {
+            tag,
+            path: path.clone(),
+            parent,
+            children: Vec::new(),
+            position,
+        });
+        let mut position = 0;
+        for (i, child) in tag.children.iter().enumerate() {
+            if let Node::Element(child) = child {
+                let child_index = self.elements.len();
+                self.elements[index].children.push(child_index);
+                let mut child_path = path.clone();
+                child_path.push(i);
+                self.add(child, child_path, Some(index), position);
+                position += 1;
+            }
+        }
+    }
+
+    fn siblings(&self, element: usize) -> &[usize] {
+        match self.elements[element].parent {
+            Some(parent) => &self.elements[parent].children,
+            None => &self.roots,
+        }
+    }
+
+    fn previous_siblings(&self, element: usize) -> &[usize] {
+        &self.siblings(element)[..self.elements[element].position]
+    }
+
+    fn ancestors(&self, element: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.elements[element].parent, |&a| self.elements[a].parent)
+    }
+
+    fn tag_name(&self, element: usize) -> &str {
+        self.elements[element].tag.tag_type.as_str()
+    }
+
+    /// Returns whether the element matches the selector, where `anchor`
+    /// is the element a relative selector, like in `:has(> img)`,
+    /// starts from.
+    fn matches(&self, selector: &Selector, element: usize, anchor: Option<usize>) -> bool {
+        !selector.compounds.is_empty()
+            && self.matches_from(selector, selector.compounds.len() - 1, element, anchor)
+    }
+
+    /// Matches the compound selectors up to the given one from right to
+    /// left, trying every element the combinators allow.
+    fn matches_from(
+        &self,
+        selector: &Selector,
+        compound: usize,
+        element: usize,
+        anchor: Option<usize>,
+    ) -> bool {
+        let current = &selector.compounds[compound];
+        if !current
+            .selectors
+            .iter()
+            .all(|simple| self.matches_simple(simple, element))
+        {
+            return false;
+        }
+        let combinator = current.combinator.unwrap_or(Combinator::Descendant);
+        if compound == 0 {
+            return match anchor {
+                Some(anchor) => self.related(combinator, anchor, element),
+                None => true,
+            };
+        }
+        let previous = |other: usize| self.matches_from(selector, compound - 1, other, anchor);
+        match combinator {
+            Combinator::Descendant => self.ancestors(element).any(previous),
+            Combinator::Child => self.elements[element].parent.is_some_and(previous),
+            Combinator::NextSibling => self
+                .previous_siblings(element)
+                .last()
+                .is_some_and(|&s| previous(s)),
+            Combinator::SubsequentSibling => {
+                self.previous_siblings(element).iter().any(|&s| previous(s))
+            }
+        }
+    }
+
+    /// Returns whether the element is related to the anchor by the
+    /// combinator.
+    fn related(&self, combinator: Combinator, anchor: usize, element: usize) -> bool {
+        match combinator {
+            Combinator::Descendant => self.ancestors(element).any(|a| a == anchor),
+            Combinator::Child => self.elements[element].parent == Some(anchor),
+            Combinator::NextSibling => {
+                self.elements[element].parent == self.elements[anchor].parent
+                    && self.elements[element].position == self.elements[anchor].position + 1
+            }
+            Combinator::SubsequentSibling => {
+                self.elements[element].parent == self.elements[anchor].parent
+                    && self.elements[element].position > self.elements[anchor].position
+            }
+        }
+    }
+
+    fn matches_simple(&self, selector: &SimpleSelector, element: usize) -> bool {
+        let tag = self.elements[element].tag;
+        match selector {
+            SimpleSelector::Universal => true,
+            SimpleSelector::Type(name) => self.tag_name(element).eq_ignore_ascii_case(name),
+            SimpleSelector::Id(id) => tag.id.as_deref() == Some(id.as_str()),
+            SimpleSelector::Class(name) => tag.has_class(name),
+            SimpleSelector::Attribute(attribute) => matches_attribute(attribute, tag),
+            SimpleSelector::PseudoClass(pseudo_class) => {
+                self.matches_pseudo_class(pseudo_class, element)
+            }
+            // A pseudo-element styles a part of the element, not the
+            // element itself.
+            SimpleSelector::PseudoElement(_) => false,
+        }
+    }
+
+    fn matches_pseudo_class(&self, pseudo_class: &PseudoClass, element: usize) -> bool {
+        let tag = self.elements[element].tag;
+        match pseudo_class {
+            PseudoClass::Name(name) => match name.as_str() {
+                "root" => self.elements[element].parent.is_none(),
+                "empty" => tag.children.iter().all(|child| match child {
+                    Node::Comment(_) => true,
+                    Node::Text(text) => text.is_empty(),
+                    _ => false,
+                }),
+                "first-child" => self.nth(element, false, None, |position| position == 1),
+                "last-child" => self.nth(element, true, None, |position| position == 1),
+                "only-child" => self.siblings(element).len() == 1,
+                "first-of-type" => self.nth_of_type(element, false, |position| position == 1),
+                "last-of-type" => self.nth_of_type(element, true, |position| position == 1),
+                "only-of-type" => {
+                    self.nth_of_type(element, false, |position| position == 1)
+                        && self.nth_of_type(element, true, |position| position == 1)
+                }
+                "link" | "any-link" => {
+                    matches!(self.tag_name(element), "a" | "area") && tag.has_attribute("href")
+                }
+                "checked" => match self.tag_name(element) {
+                    "input" => tag.has_attribute("checked"),
+                    "option" => tag.has_attribute("selected"),
+                    _ => false,
+                },
+                "disabled" => {
+                    is_form_element(self.tag_name(element)) && tag.has_attribute("disabled")
+                }
+                "enabled" => {
+                    is_form_element(self.tag_name(element)) && !tag.has_attribute("disabled")
+                }
+                "required" => tag.has_attribute("required"),
+                "optional" => {
+                    matches!(self.tag_name(element), "input" | "select" | "textarea")
+                        && !tag.has_attribute("required")
+                }
+                // States like `:hover` never match a static document.
+                _ => false,
+            },
+            PseudoClass::Selectors(name, selectors) => match name.as_str() {
+                "not" => !selectors
+                    .iter()
+                    .any(|selector| self.matches(selector, element, None)),
+                "has" => selectors.iter().any(|selector| {
+                    (0..self.elements.len())
+                        .any(|other| self.matches(selector, other, Some(element)))
+                }),
+                _ => selectors
+                    .iter()
+                    .any(|selector| self.matches(selector, element, None)),
+            },
+            PseudoClass::Function(name, argument) => {
+                let (formula, of) = match argument.split_once(" of ") {
+                    Some((formula, of)) => (formula, Some(of)),
+                    None => (argument.as_str(), None),
+                };
+                let Some((a, b)) = parse_nth(formula) else {
+                    return false;
+                };
+                // The coefficients come straight from the stylesheet, so a
+                // formula that overflows never matches.
+                let nth = |position: usize| {
+                    let Some(offset) = i64::try_from(position)
+                        .ok()
+                        .and_then(|position| position.checked_sub(b))
+                    else {
+                        return false;
+                    };
+                    match a {
+                        0 => offset == 0,
+                        a => {
+                            offset.checked_rem(a) == Some(0)
+                                && offset.checked_div(a).is_some_and(|n| n >= 0)
+                        }
+                    }
+                };
+                match name.as_str() {
+                    "nth-child" | "nth-last-child" => {
+                        let of = match of.map(Selector::parse_list) {
+                            Some(Ok(selectors)) => Some(selectors),
+                            Some(Err(_)) => return false,
+                            None => None,
+                        };
+                        self.nth(element, name == "nth-last-child", of.as_deref(), nth)
+                    }
+                    "nth-of-type" if of.is_none() => self.nth_of_type(element, false, nth),
+                    "nth-last-of-type" if of.is_none() => self.nth_of_type(element, true, nth),
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// Checks the position of the element among its siblings that match
+    /// one of the given selectors, counting from 1.
+    fn nth(
+        &self,
+        element: usize,
+        from_end: bool,
+        of: Option<&[Selector]>,
+        check: impl Fn(usize) -> bool,
+    ) -> bool {
+        let counts = |sibling: &usize| match of {
+            Some(selectors) => selectors
+                .iter()
+                .any(|selector| self.matches(selector, *sibling, None)),
+            None => true,
+        };
+        if !counts(&element) {
+            return false;
+        }
+        let siblings = self.siblings(element);
+        let position = self.elements[element].position;
+        let before = if from_end {
+            siblings[position + 1..]
+                .iter()
+                .filter(|s| counts(s))
+                .count()
+        } else {
+            siblings[..position].iter().filter(|s| counts(s)).count()
+        };
+        check(before + 1)
+    }
+
+    fn nth_of_type(&self, element: usize, from_end: bool, check: impl Fn(usize) -> bool) -> bool {
+        let name = self.tag_name(element);
+        let siblings = self.siblings(element);
+        let position = self.elements[element].position;
+        let others = if from_end {
+            &siblings[position + 1..]
+        } else {
+            &siblings[..position]
+        };
+        let before = others
+            .iter()
+            .filter(|&&sibling| self.tag_name(sibling) == name)
+            .count();
+        check(before + 1)
+    }
+}
+
+fn is_form_element(name: &str) -> bool {
+    matches!(
+        name,
+        "button" | "input" | "select" | "textarea" | "optgroup" | "option" | "fieldset"
+    )
+}
+
+fn matches_attribute(attribute: &AttributeSelector, tag: &HtmlTag) -> bool {
+    let Some(actual) = tag.get_attribute(&attribute.name.to_ascii_lowercase()) else {
+        return false;
+    };
+    let Some((operator, expected)) = &attribute.value else {
+        return true;
+    };
+    let (actual, expected) = if attribute.case_insensitive {
+        (actual.to_lowercase(), expected.to_lowercase())
+    } else {
+        (actual, expected.clone())
+    };
+    match operator {
+        AttributeOperator::Equals => actual == expected,
+        AttributeOperator::Includes => actual.split_whitespace().any(|word| word == expected),
+        AttributeOperator::DashMatch => {
+            actual == expected || actual.starts_with(&format!("{}-", expected))
+        }
+        // An empty value never matches these.
+        AttributeOperator::Prefix => !expected.is_empty() && actual.starts_with(&expected),
+        AttributeOperator::Suffix => !expected.is_empty() && actual.ends_with(&expected),
+        AttributeOperator::Substring => !expected.is_empty() && actual.contains(&expected),
+    }
+}
+
+/// Parses the `An+B` argument of `:nth-child` and friends, like `2n + 1`,
+/// `odd` or `-n+3`.
+fn parse_nth(formula: &str) -> Option<(i64, i64)> {
+    let formula: String = formula
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match formula.as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => {}
+    }
+    let Some((a, b)) = formula.split_once('n') else {
+        return formula.parse().ok().map(|b| (0, b));
+    };
+    let a = match a {
+        "" | "+" => 1,
+        "-" => -1,
+        a => a.parse().ok()?,
+    };
+    let b = match b {
+        "" => 0,
+        b if b.starts_with(['+', '-']) => b.strip_prefix('+').unwrap_or(b).parse().ok()?,
+        _ => return None,
+    };
+    Some((a, b))
+}
@@ -0,0 +1,2391 @@
+//! CSS properties, which catch misspelled property names like `colour`
+//! or `font-wieght`, either when they are used as a `Property`, or when
+//! a `StyleSheet` is checked with `StyleSheet::validate`.
+
+use std::fmt::{self, Display};
+
+/// A CSS property.
+///
+/// This has every standard property that browsers support as an enum,
+/// and can be passed anywhere a property name is accepted today, like
+/// `add_style` or `HtmlTag::set_style`.
+///
+/// The `Custom` enum is meant for custom properties, like `--brand`,
+/// vendor prefixed properties, like `-webkit-line-clamp`, and any other
+/// property that is not part of the standard.
+/// `Property::from` never returns a `Custom` property for a standard one.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::Property;
+/// use html_tag::{Style, StyleSheet};
+///
+/// let mut sheet = StyleSheet::new();
+/// sheet.add_style(".title", Property::FontWeight, "bold");
+/// sheet.add_style(".title", Property::from("--accent"), "red");
+///
+/// assert_eq!(sheet[".title"]["font-weight"], "bold");
+/// assert_eq!(Property::from("Font-Weight"), Property::FontWeight);
+/// assert!(Property::from("-webkit-line-clamp").is_known());
+/// assert!(!Property::from("font-wieght").is_known());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Property {
+    AccentColor,
+    AlignContent,
+    AlignItems,
+    AlignSelf,
+    AlignmentBaseline,
+    All,
+    AnchorName,
+    AnchorScope,
+    Animation,
+    AnimationComposition,
+    AnimationDelay,
+    AnimationDirection,
+    AnimationDuration,
+    AnimationFillMode,
+    AnimationIterationCount,
+    AnimationName,
+    AnimationPlayState,
+    AnimationRange,
+    AnimationRangeEnd,
+    AnimationRangeStart,
+    AnimationTimeline,
+    AnimationTimingFunction,
+    Appearance,
+    AspectRatio,
+    BackdropFilter,
+    BackfaceVisibility,
+    Background,
+    BackgroundAttachment,
+    BackgroundBlendMode,
+    BackgroundClip,
+    BackgroundColor,
+    BackgroundImage,
+    BackgroundOrigin,
+    BackgroundPosition,
+    BackgroundPositionX,
+    BackgroundPositionY,
+    BackgroundRepeat,
+    BackgroundSize,
+    BaselineShift,
+    BlockSize,
+    Border,
+    BorderBlock,
+    BorderBlockColor,
+    BorderBlockEnd,
+    BorderBlockEndColor,
+    BorderBlockEndStyle,
+    BorderBlockEndWidth,
+    BorderBlockStart,
+    BorderBlockStartColor,
+    BorderBlockStartStyle,
+    BorderBlockStartWidth,
+    BorderBlockStyle,
+    BorderBlockWidth,
+    BorderBottom,
+    BorderBottomColor,
+    BorderBottomLeftRadius,
+    BorderBottomRightRadius,
+    BorderBottomStyle,
+    BorderBottomWidth,
+    BorderCollapse,
+    BorderColor,
+    BorderEndEndRadius,
+    BorderEndStartRadius,
+    BorderImage,
+    BorderImageOutset,
+    BorderImageRepeat,
+    BorderImageSlice,
+    BorderImageSource,
+    BorderImageWidth,
+    BorderInline,
+    BorderInlineColor,
+    BorderInlineEnd,
+    BorderInlineEndColor,
+    BorderInlineEndStyle,
+    BorderInlineEndWidth,
+    BorderInlineStart,
+    BorderInlineStartColor,
+    BorderInlineStartStyle,
+    BorderInlineStartWidth,
+    BorderInlineStyle,
+    BorderInlineWidth,
+    BorderLeft,
+    BorderLeftColor,
+    BorderLeftStyle,
+    BorderLeftWidth,
+    BorderRadius,
+    BorderRight,
+    BorderRightColor,
+    BorderRightStyle,
+    BorderRightWidth,
+    BorderSpacing,
+    BorderStartEndRadius,
+    BorderStartStartRadius,
+    BorderStyle,
+    BorderTop,
+    BorderTopColor,
+    BorderTopLeftRadius,
+    BorderTopRightRadius,
+    BorderTopStyle,
+    BorderTopWidth,
+    BorderWidth,
+    Bottom,
+    BoxDecorationBreak,
+    BoxShadow,
+    BoxSizing,
+    BreakAfter,
+    BreakBefore,
+    BreakInside,
+    CaptionSide,
+    CaretColor,
+    Clear,
+    Clip,
+    ClipPath,
+    ClipRule,
+    Color,
+    ColorInterpolation,
+    ColorInterpolationFilters,
+    ColorScheme,
+    ColumnCount,
+    ColumnFill,
+    ColumnGap,
+    ColumnRule,
+    ColumnRuleColor,
+    ColumnRuleStyle,
+    ColumnRuleWidth,
+    ColumnSpan,
+    ColumnWidth,
+    Columns,
+    Contain,
+    ContainIntrinsicBlockSize,
+    ContainIntrinsicHeight,
+    ContainIntrinsicInlineSize,
+    ContainIntrinsicSize,
+    ContainIntrinsicWidth,
+    Container,
+    ContainerName,
+    ContainerType,
+    Content,
+    ContentVisibility,
+    CounterIncrement,
+    CounterReset,
+    CounterSet,
+    Cursor,
+    Cx,
+    Cy,
+    D,
+    Direction,
+    Display,
+    DominantBaseline,
+    EmptyCells,
+    FieldSizing,
+    Fill,
+    FillOpacity,
+    FillRule,
+    Filter,
+    Flex,
+    FlexBasis,
+    FlexDirection,
+    FlexFlow,
+    FlexGrow,
+    FlexShrink,
+    FlexWrap,
+    Float,
+    FloodColor,
+    FloodOpacity,
+    Font,
+    FontFamily,
+    FontFeatureSettings,
+    FontKerning,
+    FontLanguageOverride,
+    FontOpticalSizing,
+    FontPalette,
+    FontSize,
+    FontSizeAdjust,
+    FontStretch,
+    FontStyle,
+    FontSynthesis,
+    FontSynthesisPosition,
+    FontSynthesisSmallCaps,
+    FontSynthesisStyle,
+    FontSynthesisWeight,
+    FontVariant,
+    FontVariantAlternates,
+    FontVariantCaps,
+    FontVariantEastAsian,
+    FontVariantEmoji,
+    FontVariantLigatures,
+    FontVariantNumeric,
+    FontVariantPosition,
+    FontVariationSettings,
+    FontWeight,
+    ForcedColorAdjust,
+    Gap,
+    Grid,
+    GridArea,
+    GridAutoColumns,
+    GridAutoFlow,
+    GridAutoRows,
+    GridColumn,
+    GridColumnEnd,
+    GridColumnStart,
+    GridRow,
+    GridRowEnd,
+    GridRowStart,
+    GridTemplate,
+    GridTemplateAreas,
+    GridTemplateColumns,
+    GridTemplateRows,
+    HangingPunctuation,
+    Height,
+    HyphenateCharacter,
+    Hyphens,
+    ImageOrientation,
+    ImageRendering,
+    InitialLetter,
+    InlineSize,
+    Inset,
+    InsetBlock,
+    InsetBlockEnd,
+    InsetBlockStart,
+    InsetInline,
+    InsetInlineEnd,
+    InsetInlineStart,
+    InterpolateSize,
+    Isolation,
+    JustifyContent,
+    JustifyItems,
+    JustifySelf,
+    Left,
+    LetterSpacing,
+    LightingColor,
+    LineBreak,
+    LineClamp,
+    LineHeight,
+    ListStyle,
+    ListStyleImage,
+    ListStylePosition,
+    ListStyleType,
+    Margin,
+    MarginBlock,
+    MarginBlockEnd,
+    MarginBlockStart,
+    MarginBottom,
+    MarginInline,
+    MarginInlineEnd,
+    MarginInlineStart,
+    MarginLeft,
+    MarginRight,
+    MarginTop,
+    Marker,
+    MarkerEnd,
+    MarkerMid,
+    MarkerStart,
+    Mask,
+    MaskBorder,
+    MaskBorderMode,
+    MaskBorderOutset,
+    MaskBorderRepeat,
+    MaskBorderSlice,
+    MaskBorderSource,
+    MaskBorderWidth,
+    MaskClip,
+    MaskComposite,
+    MaskImage,
+    MaskMode,
+    MaskOrigin,
+    MaskPosition,
+    MaskRepeat,
+    MaskSize,
+    MaskType,
+    MathDepth,
+    MathStyle,
+    MaxBlockSize,
+    MaxHeight,
+    MaxInlineSize,
+    MaxWidth,
+    MinBlockSize,
+    MinHeight,
+    MinInlineSize,
+    MinWidth,
+    MixBlendMode,
+    ObjectFit,
+    ObjectPosition,
+    Offset,
+    OffsetAnchor,
+    OffsetDistance,
+    OffsetPath,
+    OffsetPosition,
+    OffsetRotate,
+    Opacity,
+    Order,
+    Orphans,
+    Outline,
+    OutlineColor,
+    OutlineOffset,
+    OutlineStyle,
+    OutlineWidth,
+    Overflow,
+    OverflowAnchor,
+    OverflowBlock,
+    OverflowClipMargin,
+    OverflowInline,
+    OverflowWrap,
+    OverflowX,
+    OverflowY,
+    OverscrollBehavior,
+    OverscrollBehaviorBlock,
+    OverscrollBehaviorInline,
+    OverscrollBehaviorX,
+    OverscrollBehaviorY,
+    Padding,
+    PaddingBlock,
+    PaddingBlockEnd,
+    PaddingBlockStart,
+    PaddingBottom,
+    PaddingInline,
+    PaddingInlineEnd,
+    PaddingInlineStart,
+    PaddingLeft,
+    PaddingRight,
+    PaddingTop,
+    Page,
+    PageBreakAfter,
+    PageBreakBefore,
+    PageBreakInside,
+    PaintOrder,
+    Perspective,
+    PerspectiveOrigin,
+    PlaceContent,
+    PlaceItems,
+    PlaceSelf,
+    PointerEvents,
+    Position,
+    PositionAnchor,
+    PositionArea,
+    PositionTry,
+    PositionTryFallbacks,
+    PositionTryOrder,
+    PositionVisibility,
+    PrintColorAdjust,
+    Quotes,
+    R,
+    Resize,
+    Right,
+    Rotate,
+    RowGap,
+    RubyAlign,
+    RubyPosition,
+    Rx,
+    Ry,
+    Scale,
+    ScrollBehavior,
+    ScrollMargin,
+    ScrollMarginBlock,
+    ScrollMarginBlockEnd,
+    ScrollMarginBlockStart,
+    ScrollMarginBottom,
+    ScrollMarginInline,
+    ScrollMarginInlineEnd,
+    ScrollMarginInlineStart,
+    ScrollMarginLeft,
+    ScrollMarginRight,
+    ScrollMarginTop,
+    ScrollPadding,
+    ScrollPaddingBlock,
+    ScrollPaddingBlockEnd,
+    ScrollPaddingBlockStart,
+    ScrollPaddingBottom,
+    ScrollPaddingInline,
+    ScrollPaddingInlineEnd,
+    ScrollPaddingInlineStart,
+    ScrollPaddingLeft,
+    ScrollPaddingRight,
+    ScrollPaddingTop,
+    ScrollSnapAlign,
+    ScrollSnapStop,
+    ScrollSnapType,
+    ScrollTimeline,
+    ScrollTimelineAxis,
+    ScrollTimelineName,
+    ScrollbarColor,
+    ScrollbarGutter,
+    ScrollbarWidth,
+    ShapeImageThreshold,
+    ShapeMargin,
+    ShapeOutside,
+    ShapeRendering,
+    StopColor,
+    StopOpacity,
+    Stroke,
+    StrokeDasharray,
+    StrokeDashoffset,
+    StrokeLinecap,
+    StrokeLinejoin,
+    StrokeMiterlimit,
+    StrokeOpacity,
+    StrokeWidth,
+    TabSize,
+    TableLayout,
+    TextAlign,
+    TextAlignLast,
+    TextAnchor,
+    TextAutospace,
+    TextBox,
+    TextBoxEdge,
+    TextBoxTrim,
+    TextCombineUpright,
+    TextDecoration,
+    TextDecorationColor,
+    TextDecorationLine,
+    TextDecorationSkipInk,
+    TextDecorationStyle,
+    TextDecorationThickness,
+    TextEmphasis,
+    TextEmphasisColor,
+    TextEmphasisPosition,
+    TextEmphasisStyle,
+    TextIndent,
+    TextJustify,
+    TextOrientation,
+    TextOverflow,
+    TextRendering,
+    TextShadow,
+    TextSizeAdjust,
+    TextSpacingTrim,
+    TextTransform,
+    TextUnderlineOffset,
+    TextUnderlinePosition,
+    TextWrap,
+    TextWrapMode,
+    TextWrapStyle,
+    TimelineScope,
+    Top,
+    TouchAction,
+    Transform,
+    TransformBox,
+    TransformOrigin,
+    TransformStyle,
+    Transition,
+    TransitionBehavior,
+    TransitionDelay,
+    TransitionDuration,
+    TransitionProperty,
+    TransitionTimingFunction,
+    Translate,
+    UnicodeBidi,
+    UserSelect,
+    VectorEffect,
+    VerticalAlign,
+    ViewTimeline,
+    ViewTimelineAxis,
+    ViewTimelineInset,
+    ViewTimelineName,
+    ViewTransitionClass,
+    ViewTransitionName,
+    Visibility,
+    WhiteSpace,
+    WhiteSpaceCollapse,
+    Widows,
+    Width,
+    WillChange,
+    WordBreak,
+    WordSpacing,
+    WordWrap,
+    WritingMode,
+    X,
+    Y,
+    ZIndex,
+    Zoom,
+    Custom(String),
+}
+
+impl Property {
+    /// Every property defined by the CSS standards, i.e. every
+    /// `Property` except `Custom`.
+    pub const STANDARD: &'static [Property] = &[
+        Property::AccentColor,
+        Property::AlignContent,
+        Property::AlignItems,
+        Property::AlignSelf,
+        Property::AlignmentBaseline,
+        Property::All,
+        Property::AnchorName,
+        Property::AnchorScope,
+        Property::Animation,
+        Property::AnimationComposition,
+        Property::AnimationDelay,
+        Property::AnimationDirection,
+        Property::AnimationDuration,
+        Property::AnimationFillMode,
+        Property::AnimationIterationCount,
+        Property::AnimationName,
+        Property::AnimationPlayState,
+        Property::AnimationRange,
+        Property::AnimationRangeEnd,
+        Property::AnimationRangeStart,
+        Property::AnimationTimeline,
+        Property::AnimationTimingFunction,
+        Property::Appearance,
+        Property::AspectRatio,
+        Property::BackdropFilter,
+        Property::BackfaceVisibility,
+        Property::Background,
+        Property::BackgroundAttachment,
+        Property::BackgroundBlendMode,
+        Property::BackgroundClip,
+        Property::BackgroundColor,
+        Property::BackgroundImage,
+        Property::BackgroundOrigin,
+        Property::BackgroundPosition,
+        Property::BackgroundPositionX,
+        Property::BackgroundPositionY,
+        Property::BackgroundRepeat,
+        Property::BackgroundSize,
+        Property::BaselineShift,
+        Property::BlockSize,
+        Property::Border,
+        Property::BorderBlock,
+        Property::BorderBlockColor,
+        Property::BorderBlockEnd,
+        Property::BorderBlockEndColor,
+        Property::BorderBlockEndStyle,
+        Property::BorderBlockEndWidth,
+        Property::BorderBlockStart,
+        Property::BorderBlockStartColor,
+        Property::BorderBlockStartStyle,
+        Property::BorderBlockStartWidth,
+        Property::BorderBlockStyle,
+        Property::BorderBlockWidth,
+        Property::BorderBottom,
+        Property::BorderBottomColor,
+        Property::BorderBottomLeftRadius,
+        Property::BorderBottomRightRadius,
+        Property::BorderBottomStyle,
+        Property::BorderBottomWidth,
+        Property::BorderCollapse,
+        Property::BorderColor,
+        Property::BorderEndEndRadius,
+        Property::BorderEndStartRadius,
+        Property::BorderImage,
+        Property::BorderImageOutset,
+        Property::BorderImageRepeat,
+        Property::BorderImageSlice,
+        Property::BorderImageSource,
+        Property::BorderImageWidth,
+        Property::BorderInline,
+        Property::BorderInlineColor,
+        Property::BorderInlineEnd,
+        Property::BorderInlineEndColor,
+        Property::BorderInlineEndStyle,
+        Property::BorderInlineEndWidth,
+        Property::BorderInlineStart,
+        Property::BorderInlineStartColor,
+        Property::BorderInlineStartStyle,
+        Property::BorderInlineStartWidth,
+        Property::BorderInlineStyle,
+        Property::BorderInlineWidth,
+        Property::BorderLeft,
+        Property::BorderLeftColor,
+        Property::BorderLeftStyle,
+        Property::BorderLeftWidth,
+        Property::BorderRadius,
+        Property::BorderRight,
+        Property::BorderRightColor,
+        Property::BorderRightStyle,
+        Property::BorderRightWidth,
+        Property::BorderSpacing,
+        Property::BorderStartEndRadius,
+        Property::BorderStartStartRadius,
+        Property::BorderStyle,
+        Property::BorderTop,
+        Property::BorderTopColor,
+        Property::BorderTopLeftRadius,
+        Property::BorderTopRightRadius,
+        Property::BorderTopStyle,
+        Property::BorderTopWidth,
+        Property::BorderWidth,
+        Property::Bottom,
+        Property::BoxDecorationBreak,
+        Property::BoxShadow,
+        Property::BoxSizing,
+        Property::BreakAfter,
+        Property::BreakBefore,
+        Property::BreakInside,
+        Property::CaptionSide,
+        Property::CaretColor,
+        Property::Clear,
+        Property::Clip,
+        Property::ClipPath,
+        Property::ClipRule,
+        Property::Color,
+        Property::ColorInterpolation,
+        Property::ColorInterpolationFilters,
+        Property::ColorScheme,
+        Property::ColumnCount,
+        Property::ColumnFill,
+        Property::ColumnGap,
+        Property::ColumnRule,
+        Property::ColumnRuleColor,
+        Property::ColumnRuleStyle,
+        Property::ColumnRuleWidth,
+        Property::ColumnSpan,
+        Property::ColumnWidth,
+        Property::Columns,
+        Property::Contain,
+        Property::ContainIntrinsicBlockSize,
+        Property::ContainIntrinsicHeight,
+        Property::ContainIntrinsicInlineSize,
+        Property::ContainIntrinsicSize,
+        Property::ContainIntrinsicWidth,
+        Property::Container,
+        Property::ContainerName,
+        Property::ContainerType,
+        Property::Content,
+        Property::ContentVisibility,
+        Property::CounterIncrement,
+        Property::CounterReset,
+        Property::CounterSet,
+        Property::Cursor,
+        Property::Cx,
+        Property::Cy,
+        Property::D,
+        Property::Direction,
+        Property::Display,
+        Property::DominantBaseline,
+        Property::EmptyCells,
+        Property::FieldSizing,
+        Property::Fill,
+        Property::FillOpacity,
+        Property::FillRule,
+        Property::Filter,
+        Property::Flex,
+        Property::FlexBasis,
+        Property::FlexDirection,
+        Property::FlexFlow,
+        Property::FlexGrow,
+        Property::FlexShrink,
+        Property::FlexWrap,
+        Property::Float,
+        Property::FloodColor,
+        Property::FloodOpacity,
+        Property::Font,
+        Property::FontFamily,
+        Property::FontFeatureSettings,
+        Property::FontKerning,
+        Property::FontLanguageOverride,
+        Property::FontOpticalSizing,
+        Property::FontPalette,
+        Property::FontSize,
+        Property::FontSizeAdjust,
+        Property::FontStretch,
+        Property::FontStyle,
+        Property::FontSynthesis,
+        Property::FontSynthesisPosition,
+        Property::FontSynthesisSmallCaps,
+        Property::FontSynthesisStyle,
+        Property::FontSynthesisWeight,
+        Property::FontVariant,
+        Property::FontVariantAlternates,
+        Property::FontVariantCaps,
+        Property::FontVariantEastAsian,
+        Property::FontVariantEmoji,
+        Property::FontVariantLigatures,
+        Property::FontVariantNumeric,
+        Property::FontVariantPosition,
+        Property::FontVariationSettings,
+        Property::FontWeight,
+        Property::ForcedColorAdjust,
+        Property::Gap,
+        Property::Grid,
+        Property::GridArea,
+        Property::GridAutoColumns,
+        Property::GridAutoFlow,
+        Property::GridAutoRows,
+        Property::GridColumn,
+        Property::GridColumnEnd,
+        Property::GridColumnStart,
+        Property::GridRow,
+        Property::GridRowEnd,
+        Property::GridRowStart,
+        Property::GridTemplate,
+        Property::GridTemplateAreas,
+        Property::GridTemplateColumns,
+        Property::GridTemplateRows,
+        Property::HangingPunctuation,
+        Property::Height,
+        Property::HyphenateCharacter,
+        Property::Hyphens,
+        Property::ImageOrientation,
+        Property::ImageRendering,
+        Property::InitialLetter,
+        Property::InlineSize,
+        Property::Inset,
+        Property::InsetBlock,
+        Property::InsetBlockEnd,
+        Property::InsetBlockStart,
+        Property::InsetInline,
+        Property::InsetInlineEnd,
+        Property::InsetInlineStart,
+        Property::InterpolateSize,
+        Property::Isolation,
+        Property::JustifyContent,
+        Property::JustifyItems,
+        Property::JustifySelf,
+        Property::Left,
+        Property::LetterSpacing,
+        Property::LightingColor,
+        Property::LineBreak,
+        Property::LineClamp,
+        Property::LineHeight,
+        Property::ListStyle,
+        Property::ListStyleImage,
+        Property::ListStylePosition,
+        Property::ListStyleType,
+        Property::Margin,
+        Property::MarginBlock,
+        Property::MarginBlockEnd,
+        Property::MarginBlockStart,
+        Property::MarginBottom,
+        Property::MarginInline,
+        Property::MarginInlineEnd,
+        Property::MarginInlineStart,
+        Property::MarginLeft,
+        Property::MarginRight,
+        Property::MarginTop,
+        Property::Marker,
+        Property::MarkerEnd,
+        Property::MarkerMid,
+        Property::MarkerStart,
+        Property::Mask,
+        Property::MaskBorder,
+        Property::MaskBorderMode,
+        Property::MaskBorderOutset,
+        Property::MaskBorderRepeat,
+        Property::MaskBorderSlice,
+        Property::MaskBorderSource,
+        Property::MaskBorderWidth,
+        Property::MaskClip,
+        Property::MaskComposite,
+        Property::MaskImage,
+        Property::MaskMode,
+        Property::MaskOrigin,
+        Property::MaskPosition,
+        Property::MaskRepeat,
+        Property::MaskSize,
+        Property::MaskType,
+        Property::MathDepth,
+        Property::MathStyle,
+        Property::MaxBlockSize,
+        Property::MaxHeight,
+        Property::MaxInlineSize,
+        Property::MaxWidth,
+        Property::MinBlockSize,
+        Property::MinHeight,
+        Property::MinInlineSize,
+        Property::MinWidth,
+        Property::MixBlendMode,
+        Property::ObjectFit,
+        Property::ObjectPosition,
+        Property::Offset,
+        Property::OffsetAnchor,
+        Property::OffsetDistance,
+        Property::OffsetPath,
+        Property::OffsetPosition,
+        Property::OffsetRotate,
+        Property::Opacity,
+        Property::Order,
+        Property::Orphans,
+        Property::Outline,
+        Property::OutlineColor,
+        Property::OutlineOffset,
+        Property::OutlineStyle,
+        Property::OutlineWidth,
+        Property::Overflow,
+        Property::OverflowAnchor,
+        Property::OverflowBlock,
+        Property::OverflowClipMargin,
+        Property::OverflowInline,
+        Property::OverflowWrap,
+        Property::OverflowX,
+        Property::OverflowY,
+        Property::OverscrollBehavior,
+        Property::OverscrollBehaviorBlock,
+        Property::OverscrollBehaviorInline,
+        Property::OverscrollBehaviorX,
+        Property::OverscrollBehaviorY,
+        Property::Padding,
+        Property::PaddingBlock,
+        Property::PaddingBlockEnd,
+        Property::PaddingBlockStart,
+        Property::PaddingBottom,
+        Property::PaddingInline,
+        Property::PaddingInlineEnd,
+        Property::PaddingInlineStart,
+        Property::PaddingLeft,
+        Property::PaddingRight,
+        Property::PaddingTop,
+        Property::Page,
+        Property::PageBreakAfter,
+        Property::PageBreakBefore,
+        Property::PageBreakInside,
+        Property::PaintOrder,
+        Property::Perspective,
+        Property::PerspectiveOrigin,
+        Property::PlaceContent,
+        Property::PlaceItems,
+        Property::PlaceSelf,
+        Property::PointerEvents,
+        Property::Position,
+        Property::PositionAnchor,
+        Property::PositionArea,
+        Property::PositionTry,
+        Property::PositionTryFallbacks,
+        Property::PositionTryOrder,
+        Property::PositionVisibility,
+        Property::PrintColorAdjust,
+        Property::Quotes,
+        Property::R,
+        Property::Resize,
+        Property::Right,
+        Property::Rotate,
+        Property::RowGap,
+        Property::RubyAlign,
+        Property::RubyPosition,
+        Property::Rx,
+        Property::Ry,
+        Property::Scale,
+        Property::ScrollBehavior,
+        Property::ScrollMargin,
+        Property::ScrollMarginBlock,
+        Property::ScrollMarginBlockEnd,
+        Property::ScrollMarginBlockStart,
+        Property::ScrollMarginBottom,
+        Property::ScrollMarginInline,
+        Property::ScrollMarginInlineEnd,
+        Property::ScrollMarginInlineStart,
+        Property::ScrollMarginLeft,
+        Property::ScrollMarginRight,
+        Property::ScrollMarginTop,
+        Property::ScrollPadding,
+        Property::ScrollPaddingBlock,
+        Property::ScrollPaddingBlockEnd,
+        Property::ScrollPaddingBlockStart,
+        Property::ScrollPaddingBottom,
+        Property::ScrollPaddingInline,
+        Property::ScrollPaddingInlineEnd,
+        Property::ScrollPaddingInlineStart,
+        Property::ScrollPaddingLeft,
+        Property::ScrollPaddingRight,
+        Property::ScrollPaddingTop,
+        Property::ScrollSnapAlign,
+        Property::ScrollSnapStop,
+        Property::ScrollSnapType,
+        Property::ScrollTimeline,
+        Property::ScrollTimelineAxis,
+        Property::ScrollTimelineName,
+        Property::ScrollbarColor,
+        Property::ScrollbarGutter,
+        Property::ScrollbarWidth,
+        Property::ShapeImageThreshold,
+        Property::ShapeMargin,
+        Property::ShapeOutside,
+        Property::ShapeRendering,
+        Property::StopColor,
+        Property::StopOpacity,
+        Property::Stroke,
+        Property::StrokeDasharray,
+        Property::StrokeDashoffset,
+        Property::StrokeLinecap,
+        Property::StrokeLinejoin,
+        Property::StrokeMiterlimit,
+        Property::StrokeOpacity,
+        Property::StrokeWidth,
+        Property::TabSize,
+        Property::TableLayout,
+        Property::TextAlign,
+        Property::TextAlignLast,
+        Property::TextAnchor,
+        Property::TextAutospace,
+        Property::TextBox,
+        Property::TextBoxEdge,
+        Property::TextBoxTrim,
+        Property::TextCombineUpright,
+        Property::TextDecoration,
+        Property::TextDecorationColor,
+        Property::TextDecorationLine,
+        Property::TextDecorationSkipInk,
+        Property::TextDecorationStyle,
+        Property::TextDecorationThickness,
+        Property::TextEmphasis,
+        Property::TextEmphasisColor,
+        Property::TextEmphasisPosition,
+        Property::TextEmphasisStyle,
+        Property::TextIndent,
+        Property::TextJustify,
+        Property::TextOrientation,
+        Property::TextOverflow,
+        Property::TextRendering,
+        Property::TextShadow,
+        Property::TextSizeAdjust,
+        Property::TextSpacingTrim,
+        Property::TextTransform,
+        Property::TextUnderlineOffset,
+        Property::TextUnderlinePosition,
+        Property::TextWrap,
+        Property::TextWrapMode,
+        Property::TextWrapStyle,
+        Property::TimelineScope,
+        Property::Top,
+        Property::TouchAction,
+        Property::Transform,
+        Property::TransformBox,
+        Property::TransformOrigin,
+        Property::TransformStyle,
+        Property::Transition,
+        Property::TransitionBehavior,
+        Property::TransitionDelay,
+        Property::TransitionDuration,
+        Property::TransitionProperty,
+        Property::TransitionTimingFunction,
+        Property::Translate,
+        Property::UnicodeBidi,
+        Property::UserSelect,
+        Property::VectorEffect,
+        Property::VerticalAlign,
+        Property::ViewTimeline,
+        Property::ViewTimelineAxis,
+        Property::ViewTimelineInset,
+        Property::ViewTimelineName,
+        Property::ViewTransitionClass,
+        Property::ViewTransitionName,
+        Property::Visibility,
+        Property::WhiteSpace,
+        Property::WhiteSpaceCollapse,
+        Property::Widows,
+        Property::Width,
+        Property::WillChange,
+        Property::WordBreak,
+        Property::WordSpacing,
+        Property::WordWrap,
+        Property::WritingMode,
+        Property::X,
+        Property::Y,
+        Property::ZIndex,
+        Property::Zoom,
+    ];
+
+    /// Returns the property with the given name, ignoring case except
+    /// for custom properties.
+    ///
+    /// Any name that is not a standard property is a `Custom` one.
+    ///
+    /// Every standard property round trips through `as_str`.
+    ///
+    /// ```
+    /// use html_tag::styles::Property;
+    ///
+    /// for property in Property::STANDARD {
+    ///     assert_eq!(&Property::from(property.as_str()), property);
+    /// }
+    /// ```
+    pub fn from(name: &str) -> Property {
+        let name = name.trim();
+        if name.starts_with("--") {
+            return Property::Custom(name.to_string());
+        }
+        let name = name.to_ascii_lowercase();
+        match name.as_str() {
+            "accent-color" => Property::AccentColor,
+            "align-content" => Property::AlignContent,
+            "align-items" => Property::AlignItems,
+            "align-self" => Property::AlignSelf,
+            "alignment-baseline" => Property::AlignmentBaseline,
+            "all" => Property::All,
+            "anchor-name" => Property::AnchorName,
+            "anchor-scope" => Property::AnchorScope,
+            "animation" => Property::Animation,
+            "animation-composition" => Property::AnimationComposition,
+            "animation-delay" => Property::AnimationDelay,
+            "animation-direction" => Property::AnimationDirection,
+            "animation-duration" => Property::AnimationDuration,
+            "animation-fill-mode" => Property::AnimationFillMode,
+            "animation-iteration-count" => Property::AnimationIterationCount,
+            "animation-name" => Property::AnimationName,
+            "animation-play-state" => Property::AnimationPlayState,
+            "animation-range" => Property::AnimationRange,
+            "animation-range-end" => Property::AnimationRangeEnd,
+            "animation-range-start" => Property::AnimationRangeStart,
+            "animation-timeline" => Property::AnimationTimeline,
+            "animation-timing-function" => Property::AnimationTimingFunction,
+            "appearance" => Property::Appearance,
+            "aspect-ratio" => Property::AspectRatio,
+            "backdrop-filter" => Property::BackdropFilter,
+            "backface-visibility" => Property::BackfaceVisibility,
+            "background" => Property::Background,
+            "background-attachment" => Property::BackgroundAttachment,
+            "background-blend-mode" => Property::BackgroundBlendMode,
+            "background-clip" => Property::BackgroundClip,
+            "background-color" => Property::BackgroundColor,
+            "background-image" => Property::BackgroundImage,
+            "background-origin" => Property::BackgroundOrigin,
+            "background-position" => Property::BackgroundPosition,
+            "background-position-x" => Property::BackgroundPositionX,
+            "background-position-y" => Property::BackgroundPositionY,
+            "background-repeat" => Property::BackgroundRepeat,
+            "background-size" => Property::BackgroundSize,
+            "baseline-shift" => Property::BaselineShift,
+            "block-size" => Property::BlockSize,
+            "border" => Property::Border,
+            "border-block" => Property::BorderBlock,
+            "border-block-color" => Property::BorderBlockColor,
+            "border-block-end" => Property::BorderBlockEnd,
+            "border-block-end-color" => Property::BorderBlockEndColor,
+            "border-block-end-style" => Property::BorderBlockEndStyle,
+            "border-block-end-width" => Property::BorderBlockEndWidth,
+            "border-block-start" => Property::BorderBlockStart,
+            "border-block-start-color" => Property::BorderBlockStartColor,
+            "border-block-start-style" => Property::BorderBlockStartStyle,
+            "border-block-start-width" => Property::BorderBlockStartWidth,
+            "border-block-style" => Property::BorderBlockStyle,
+            "border-block-width" => Property::BorderBlockWidth,
+            "border-bottom" => Property::BorderBottom,
+            "border-bottom-color" => Property::BorderBottomColor,
+            "border-bottom-left-radius" => Property::BorderBottomLeftRadius,
+            "border-bottom-right-radius" => Property::BorderBottomRightRadius,
+            "border-bottom-style" => Property::BorderBottomStyle,
+            "border-bottom-width" => Property::BorderBottomWidth,
+            "border-collapse" => Property::BorderCollapse,
+            "border-color" => Property::BorderColor,
+            "border-end-end-radius" => Property::BorderEndEndRadius,
+            "border-end-start-radius" => Property::BorderEndStartRadius,
+            "border-image" => Property::BorderImage,
+            "border-image-outset" => Property::BorderImageOutset,
+            "border-image-repeat" => Property::BorderImageRepeat,
+            "border-image-slice" => Property::BorderImageSlice,
+            "border-image-source" => Property::BorderImageSource,
+            "border-image-width" => Property::BorderImageWidth,
+            "border-inline" => Property::BorderInline,
+            "border-inline-color" => Property::BorderInlineColor,
+            "border-inline-end" => Property::BorderInlineEnd,
+            "border-inline-end-color" => Property::BorderInlineEndColor,
+            "border-inline-end-style" => Property::BorderInlineEndStyle,
+            "border-inline-end-width" => Property::BorderInlineEndWidth,
+            "border-inline-start" => Property::BorderInlineStart,
+            "border-inline-start-color" => Property::BorderInlineStartColor,
+            "border-inline-start-style" => Property::BorderInlineStartStyle,
+            "border-inline-start-width" => Property::BorderInlineStartWidth,
+            "border-inline-style" => Property::BorderInlineStyle,
+            "border-inline-width" => Property::BorderInlineWidth,
+            "border-left" => Property::BorderLeft,
+            "border-left-color" => Property::BorderLeftColor,
+            "border-left-style" => Property::BorderLeftStyle,
+            "border-left-width" => Property::BorderLeftWidth,
+            "border-radius" => Property::BorderRadius,
+            "border-right" => Property::BorderRight,
+            "border-right-color" => Property::BorderRightColor,
+            "border-right-style" => Property::BorderRightStyle,
+            "border-right-width" => Property::BorderRightWidth,
+            "border-spacing" => Property::BorderSpacing,
+            "border-start-end-radius" => Property::BorderStartEndRadius,
+            "border-start-start-radius" => Property::BorderStartStartRadius,
+            "border-style" => Property::BorderStyle,
+            "border-top" => Property::BorderTop,
+            "border-top-color" => Property::BorderTopColor,
+            "border-top-left-radius" => Property::BorderTopLeftRadius,
+            "border-top-right-radius" => Property::BorderTopRightRadius,
+            "border-top-style" => Property::BorderTopStyle,
+            "border-top-width" => Property::BorderTopWidth,
+            "border-width" => Property::BorderWidth,
+            "bottom" => Property::Bottom,
+            "box-decoration-break" => Property::BoxDecorationBreak,
+            "box-shadow" => Property::BoxShadow,
+            "box-sizing" => Property::BoxSizing,
+            "break-after" => Property::BreakAfter,
+            "break-before" => Property::BreakBefore,
+            "break-inside" => Property::BreakInside,
+            "caption-side" => Property::CaptionSide,
+            "caret-color" => Property::CaretColor,
+            "clear" => Property::Clear,
+            "clip" => Property::Clip,
+            "clip-path" => Property::ClipPath,
+            "clip-rule" => Property::ClipRule,
+            "color" => Property::Color,
+            "color-interpolation" => Property::ColorInterpolation,
+            "color-interpolation-filters" => Property::ColorInterpolationFilters,
+            "color-scheme" => Property::ColorScheme,
+            "column-count" => Property::ColumnCount,
+            "column-fill" => Property::ColumnFill,
+            "column-gap" => Property::ColumnGap,
+            "column-rule" => Property::ColumnRule,
+            "column-rule-color" => Property::ColumnRuleColor,
+            "column-rule-style" => Property::ColumnRuleStyle,
+            "column-rule-width" => Property::ColumnRuleWidth,
+            "column-span" => Property::ColumnSpan,
+            "column-width" => Property::ColumnWidth,
+            "columns" => Property::Columns,
+            "contain" => Property::Contain,
+            "contain-intrinsic-block-size" => Property::ContainIntrinsicBlockSize,
+            "contain-intrinsic-height" => Property::ContainIntrinsicHeight,
+            "contain-intrinsic-inline-size" => Property::ContainIntrinsicInlineSize,
+            "contain-intrinsic-size" => Property::ContainIntrinsicSize,
+            "contain-intrinsic-width" => Property::ContainIntrinsicWidth,
+            "container" => Property::Container,
+            "container-name" => Property::ContainerName,
+            "container-type" => Property::ContainerType,
+            "content" => Property::Content,
+            "content-visibility" => Property::ContentVisibility,
+            "counter-increment" => Property::CounterIncrement,
+            "counter-reset" => Property::CounterReset,
+            "counter-set" => Property::CounterSet,
+            "cursor" => Property::Cursor,
+            "cx" => Property::Cx,
+            "cy" => Property::Cy,
+            "d" => Property::D,
+            "direction" => Property::Direction,
+            "display" => Property::Display,
+            "dominant-baseline" => Property::DominantBaseline,
+            "empty-cells" => Property::EmptyCells,
+            "field-sizing" => Property::FieldSizing,
+            "fill" => Property::Fill,
+            "fill-opacity" => Property::FillOpacity,
+            "fill-rule" => Property::FillRule,
+            "filter" => Property::Filter,
+            "flex" => Property::Flex,
+            "flex-basis" => Property::FlexBasis,
+            "flex-direction" => Property::FlexDirection,
+            "flex-flow" => Property::FlexFlow,
+            "flex-grow" => Property::FlexGrow,
+            "flex-shrink" => Property::FlexShrink,
+            "flex-wrap" => Property::FlexWrap,
+            "float" => Property::Float,
+            "flood-color" => Property::FloodColor,
+            "flood-opacity" => Property::FloodOpacity,
+            "font" => Property::Font,
+            "font-family" => Property::FontFamily,
+            "font-feature-settings" => Property::FontFeatureSettings,
+            "font-kerning" => Property::FontKerning,
+            "font-language-override" => Property::FontLanguageOverride,
+            "font-optical-sizing" => Property::FontOpticalSizing,
+            "font-palette" => Property::FontPalette,
+            "font-size" => Property::FontSize,
+            "font-size-adjust" => Property::FontSizeAdjust,
+            "font-stretch" => Property::FontStretch,
+            "font-style" => Property::FontStyle,
+            "font-synthesis" => Property::FontSynthesis,
+            "font-synthesis-position" => Property::FontSynthesisPosition,
+            "font-synthesis-small-caps" => Property::FontSynthesisSmallCaps,
+            "font-synthesis-style" => Property::FontSynthesisStyle,
+            "font-synthesis-weight" => Property::FontSynthesisWeight,
+            "font-variant" => Property::FontVariant,
+            "font-variant-alternates" => Property::FontVariantAlternates,
+            "font-variant-caps" => Property::FontVariantCaps,
+            "font-variant-east-asian" => Property::FontVariantEastAsian,
+            "font-variant-emoji" => Property::FontVariantEmoji,
+            "font-variant-ligatures" => Property::FontVariantLigatures,
+            "font-variant-numeric" => Property::FontVariantNumeric,
+            "font-variant-position" => Property::FontVariantPosition,
+            "font-variation-settings" => Property::FontVariationSettings,
+            "font-weight" => Property::FontWeight,
+            "forced-color-adjust" => Property::ForcedColorAdjust,
+            "gap" => Property::Gap,
+            "grid" => Property::Grid,
+            "grid-area" => Property::GridArea,
+            "grid-auto-columns" => Property::GridAutoColumns,
+            "grid-auto-flow" => Property::GridAutoFlow,
+            "grid-auto-rows" => Property::GridAutoRows,
+            "grid-column" => Property::GridColumn,
+            "grid-column-end" => Property::GridColumnEnd,
+            "grid-column-start" => Property::GridColumnStart,
+            "grid-row" => Property::GridRow,
+            "grid-row-end" => Property::GridRowEnd,
+            "grid-row-start" => Property::GridRowStart,
+            "grid-template" => Property::GridTemplate,
+            "grid-template-areas" => Property::GridTemplateAreas,
+            "grid-template-columns" => Property::GridTemplateColumns,
+            "grid-template-rows" => Property::GridTemplateRows,
+            "hanging-punctuation" => Property::HangingPunctuation,
+            "height" => Property::Height,
+            "hyphenate-character" => Property::HyphenateCharacter,
+            "hyphens" => Property::Hyphens,
+            "image-orientation" => Property::ImageOrientation,
+            "image-rendering" => Property::ImageRendering,
+            "initial-letter" => Property::InitialLetter,
+            "inline-size" => Property::InlineSize,
+            "inset" => Property::Inset,
+            "inset-block" => Property::InsetBlock,
+            "inset-block-end" => Property::InsetBlockEnd,
+            "inset-block-start" => Property::InsetBlockStart,
+            "inset-inline" => Property::InsetInline,
+            "inset-inline-end" => Property::InsetInlineEnd,
+            "inset-inline-start" => Property::InsetInlineStart,
+            "interpolate-size" => Property::InterpolateSize,
+            "isolation" => Property::Isolation,
+            "justify-content" => Property::JustifyContent,
+            "justify-items" => Property::JustifyItems,
+            "justify-self" => Property::JustifySelf,
+            "left" => Property::Left,
+            "letter-spacing" => Property::LetterSpacing,
+            "lighting-color" => Property::LightingColor,
+            "line-break" => Property::LineBreak,
+            "line-clamp" => Property::LineClamp,
+            "line-height" => Property::LineHeight,
+            "list-style" => Property::ListStyle,
+            "list-style-image" => Property::ListStyleImage,
+            "list-style-position" => Property::ListStylePosition,
+            "list-style-type" => Property::ListStyleType,
+            "margin" => Property::Margin,
+            "margin-block" => Property::MarginBlock,
+            "margin-block-end" => Property::MarginBlockEnd,
+            "margin-block-start" => Property::MarginBlockStart,
+            "margin-bottom" => Property::MarginBottom,
+            "margin-inline" => Property::MarginInline,
+            "margin-inline-end" => Property::MarginInlineEnd,
+            "margin-inline-start" => Property::MarginInlineStart,
+            "margin-left" => Property::MarginLeft,
+            "margin-right" => Property::MarginRight,
+            "margin-top" => Property::MarginTop,
+            "marker" => Property::Marker,
+            "marker-end" => Property::MarkerEnd,
+            "marker-mid" => Property::MarkerMid,
+            "marker-start" => Property::MarkerStart,
+            "mask" => Property::Mask,
+            "mask-border" => Property::MaskBorder,
+            "mask-border-mode" => Property::MaskBorderMode,
+            "mask-border-outset" => Property::MaskBorderOutset,
+            "mask-border-repeat" => Property::MaskBorderRepeat,
+            "mask-border-slice" => Property::MaskBorderSlice,
+            "mask-border-source" => Property::MaskBorderSource,
+            "mask-border-width" => Property::MaskBorderWidth,
+            "mask-clip" => Property::MaskClip,
+            "mask-composite" => Property::MaskComposite,
+            "mask-image" => Property::MaskImage,
+            "mask-mode" => Property::MaskMode,
+            "mask-origin" => Property::MaskOrigin,
+            "mask-position" => Property::MaskPosition,
+            "mask-repeat" => Property::MaskRepeat,
+            "mask-size" => Property::MaskSize,
+            "mask-type" => Property::MaskType,
+            "math-depth" => Property::MathDepth,
+            "math-style" => Property::MathStyle,
+            "max-block-size" => Property::MaxBlockSize,
+            "max-height" => Property::MaxHeight,
+            "max-inline-size" => Property::MaxInlineSize,
+            "max-width" => Property::MaxWidth,
+            "min-block-size" => Property::MinBlockSize,
+            "min-height" => Property::MinHeight,
+            "min-inline-size" => Property::MinInlineSize,
+            "min-width" => Property::MinWidth,
+            "mix-blend-mode" => Property::MixBlendMode,
+            "object-fit" => Property::ObjectFit,
+            "object-position" => Property::ObjectPosition,
+            "offset" => Property::Offset,
+            "offset-anchor" => Property::OffsetAnchor,
+            "offset-distance" => Property::OffsetDistance,
+            "offset-path" => Property::OffsetPath,
+            "offset-position" => Property::OffsetPosition,
+            "offset-rotate" => Property::OffsetRotate,
+            "opacity" => Property::Opacity,
+            "order" => Property::Order,
+            "orphans" => Property::Orphans,
+            "outline" => Property::Outline,
+            "outline-color" => Property::OutlineColor,
+            "outline-offset" => Property::OutlineOffset,
+            "outline-style" => Property::OutlineStyle,
+            "outline-width" => Property::OutlineWidth,
+            "overflow" => Property::Overflow,
+            "overflow-anchor" => Property::OverflowAnchor,
+            "overflow-block" => Property::OverflowBlock,
+            "overflow-clip-margin" => Property::OverflowClipMargin,
+            "overflow-inline" => Property::OverflowInline,
+            "overflow-wrap" => Property::OverflowWrap,
+            "overflow-x" => Property::OverflowX,
+            "overflow-y" => Property::OverflowY,
+            "overscroll-behavior" => Property::OverscrollBehavior,
+            "overscroll-behavior-block" => Property::OverscrollBehaviorBlock,
+            "overscroll-behavior-inline" => Property::OverscrollBehaviorInline,
+            "overscroll-behavior-x" => Property::OverscrollBehaviorX,
+            "overscroll-behavior-y" => Property::OverscrollBehaviorY,
+            "padding" => Property::Padding,
+            "padding-block" => Property::PaddingBlock,
+            "padding-block-end" => Property::PaddingBlockEnd,
+            "padding-block-start" => Property::PaddingBlockStart,
+            "padding-bottom" => Property::PaddingBottom,
+            "padding-inline" => Property::PaddingInline,
+            "padding-inline-end" => Property::PaddingInlineEnd,
+            "padding-inline-start" => Property::PaddingInlineStart,
+            "padding-left" => Property::PaddingLeft,
+            "padding-right" => Property::PaddingRight,
+            "padding-top" => Property::PaddingTop,
+            "page" => Property::Page,
+            "page-break-after" => Property::PageBreakAfter,
+            "page-break-before" => Property::PageBreakBefore,
+            "page-break-inside" => Property::PageBreakInside,
+            "paint-order" => Property::PaintOrder,
+            "perspective" => Property::Perspective,
+            "perspective-origin" => Property::PerspectiveOrigin,
+            "place-content" => Property::PlaceContent,
+            "place-items" => Property::PlaceItems,
+            "place-self" => Property::PlaceSelf,
+            "pointer-events" => Property::PointerEvents,
+            "position" => Property::Position,
+            "position-anchor" => Property::PositionAnchor,
+            "position-area" => Property::PositionArea,
+            "position-try" => Property::PositionTry,
+            "position-try-fallbacks" => Property::PositionTryFallbacks,
+            "position-try-order" => Property::PositionTryOrder,
+            "position-visibility" => Property::PositionVisibility,
+            "print-color-adjust" => Property::PrintColorAdjust,
+            "quotes" => Property::Quotes,
+            "r" => Property::R,
+            "resize" => Property::Resize,
+            "right" => Property::Right,
+            "rotate" => Property::Rotate,
+            "row-gap" => Property::RowGap,
+            "ruby-align" => Property::RubyAlign,
+            "ruby-position" => Property::RubyPosition,
+            "rx" => Property::Rx,
+            "ry" => Property::Ry,
+            "scale" => Property::Scale,
+            "scroll-behavior" => Property::ScrollBehavior,
+            "scroll-margin" => Property::ScrollMargin,
+            "scroll-margin-block" => Property::ScrollMarginBlock,
+            "scroll-margin-block-end" => Property::ScrollMarginBlockEnd,
+            "scroll-margin-block-start" => Property::ScrollMarginBlockStart,
+            "scroll-margin-bottom" => Property::ScrollMarginBottom,
+            "scroll-margin-inline" => Property::ScrollMarginInline,
+            "scroll-margin-inline-end" => Property::ScrollMarginInlineEnd,
+            "scroll-margin-inline-start" => Property::ScrollMarginInlineStart,
+            "scroll-margin-left" => Property::ScrollMarginLeft,
+            "scroll-margin-right" => Property::ScrollMarginRight,
+            "scroll-margin-top" => Property::ScrollMarginTop,
+            "scroll-padding" => Property::ScrollPadding,
+            "scroll-padding-block" => Property::ScrollPaddingBlock,
+            "scroll-padding-block-end" => Property::ScrollPaddingBlockEnd,
+            "scroll-padding-block-start" => Property::ScrollPaddingBlockStart,
+            "scroll-padding-bottom" => Property::ScrollPaddingBottom,
+            "scroll-padding-inline" => Property::ScrollPaddingInline,
+            "scroll-padding-inline-end" => Property::ScrollPaddingInlineEnd,
+            "scroll-padding-inline-start" => Property::ScrollPaddingInlineStart,
+            "scroll-padding-left" => Property::ScrollPaddingLeft,
+            "scroll-padding-right" => Property::ScrollPaddingRight,
+            "scroll-padding-top" => Property::ScrollPaddingTop,
+            "scroll-snap-align" => Property::ScrollSnapAlign,
+            "scroll-snap-stop" => Property::ScrollSnapStop,
+            "scroll-snap-type" => Property::ScrollSnapType,
+            "scroll-timeline" => Property::ScrollTimeline,
+            "scroll-timeline-axis" => Property::ScrollTimelineAxis,
+            "scroll-timeline-name" => Property::ScrollTimelineName,
+            "scrollbar-color" => Property::ScrollbarColor,
+            "scrollbar-gutter" => Property::ScrollbarGutter,
+            "scrollbar-width" => Property::ScrollbarWidth,
+            "shape-image-threshold" => Property::ShapeImageThreshold,
+            "shape-margin" => Property::ShapeMargin,
+            "shape-outside" => Property::ShapeOutside,
+            "shape-rendering" => Property::ShapeRendering,
+            "stop-color" => Property::StopColor,
+            "stop-opacity" => Property::StopOpacity,
+            "stroke" => Property::Stroke,
+            "stroke-dasharray" => Property::StrokeDasharray,
+            "stroke-dashoffset" => Property::StrokeDashoffset,
+            "stroke-linecap" => Property::StrokeLinecap,
+            "stroke-linejoin" => Property::StrokeLinejoin,
+            "stroke-miterlimit" => Property::StrokeMiterlimit,
+            "stroke-opacity" => Property::StrokeOpacity,
+            "stroke-width" => Property::StrokeWidth,
+            "tab-size" => Property::TabSize,
+            "table-layout" => Property::TableLayout,
+            "text-align" => Property::TextAlign,
+            "text-align-last" => Property::TextAlignLast,
+            "text-anchor" => Property::TextAnchor,
+            "text-autospace" => Property::TextAutospace,
+            "text-box" => Property::TextBox,
+            "text-box-edge" => Property::TextBoxEdge,
+            "text-box-trim" => Property::TextBoxTrim,
+            "text-combine-upright" => Property::TextCombineUpright,
+            "text-decoration" => Property::TextDecoration,
+            "text-decoration-color" => Property::TextDecorationColor,
+            "text-decoration-line" => Property::TextDecorationLine,
+            "text-decoration-skip-ink" => Property::TextDecorationSkipInk,
+            "text-decoration-style" => Property::TextDecorationStyle,
+            "text-decoration-thickness" => Property::TextDecorationThickness,
+            "text-emphasis" => Property::TextEmphasis,
+            "text-emphasis-color" => Property::TextEmphasisColor,
+            "text-emphasis-position" => Property::TextEmphasisPosition,
+            "text-emphasis-style" => Property::TextEmphasisStyle,
+            "text-indent" => Property::TextIndent,
+            "text-justify" => Property::TextJustify,
+            "text-orientation" => Property::TextOrientation,
+            "text-overflow" => Property::TextOverflow,
+            "text-rendering" => Property::TextRendering,
+            "text-shadow" => Property::TextShadow,
+            "text-size-adjust" => Property::TextSizeAdjust,
+            "text-spacing-trim" => Property::TextSpacingTrim,
+            "text-transform" => Property::TextTransform,
+            "text-underline-offset" => Property::TextUnderlineOffset,
+            "text-underline-position" => Property::TextUnderlinePosition,
+            "text-wrap" => Property::TextWrap,
+            "text-wrap-mode" => Property::TextWrapMode,
+            "text-wrap-style" => Property::TextWrapStyle,
+            "timeline-scope" => Property::TimelineScope,
+            "top" => Property::Top,
+            "touch-action" => Property::TouchAction,
+            "transform" => Property::Transform,
+            "transform-box" => Property::TransformBox,
+            "transform-origin" => Property::TransformOrigin,
+            "transform-style" => Property::TransformStyle,
+            "transition" => Property::Transition,
+            "transition-behavior" => Property::TransitionBehavior,
+            "transition-delay" => Property::TransitionDelay,
+            "transition-duration" => Property::TransitionDuration,
+            "transition-property" => Property::TransitionProperty,
+            "transition-timing-function" => Property::TransitionTimingFunction,
+            "translate" => Property::Translate,
+            "unicode-bidi" => Property::UnicodeBidi,
+            "user-select" => Property::UserSelect,
+            "vector-effect" => Property::VectorEffect,
+            "vertical-align" => Property::VerticalAlign,
+            "view-timeline" => Property::ViewTimeline,
+            "view-timeline-axis" => Property::ViewTimelineAxis,
+            "view-timeline-inset" => Property::ViewTimelineInset,
+            "view-timeline-name" => Property::ViewTimelineName,
+            "view-transition-class" => Property::ViewTransitionClass,
+            "view-transition-name" => Property::ViewTransitionName,
+            "visibility" => Property::Visibility,
+            "white-space" => Property::WhiteSpace,
+            "white-space-collapse" => Property::WhiteSpaceCollapse,
+            "widows" => Property::Widows,
+            "width" => Property::Width,
+            "will-change" => Property::WillChange,
+            "word-break" => Property::WordBreak,
+            "word-spacing" => Property::WordSpacing,
+            "word-wrap" => Property::WordWrap,
+            "writing-mode" => Property::WritingMode,
+            "x" => Property::X,
+            "y" => Property::Y,
+            "z-index" => Property::ZIndex,
+            "zoom" => Property::Zoom,
+            _ => Property::Custom(name),
+        }
+    }
+
+    /// Returns the name of the property, without allocating.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Property::AccentColor => "accent-color",
+            Property::AlignContent => "align-content",
+            Property::AlignItems => "align-items",
+            Property::AlignSelf => "align-self",
+            Property::AlignmentBaseline => "alignment-baseline",
+            Property::All => "all",
+            Property::AnchorName => "anchor-name",
+            Property::AnchorScope => "anchor-scope",
+            Property::Animation => "animation",
+            Property::AnimationComposition => "animation-composition",
+            Property::AnimationDelay => "animation-delay",
+            Property::AnimationDirection => "animation-direction",
+            Property::AnimationDuration => "animation-duration",
+            Property::AnimationFillMode => "animation-fill-mode",
+            Property::AnimationIterationCount => "animation-iteration-count",
+            Property::AnimationName => "animation-name",
+            Property::AnimationPlayState => "animation-play-state",
+            Property::AnimationRange => "animation-range",
+            Property::AnimationRangeEnd => "animation-range-end",
+            Property::AnimationRangeStart => "animation-range-start",
+            Property::AnimationTimeline => "animation-timeline",
+            Property::AnimationTimingFunction => "animation-timing-function",
+            Property::Appearance => "appearance",
+            Property::AspectRatio => "aspect-ratio",
+            Property::BackdropFilter => "backdrop-filter",
+            Property::BackfaceVisibility => "backface-visibility",
+            Property::Background => "background",
+            Property::BackgroundAttachment => "background-attachment",
+            Property::BackgroundBlendMode => "background-blend-mode",
+            Property::BackgroundClip => "background-clip",
+            Property::BackgroundColor => "background-color",
+            Property::BackgroundImage => "background-image",
+            Property::BackgroundOrigin => "background-origin",
+            Property::BackgroundPosition => "background-position",
+            Property::BackgroundPositionX => "background-position-x",
+            Property::BackgroundPositionY => "background-position-y",
+            Property::BackgroundRepeat => "background-repeat",
+            Property::BackgroundSize => "background-size",
+            Property::BaselineShift => "baseline-shift",
+            Property::BlockSize => "block-size",
+            Property::Border => "border",
+            Property::BorderBlock => "border-block",
+            Property::BorderBlockColor => "border-block-color",
+            Property::BorderBlockEnd => "border-block-end",
+            Property::BorderBlockEndColor => "border-block-end-color",
+            Property::BorderBlockEndStyle => "border-block-end-style",
+            Property::BorderBlockEndWidth => "border-block-end-width",
+            Property::BorderBlockStart => "border-block-start",
+            Property::BorderBlockStartColor => "border-block-start-color",
+            Property::BorderBlockStartStyle => "border-block-start-style",
+            Property::BorderBlockStartWidth => "border-block-start-width",
+            Property::BorderBlockStyle => "border-block-style",
+            Property::BorderBlockWidth => "border-block-width",
+            Property::BorderBottom => "border-bottom",
+            Property::BorderBottomColor => "border-bottom-color",
+            Property::BorderBottomLeftRadius => "border-bottom-left-radius",
+            Property::BorderBottomRightRadius => "border-bottom-right-radius",
+            Property::BorderBottomStyle => "border-bottom-style",
+            Property::BorderBottomWidth => "border-bottom-width",
+            Property::BorderCollapse => "border-collapse",
+            Property::BorderColor => "border-color",
+            Property::BorderEndEndRadius => "border-end-end-radius",
+            Property::BorderEndStartRadius => "border-end-start-radius",
+            Property::BorderImage => "border-image",
+            Property::BorderImageOutset => "border-image-outset",
+            Property::BorderImageRepeat => "border-image-repeat",
+            Property::BorderImageSlice => "border-image-slice",
+            Property::BorderImageSource => "border-image-source",
+            Property::BorderImageWidth => "border-image-width",
+            Property::BorderInline => "border-inline",
+            Property::BorderInlineColor => "border-inline-color",
+            Property::BorderInlineEnd => "border-inline-end",
+            Property::BorderInlineEndColor => "border-inline-end-color",
+            Property::BorderInlineEndStyle => "border-inline-end-style",
+            Property::BorderInlineEndWidth => "border-inline-end-width",
+            Property::BorderInlineStart => "border-inline-start",
+            Property::BorderInlineStartColor => "border-inline-start-color",
+            Property::BorderInlineStartStyle => "border-inline-start-style",
+            Property::BorderInlineStartWidth => "border-inline-start-width",
+            Property::BorderInlineStyle => "border-inline-style",
+            Property::BorderInlineWidth => "border-inline-width",
+            Property::BorderLeft => "border-left",
+            Property::BorderLeftColor => "border-left-color",
+            Property::BorderLeftStyle => "border-left-style",
+            Property::BorderLeftWidth => "border-left-width",
+            Property::BorderRadius => "border-radius",
+            Property::BorderRight => "border-right",
+            Property::BorderRightColor => "border-right-color",
+            Property::BorderRightStyle => "border-right-style",
+            Property::BorderRightWidth => "border-right-width",
+            Property::BorderSpacing => "border-spacing",
+            Property::BorderStartEndRadius => "border-start-end-radius",
+            Property::BorderStartStartRadius => "border-start-start-radius",
+            Property::BorderStyle => "border-style",
+            Property::BorderTop => "border-top",
+            Property::BorderTopColor => "border-top-color",
+            Property::BorderTopLeftRadius => "border-top-left-radius",
+            Property::BorderTopRightRadius => "border-top-right-radius",
+            Property::BorderTopStyle => "border-top-style",
+            Property::BorderTopWidth => "border-top-width",
+            Property::BorderWidth => "border-width",
+            Property::Bottom => "bottom",
+            Property::BoxDecorationBreak => "box-decoration-break",
+            Property::BoxShadow => "box-shadow",
+            Property::BoxSizing => "box-sizing",
+            Property::BreakAfter => "break-after",
+            Property::BreakBefore => "break-before",
+            Property::BreakInside => "break-inside",
+            Property::CaptionSide => "caption-side",
+            Property::CaretColor => "caret-color",
+            Property::Clear => "clear",
+            Property::Clip => "clip",
+            Property::ClipPath => "clip-path",
+            Property::ClipRule => "clip-rule",
+            Property::Color => "color",
+            Property::ColorInterpolation => "color-interpolation",
+            Property::ColorInterpolationFilters => "color-interpolation-filters",
+            Property::ColorScheme => "color-scheme",
+            Property::ColumnCount => "column-count",
+            Property::ColumnFill => "column-fill",
+            Property::ColumnGap => "column-gap",
+            Property::ColumnRule => "column-rule",
+            Property::ColumnRuleColor => "column-rule-color",
+            Property::ColumnRuleStyle => "column-rule-style",
+            Property::ColumnRuleWidth => "column-rule-width",
+            Property::ColumnSpan => "column-span",
+            Property::ColumnWidth => "column-width",
+            Property::Columns => "columns",
+            Property::Contain => "contain",
+            Property::ContainIntrinsicBlockSize => "contain-intrinsic-block-size",
+            Property::ContainIntrinsicHeight => "contain-intrinsic-height",
+            Property::ContainIntrinsicInlineSize => "contain-intrinsic-inline-size",
+            Property::ContainIntrinsicSize => "contain-intrinsic-size",
+            Property::ContainIntrinsicWidth => "contain-intrinsic-width",
+            Property::Container => "container",
+            Property::ContainerName => "container-name",
+            Property::ContainerType => "container-type",
+            Property::Content => "content",
+            Property::ContentVisibility => "content-visibility",
+            Property::CounterIncrement => "counter-increment",
+            Property::CounterReset => "counter-reset",
+            Property::CounterSet => "counter-set",
+            Property::Cursor => "cursor",
+            Property::Cx => "cx",
+            Property::Cy => "cy",
+            Property::D => "d",
+            Property::Direction => "direction",
+            Property::Display => "display",
+            Property::DominantBaseline => "dominant-baseline",
+            Property::EmptyCells => "empty-cells",
+            Property::FieldSizing => "field-sizing",
+            Property::Fill => "fill",
+            Property::FillOpacity => "fill-opacity",
+            Property::FillRule => "fill-rule",
+            Property::Filter => "filter",
+            Property::Flex => "flex",
+            Property::FlexBasis => "flex-basis",
+            Property::FlexDirection => "flex-direction",
+            Property::FlexFlow => "flex-flow",
+            Property::FlexGrow => "flex-grow",
+            Property::FlexShrink => "flex-shrink",
+            Property::FlexWrap => "flex-wrap",
+            Property::Float => "float",
+            Property::FloodColor => "flood-color",
+            Property::FloodOpacity => "flood-opacity",
+            Property::Font => "font",
+            Property::FontFamily => "font-family",
+            Property::FontFeatureSettings => "font-feature-settings",
+            Property::FontKerning => "font-kerning",
+            Property::FontLanguageOverride => "font-language-override",
+            Property::FontOpticalSizing => "font-optical-sizing",
+            Property::FontPalette => "font-palette",
+            Property::FontSize => "font-size",
+            Property::FontSizeAdjust => "font-size-adjust",
+            Property::FontStretch => "font-stretch",
+            Property::FontStyle => "font-style",
+            Property::FontSynthesis => "font-synthesis",
+            Property::FontSynthesisPosition => "font-synthesis-position",
+            Property::FontSynthesisSmallCaps => "font-synthesis-small-caps",
+            Property::FontSynthesisStyle => "font-synthesis-style",
+            Property::FontSynthesisWeight => "font-synthesis-weight",
+            Property::FontVariant => "font-variant",
+            Property::FontVariantAlternates => "font-variant-alternates",
+            Property::FontVariantCaps => "font-variant-caps",
+            Property::FontVariantEastAsian => "font-variant-east-asian",
+            Property::FontVariantEmoji => "font-variant-emoji",
+            Property::FontVariantLigatures => "font-variant-ligatures",
+            Property::FontVariantNumeric => "font-variant-numeric",
+            Property::FontVariantPosition => "font-variant-position",
+            Property::FontVariationSettings => "font-variation-settings",
+            Property::FontWeight => "font-weight",
+            Property::ForcedColorAdjust => "forced-color-adjust",
+            Property::Gap => "gap",
+            Property::Grid => "grid",
+            Property::GridArea => "grid-area",
+            Property::GridAutoColumns => "grid-auto-columns",
+            Property::GridAutoFlow => "grid-auto-flow",
+            Property::GridAutoRows => "grid-auto-rows",
+            Property::GridColumn => "grid-column",
+            Property::GridColumnEnd => "grid-column-end",
+            Property::GridColumnStart => "grid-column-start",
+            Property::GridRow => "grid-row",
+            Property::GridRowEnd => "grid-row-end",
+            Property::GridRowStart => "grid-row-start",
+            Property::GridTemplate => "grid-template",
+            Property::GridTemplateAreas => "grid-template-areas",
+            Property::GridTemplateColumns => "grid-template-columns",
+            Property::GridTemplateRows => "grid-template-rows",
+            Property::HangingPunctuation => "hanging-punctuation",
+            Property::Height => "height",
+            Property::HyphenateCharacter => "hyphenate-character",
+            Property::Hyphens => "hyphens",
+            Property::ImageOrientation => "image-orientation",
+            Property::ImageRendering => "image-rendering",
+            Property::InitialLetter => "initial-letter",
+            Property::InlineSize => "inline-size",
+            Property::Inset => "inset",
+            Property::InsetBlock => "inset-block",
+            Property::InsetBlockEnd => "inset-block-end",
+            Property::InsetBlockStart => "inset-block-start",
+            Property::InsetInline => "inset-inline",
+            Property::InsetInlineEnd => "inset-inline-end",
+            Property::InsetInlineStart => "inset-inline-start",
+            Property::InterpolateSize => "interpolate-size",
+            Property::Isolation => "isolation",
+            Property::JustifyContent => "justify-content",
+            Property::JustifyItems => "justify-items",
+            Property::JustifySelf => "justify-self",
+            Property::Left => "left",
+            Property::LetterSpacing => "letter-spacing",
+            Property::LightingColor => "lighting-color",
+            Property::LineBreak => "line-break",
+            Property::LineClamp => "line-clamp",
+            Property::LineHeight => "line-height",
+            Property::ListStyle => "list-style",
+            Property::ListStyleImage => "list-style-image",
+            Property::ListStylePosition => "list-style-position",
+            Property::ListStyleType => "list-style-type",
+            Property::Margin => "margin",
+            Property::MarginBlock => "margin-block",
+            Property::MarginBlockEnd => "margin-block-end",
+            Property::MarginBlockStart => "margin-block-start",
+            Property::MarginBottom => "margin-bottom",
+            Property::MarginInline => "margin-inline",
+            Property::MarginInlineEnd => "margin-inline-end",
+            Property::MarginInlineStart => "margin-inline-start",
+            Property::MarginLeft => "margin-left",
+            Property::MarginRight => "margin-right",
+            Property::MarginTop => "margin-top",
+            Property::Marker => "marker",
+            Property::MarkerEnd => "marker-end",
+            Property::MarkerMid => "marker-mid",
+            Property::MarkerStart => "marker-start",
+            Property::Mask => "mask",
+            Property::MaskBorder => "mask-border",
+            Property::MaskBorderMode => "mask-border-mode",
+            Property::MaskBorderOutset => "mask-border-outset",
+            Property::MaskBorderRepeat => "mask-border-repeat",
+            Property::MaskBorderSlice => "mask-border-slice",
+            Property::MaskBorderSource => "mask-border-source",
+            Property::MaskBorderWidth => "mask-border-width",
+            Property::MaskClip => "mask-clip",
+            Property::MaskComposite => "mask-composite",
+            Property::MaskImage => "mask-image",
+            Property::MaskMode => "mask-mode",
+            Property::MaskOrigin => "mask-origin",
+            Property::MaskPosition => "mask-position",
+            Property::MaskRepeat => "mask-repeat",
+            Property::MaskSize => "mask-size",
+            Property::MaskType => "mask-type",
+            Property::MathDepth => "math-depth",
+            Property::MathStyle => "math-style",
+            Property::MaxBlockSize => "max-block-size",
+            Property::MaxHeight => "max-height",
+            Property::MaxInlineSize => "max-inline-size",
+            Property::MaxWidth => "max-width",
+            Property::MinBlockSize => "min-block-size",
+            Property::MinHeight => "min-height",
+            Property::MinInlineSize => "min-inline-size",
+            Property::MinWidth => "min-width",
+            Property::MixBlendMode => "mix-blend-mode",
+            Property::ObjectFit => "object-fit",
+            Property::ObjectPosition => "object-position",
+            Property::Offset => "offset",
+            Property::OffsetAnchor => "offset-anchor",
+            Property::OffsetDistance => "offset-distance",
+            Property::OffsetPath => "offset-path",
+            Property::OffsetPosition => "offset-position",
+            Property::OffsetRotate => "offset-rotate",
+            Property::Opacity => "opacity",
+            Property::Order => "order",
+            Property::Orphans => "orphans",
+            Property::Outline => "outline",
+            Property::OutlineColor => "outline-color",
+            Property::OutlineOffset => "outline-offset",
+            Property::OutlineStyle => "outline-style",
+            Property::OutlineWidth => "outline-width",
+            Property::Overflow => "overflow",
+            Property::OverflowAnchor => "overflow-anchor",
+            Property::OverflowBlock => "overflow-block",
+            Property::OverflowClipMargin => "overflow-clip-margin",
+            Property::OverflowInline => "overflow-inline",
+            Property::OverflowWrap => "overflow-wrap",
+            Property::OverflowX => "overflow-x",
+            Property::OverflowY => "overflow-y",
+            Property::OverscrollBehavior => "overscroll-behavior",
+            Property::OverscrollBehaviorBlock => "overscroll-behavior-block",
+            Property::OverscrollBehaviorInline => "overscroll-behavior-inline",
+            Property::OverscrollBehaviorX => "overscroll-behavior-x",
+            Property::OverscrollBehaviorY => "overscroll-behavior-y",
+            Property::Padding => "padding",
+            Property::PaddingBlock => "padding-block",
+            Property::PaddingBlockEnd => "padding-block-end",
+            Property::PaddingBlockStart => "padding-block-start",
+            Property::PaddingBottom => "padding-bottom",
+            Property::PaddingInline => "padding-inline",
+            Property::PaddingInlineEnd => "padding-inline-end",
+            Property::PaddingInlineStart => "padding-inline-start",
+            Property::PaddingLeft => "padding-left",
+            Property::PaddingRight => "padding-right",
+            Property::PaddingTop => "padding-top",
+            Property::Page => "page",
+            Property::PageBreakAfter => "page-break-after",
+            Property::PageBreakBefore => "page-break-before",
+            Property::PageBreakInside => "page-break-inside",
+            Property::PaintOrder => "paint-order",
+            Property::Perspective => "perspective",
+            Property::PerspectiveOrigin => "perspective-origin",
+            Property::PlaceContent => "place-content",
+            Property::PlaceItems => "place-items",
+            Property::PlaceSelf => "place-self",
+            Property::PointerEvents => "pointer-events",
+            Property::Position => "position",
+            Property::PositionAnchor => "position-anchor",
+            Property::PositionArea => "position-area",
+            Property::PositionTry => "position-try",
+            Property::PositionTryFallbacks => "position-try-fallbacks",
+            Property::PositionTryOrder => "position-try-order",
+            Property::PositionVisibility => "position-visibility",
+            Property::PrintColorAdjust => "print-color-adjust",
+            Property::Quotes => "quotes",
+            Property::R => "r",
+            Property::Resize => "resize",
+            Property::Right => "right",
+            Property::Rotate => "rotate",
+            Property::RowGap => "row-gap",
+            Property::RubyAlign => "ruby-align",
+            Property::RubyPosition => "ruby-position",
+            Property::Rx => "rx",
+            Property::Ry => "ry",
+            Property::Scale => "scale",
+            Property::ScrollBehavior => "scroll-behavior",
+            Property::ScrollMargin => "scroll-margin",
+            Property::ScrollMarginBlock => "scroll-margin-block",
+            Property::ScrollMarginBlockEnd => "scroll-margin-block-end",
+            Property::ScrollMarginBlockStart => "scroll-margin-block-start",
+            Property::ScrollMarginBottom => "scroll-margin-bottom",
+            Property::ScrollMarginInline => "scroll-margin-inline",
+            Property::ScrollMarginInlineEnd => "scroll-margin-inline-end",
+            Property::ScrollMarginInlineStart => "scroll-margin-inline-start",
+            Property::ScrollMarginLeft => "scroll-margin-left",
+            Property::ScrollMarginRight => "scroll-margin-right",
+            Property::ScrollMarginTop => "scroll-margin-top",
+            Property::ScrollPadding => "scroll-padding",
+            Property::ScrollPaddingBlock => "scroll-padding-block",
+            Property::ScrollPaddingBlockEnd => "scroll-padding-block-end",
+            Property::ScrollPaddingBlockStart => "scroll-padding-block-start",
+            Property::ScrollPaddingBottom => "scroll-padding-bottom",
+            Property::ScrollPaddingInline => "scroll-padding-inline",
+            Property::ScrollPaddingInlineEnd => "scroll-padding-inline-end",
+            Property::ScrollPaddingInlineStart => "scroll-padding-inline-start",
+            Property::ScrollPaddingLeft => "scroll-padding-left",
+            Property::ScrollPaddingRight => "scroll-padding-right",
+            Property::ScrollPaddingTop => "scroll-padding-top",
+            Property::ScrollSnapAlign => "scroll-snap-align",
+            Property::ScrollSnapStop => "scroll-snap-stop",
+            Property::ScrollSnapType => "scroll-snap-type",
+            Property::ScrollTimeline => "scroll-timeline",
+            Property::ScrollTimelineAxis => "scroll-timeline-axis",
+            Property::ScrollTimelineName => "scroll-timeline-name",
+            Property::ScrollbarColor => "scrollbar-color",
+            Property::ScrollbarGutter => "scrollbar-gutter",
+            Property::ScrollbarWidth => "scrollbar-width",
+            Property::ShapeImageThreshold => "shape-image-threshold",
+            Property::ShapeMargin => "shape-margin",
+            Property::ShapeOutside => "shape-outside",
+            Property::ShapeRendering => "shape-rendering",
+            Property::StopColor => "stop-color",
+            Property::StopOpacity => "stop-opacity",
+            Property::Stroke => "stroke",
+            Property::StrokeDasharray => "stroke-dasharray",
+            Property::StrokeDashoffset => "stroke-dashoffset",
+            Property::StrokeLinecap => "stroke-linecap",
+            Property::StrokeLinejoin => "stroke-linejoin",
+            Property::StrokeMiterlimit => "stroke-miterlimit",
+            Property::StrokeOpacity => "stroke-opacity",
+            Property::StrokeWidth => "stroke-width",
+            Property::TabSize => "tab-size",
+            Property::TableLayout => "table-layout",
+            Property::TextAlign => "text-align",
+            Property::TextAlignLast => "text-align-last",
+            Property::TextAnchor => "text-anchor",
+            Property::TextAutospace => "text-autospace",
+            Property::TextBox => "text-box",
+            Property::TextBoxEdge => "text-box-edge",
+            Property::TextBoxTrim => "text-box-trim",
+            Property::TextCombineUpright => "text-combine-upright",
+            Property::TextDecoration => "text-decoration",
+            Property::TextDecorationColor => "text-decoration-color",
+            Property::TextDecorationLine => "text-decoration-line",
+            Property::TextDecorationSkipInk => "text-decoration-skip-ink",
+            Property::TextDecorationStyle => "text-decoration-style",
+            Property::TextDecorationThickness => "text-decoration-thickness",
+            Property::TextEmphasis => "text-emphasis",
+            Property::TextEmphasisColor => "text-emphasis-color",
+            Property::TextEmphasisPosition => "text-emphasis-position",
+            Property::TextEmphasisStyle => "text-emphasis-style",
+            Property::TextIndent => "text-indent",
+            Property::TextJustify => "text-justify",
+            Property::TextOrientation => "text-orientation",
+            Property::TextOverflow => "text-overflow",
+            Property::TextRendering => "text-rendering",
+            Property::TextShadow => "text-shadow",
+            Property::TextSizeAdjust => "text-size-adjust",
+            Property::TextSpacingTrim => "text-spacing-trim",
+            Property::TextTransform => "text-transform",
+            Property::TextUnderlineOffset => "text-underline-offset",
+            Property::TextUnderlinePosition => "text-underline-position",
+            Property::TextWrap => "text-wrap",
+            Property::TextWrapMode => "text-wrap-mode",
+            Property::TextWrapStyle => "text-wrap-style",
+            Property::TimelineScope => "timeline-scope",
+            Property::Top => "top",
+            Property::TouchAction => "touch-action",
+            Property::Transform => "transform",
+            Property::TransformBox => "transform-box",
+            Property::TransformOrigin => "transform-origin",
+            Property::TransformStyle => "transform-style",
+            Property::Transition => "transition",
+            Property::TransitionBehavior => "transition-behavior",
+            Property::TransitionDelay => "transition-delay",
+            Property::TransitionDuration => "transition-duration",
+            Property::TransitionProperty => "transition-property",
+            Property::TransitionTimingFunction => "transition-timing-function",
+            Property::Translate => "translate",
+            Property::UnicodeBidi => "unicode-bidi",
+            Property::UserSelect => "user-select",
+            Property::VectorEffect => "vector-effect",
+            Property::VerticalAlign => "vertical-align",
+            Property::ViewTimeline => "view-timeline",
+            Property::ViewTimelineAxis => "view-timeline-axis",
+            Property::ViewTimelineInset => "view-timeline-inset",
+            Property::ViewTimelineName => "view-timeline-name",
+            Property::ViewTransitionClass => "view-transition-class",
+            Property::ViewTransitionName => "view-transition-name",
+            Property::Visibility => "visibility",
+            Property::WhiteSpace => "white-space",
+            Property::WhiteSpaceCollapse => "white-space-collapse",
+            Property::Widows => "widows",
+            Property::Width => "width",
+            Property::WillChange => "will-change",
+            Property::WordBreak => "word-break",
+            Property::WordSpacing => "word-spacing",
+            Property::WordWrap => "word-wrap",
+            Property::WritingMode => "writing-mode",
+            Property::X => "x",
+            Property::Y => "y",
+            Property::ZIndex => "z-index",
+            Property::Zoom => "zoom",
+            Property::Custom(name) => name,
+        }
+    }
+
+    /// Returns whether the property is a `Custom` property, i.e. not
+    /// one of the properties defined by the CSS standards.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Property::Custom(_))
+    }
+
+    /// Returns whether the property is a custom property, like `--brand`.
+    pub fn is_custom_property(&self) -> bool {
+        self.as_str().starts_with("--")
+    }
+
+    /// Returns whether the property has a vendor prefix, like
+    /// `-webkit-line-clamp`.
+    pub fn is_vendor_prefixed(&self) -> bool {
+        has_vendor_prefix(self.as_str())
+    }
+
+    /// Returns whether the property is a standard, custom or vendor
+    /// prefixed property, which is what `StyleSheet::validate` accepts.
+    pub fn is_known(&self) -> bool {
+        !self.is_custom() || self.is_custom_property() || self.is_vendor_prefixed()
+    }
+
+    /// Returns the keywords the property accepts, for properties whose
+    /// values are mostly keywords.
+    ///
+    /// The CSS-wide keywords, like `inherit`, are accepted by every
+    /// property and are not included, nor are values that are not
+    /// keywords, like the lengths of `vertical-align`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::styles::Property;
+    ///
+    /// assert!(Property::Position.keywords().unwrap().contains(&"sticky"));
+    /// assert_eq!(Property::Color.keywords(), None);
+    /// ```
+    pub fn keywords(&self) -> Option<&'static [&'static str]> {
+        Some(match self {
+            Property::AlignContent | Property::JustifyContent | Property::PlaceContent => &[
+                "normal",
+                "stretch",
+                "center",
+                "start",
+                "end",
+                "flex-start",
+                "flex-end",
+                "baseline",
+                "first",
+                "last",
+                "safe",
+                "unsafe",
+                "left",
+                "right",
+                "space-between",
+                "space-around",
+                "space-evenly",
+            ],
+            Property::AlignItems | Property::JustifyItems | Property::PlaceItems => &[
+                "normal",
+                "stretch",
+                "center",
+                "start",
+                "end",
+                "flex-start",
+                "flex-end",
+                "self-start",
+                "self-end",
+                "baseline",
+                "first",
+                "last",
+                "safe",
+                "unsafe",
+                "left",
+                "right",
+                "anchor-center",
+                "legacy",
+            ],
+            Property::AlignSelf | Property::JustifySelf | Property::PlaceSelf => &[
+                "normal",
+                "stretch",
+                "center",
+                "start",
+                "end",
+                "flex-start",
+                "flex-end",
+                "self-start",
+                "self-end",
+                "baseline",
+                "first",
+                "last",
+                "safe",
+                "unsafe",
+                "left",
+                "right",
+                "anchor-center",
+                "auto",
+            ],
+            Property::All => &[],
+            Property::BackfaceVisibility => &["visible", "hidden"],
+            Property::BorderBlockEndStyle
+            | Property::BorderBlockStartStyle
+            | Property::BorderBlockStyle
+            | Property::BorderBottomStyle
+            | Property::BorderInlineEndStyle
+            | Property::BorderInlineStartStyle
+            | Property::BorderInlineStyle
+            | Property::BorderLeftStyle
+            | Property::BorderRightStyle
+            | Property::BorderStyle
+            | Property::BorderTopStyle
+            | Property::ColumnRuleStyle => &[
+                "none", "hidden", "dotted", "dashed", "solid", "double", "groove", "ridge",
+                "inset", "outset",
+            ],
+            Property::BorderCollapse => &["collapse", "separate"],
+            Property::BoxDecorationBreak => &["slice", "clone"],
+            Property::BoxSizing => &["content-box", "border-box"],
+            Property::CaptionSide => &["top", "bottom"],
+            Property::Clear => &[
+                "none",
+                "left",
+                "right",
+                "both",
+                "inline-start",
+                "inline-end",
+            ],
+            Property::ClipRule => &["nonzero", "evenodd"],
+            Property::ContentVisibility => &["visible", "auto", "hidden"],
+            Property::Direction => &["ltr", "rtl"],
+            Property::Display => &[
+                "block",
+                "inline",
+                "inline-block",
+                "flex",
+                "inline-flex",
+                "grid",
+                "inline-grid",
+                "flow",
+                "flow-root",
+                "none",
+                "contents",
+                "table",
+                "table-row",
+                "table-cell",
+                "table-column",
+                "table-column-group",
+                "table-header-group",
+                "table-footer-group",
+                "table-row-group",
+                "table-caption",
+                "inline-table",
+                "list-item",
+                "run-in",
+                "ruby",
+                "ruby-base",
+                "ruby-text",
+                "ruby-base-container",
+                "ruby-text-container",
+                "math",
+            ],
+            Property::DominantBaseline => &[
+                "auto",
+                "text-bottom",
+                "alphabetic",
+                "ideographic",
+                "middle",
+                "central",
+                "mathematical",
+                "hanging",
+                "text-top",
+            ],
+            Property::EmptyCells => &["show", "hide"],
+            Property::FieldSizing => &["fixed", "content"],
+            Property::FlexDirection => &["row", "row-reverse", "column", "column-reverse"],
+            Property::FlexWrap => &["nowrap", "wrap", "wrap-reverse"],
+            Property::Float => &["left", "right", "none", "inline-start", "inline-end"],
+            Property::FontKerning => &["auto", "normal", "none"],
+            Property::FontStyle => &["normal", "italic", "oblique"],
+            Property::FontWeight => &["normal", "bold", "bolder", "lighter"],
+            Property::ImageRendering => {
+                &["auto", "smooth", "high-quality", "crisp-edges", "pixelated"]
+            }
+            Property::Isolation => &["auto", "isolate"],
+            Property::MixBlendMode => &[
+                "normal",
+                "multiply",
+                "screen",
+                "overlay",
+                "darken",
+                "lighten",
+                "color-dodge",
+                "color-burn",
+                "hard-light",
+                "soft-light",
+                "difference",
+                "exclusion",
+                "hue",
+                "saturation",
+                "color",
+                "luminosity",
+                "plus-darker",
+                "plus-lighter",
+            ],
+            Property::ObjectFit => &["fill", "contain", "cover", "none", "scale-down"],
+            Property::OutlineStyle => &[
+                "none", "hidden", "dotted", "dashed", "solid", "double", "groove", "ridge",
+                "inset", "outset", "auto",
+            ],
+            Property::Overflow
+            | Property::OverflowBlock
+            | Property::OverflowInline
+            | Property::OverflowX
+            | Property::OverflowY => &["visible", "hidden", "clip", "scroll", "auto", "overlay"],
+            Property::OverflowWrap => &["normal", "break-word", "anywhere"],
+            Property::PointerEvents => &[
+                "auto",
+                "none",
+                "visiblepainted",
+                "visiblefill",
+                "visiblestroke",
+                "visible",
+                "painted",
+                "fill",
+                "stroke",
+                "all",
+                "bounding-box",
+            ],
+            Property::Position => &["static", "relative", "absolute", "fixed", "sticky"],
+            Property::Resize => &["none", "both", "horizontal", "vertical", "block", "inline"],
+            Property::ScrollBehavior => &["auto", "smooth"],
+            Property::TableLayout => &["auto", "fixed"],
+            Property::TextAlign => &[
+                "left",
+                "right",
+                "center",
+                "justify",
+                "start",
+                "end",
+                "match-parent",
+                "justify-all",
+            ],
+            Property::TextAnchor => &["start", "middle", "end"],
+            Property::TextDecorationLine => &[
+                "none",
+                "underline",
+                "overline",
+                "line-through",
+                "blink",
+                "spelling-error",
+                "grammar-error",
+            ],
+            Property::TextDecorationStyle => &["solid", "double", "dotted", "dashed", "wavy"],
+            Property::TextOverflow => &["clip", "ellipsis"],
+            Property::TextTransform => &[
+                "none",
+                "capitalize",
+                "uppercase",
+                "lowercase",
+                "full-width",
+                "full-size-kana",
+            ],
+            Property::TextWrapMode => &["wrap", "nowrap"],
+            Property::TouchAction => &[
+                "auto",
+                "none",
+                "pan-x",
+                "pan-y",
+                "pan-left",
+                "pan-right",
+                "pan-up",
+                "pan-down",
+                "pinch-zoom",
+                "manipulation",
+            ],
+            Property::UnicodeBidi => &[
+                "normal",
+                "embed",
+                "isolate",
+                "bidi-override",
+                "isolate-override",
+                "plaintext",
+            ],
+            Property::UserSelect => &["auto", "text", "none", "contain", "all"],
+            Property::VerticalAlign => &[
+                "baseline",
+                "sub",
+                "super",
+                "text-top",
+                "text-bottom",
+                "middle",
+                "top",
+                "bottom",
+            ],
+            Property::Visibility => &["visible", "hidden", "collapse"],
+            Property::WhiteSpace => &[
+                "normal",
+                "nowrap",
+                "pre",
+                "pre-wrap",
+                "pre-line",
+                "break-spaces",
+                "collapse",
+                "preserve",
+                "preserve-breaks",
+                "preserve-spaces",
+                "wrap",
+            ],
+            Property::WhiteSpaceCollapse => &[
+                "collapse",
+                "discard",
+                "preserve",
+                "preserve-breaks",
+                "preserve-spaces",
+                "break-spaces",
+            ],
+            Property::WordBreak => &[
+                "normal",
+                "break-all",
+                "keep-all",
+                "break-word",
+                "auto-phrase",
+            ],
+            Property::WritingMode => &[
+                "horizontal-tb",
+                "vertical-rl",
+                "vertical-lr",
+                "sideways-rl",
+                "sideways-lr",
+            ],
+            _ => return None,
+        })
+    }
+}
+
+impl Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for Property {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// The keywords that every property accepts.
+const CSS_WIDE_KEYWORDS: &[&str] = &["inherit", "initial", "unset", "revert", "revert-layer"];
+
+/// A problem found by `StyleSheet::validate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub kind: ValidationErrorKind,
+    /// The selector of the rule with the declaration.
+    pub selector: String,
+    /// The at-rules the rule is nested in, from the outermost one,
+    /// like `@media print`.
+    pub at_rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    /// A property that is not a standard, custom or vendor prefixed one.
+    UnknownProperty(String),
+    /// A keyword that the property does not accept.
+    InvalidKeyword { property: String, keyword: String },
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ValidationErrorKind::UnknownProperty(property) => {
+                write!(f, "unknown property `{}`", property)?
+            }
+            ValidationErrorKind::InvalidKeyword { property, keyword } => {
+                write!(f, "invalid keyword `{}` for `{}`", keyword, property)?
+            }
+        }
+        write!(f, " in `{}`", self.selector)?;
+        for at_rule in self.at_rules.iter().rev() {
+            write!(f, " in `{}`", at_rule)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Checks a declaration for an unknown property, or a keyword that
+/// the property does not accept.
+pub(crate) fn check_declaration(property: &str, value: &str) -> Option<ValidationErrorKind> {
+    let known = Property::from(property);
+    if !known.is_known() {
+        return Some(ValidationErrorKind::UnknownProperty(property.to_string()));
+    }
+    let keywords = known.keywords()?;
+    let value = value.trim();
+    let value = value.strip_suffix("!important").unwrap_or(value);
+    // Functions, like `var()`, can stand for any value.
+    if value.contains('(') {
+        return None;
+    }
+    value
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '/')
+        .filter(|word| is_identifier(word))
+        .find(|word| {
+            let word = word.to_ascii_lowercase();
+            !keywords.contains(&word.as_str())
+                && !CSS_WIDE_KEYWORDS.contains(&word.as_str())
+                // Vendor keywords, like `-webkit-box`, are not checked.
+                && !has_vendor_prefix(&word)
+        })
+        .map(|keyword| ValidationErrorKind::InvalidKeyword {
+            property: property.to_string(),
+            keyword: keyword.to_string(),
+        })
+}
+
+/// Returns whether a name has a vendor prefix, like `-webkit-`.
+fn has_vendor_prefix(name: &str) -> bool {
+    name.strip_prefix('-')
+        .and_then(|name| name.split_once('-'))
+        .is_some_and(|(vendor, rest)| {
+            !vendor.is_empty()
+                && vendor.chars().all(|c| c.is_ascii_alphanumeric())
+                && !rest.is_empty()
+        })
+}
+
+/// Returns whether a word is a keyword, rather than a number, a
+/// length or a string.
+fn is_identifier(word: &str) -> bool {
+    let name = word.strip_prefix('-').unwrap_or(word);
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
@@ -0,0 +1,888 @@
+//! Typed CSS selectors, which can be built or parsed, rendered back to
+//! a string, and compared by their specificity.
+
+use std::fmt::{self, Display, Write};
+use std::ops::Add;
+
+use crate::css::{CssParseError, CssParseErrorKind};
+
+/// The pseudo-classes that take a list of selectors as their argument.
+const SELECTOR_PSEUDO_CLASSES: &[&str] =
+    &["is", "not", "where", "has", "matches", "any", "-webkit-any"];
+
+/// The pseudo-elements that can also be written with a single `:`.
+const LEGACY_PSEUDO_ELEMENTS: &[&str] = &["before", "after", "first-line", "first-letter"];
+
+/// The specificity of a selector, which decides which of two rules wins
+/// when both set the same property on an element.
+///
+/// The fields count the ids, the classes, attributes and pseudo-classes,
+/// and the types and pseudo-elements, where a higher count in an earlier
+/// field always wins, which is also how specificities are ordered.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::{Selector, Specificity};
+///
+/// let nav = Selector::parse("#nav a").unwrap();
+/// let active = Selector::parse("ul li a.active:hover").unwrap();
+///
+/// assert_eq!(nav.specificity(), Specificity(1, 0, 1));
+/// assert_eq!(active.specificity(), Specificity(0, 2, 3));
+/// assert!(nav.specificity() > active.specificity());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Specificity(pub u32, pub u32, pub u32);
+
+impl Add for Specificity {
+    type Output = Specificity;
+
+    fn add(self, other: Specificity) -> Specificity {
+        Specificity(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+impl Display for Specificity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
+    }
+}
+
+/// How a compound selector relates to the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Combinator {
+    /// A descendant, written as whitespace, like `nav a`.
+    Descendant,
+    /// A direct child, like `ul > li`.
+    Child,
+    /// The sibling right after, like `h1 + p`.
+    NextSibling,
+    /// Any sibling after, like `h1 ~ p`.
+    SubsequentSibling,
+}
+
+impl Combinator {
+    /// Returns the combinator as it is written between two compound
+    /// selectors.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Combinator::Descendant => " ",
+            Combinator::Child => " > ",
+            Combinator::NextSibling => " + ",
+            Combinator::SubsequentSibling => " ~ ",
+        }
+    }
+}
+
+/// The way an attribute selector matches the value of the attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeOperator {
+    /// `[a=v]`, the value is exactly `v`.
+    Equals,
+    /// `[a~=v]`, one of the whitespace separated words is `v`.
+    Includes,
+    /// `[a|=v]`, the value is `v` or starts with `v-`.
+    DashMatch,
+    /// `[a^=v]`, the value starts with `v`.
+    Prefix,
+    /// `[a$=v]`, the value ends with `v`.
+    Suffix,
+    /// `[a*=v]`, the value contains `v`.
+    Substring,
+}
+
+impl AttributeOperator {
+    /// Returns the operator as it is written in CSS.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttributeOperator::Equals => "=",
+            AttributeOperator::Includes => "~=",
+            AttributeOperator::DashMatch => "|=",
+            AttributeOperator::Prefix => "^=",
+            AttributeOperator::Suffix => "$=",
+            AttributeOperator::Substring => "*=",
+        }
+    }
+}
+
+/// An attribute selector, like `[href^="https"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeSelector {
+    pub name: String,
+    /// How the value is matched, or `None` if the attribute only
+    /// has to be present.
+    pub value: Option<(AttributeOperator, String)>,
+    /// Whether the value is matched ignoring ASCII case, like `[type="a" i]`.
+    pub case_insensitive: bool,
+}
+
+/// A pseudo-class, like `:hover`, `:nth-child(2n)` or `:not(.a)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PseudoClass {
+    /// A pseudo-class without an argument, like `hover`.
+    Name(String),
+    /// A pseudo-class taking a list of selectors, i.e. `is`, `not`,
+    /// `where` and `has`, whose selectors may start with a combinator.
+    Selectors(String, Vec<Selector>),
+    /// Any other pseudo-class with an argument, like `nth-child(2n + 1)`,
+    /// whose argument is kept as it is.
+    Function(String, String),
+}
+
+/// A simple selector, one of the parts of a compound selector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimpleSelector {
+    /// `*`
+    Universal,
+    /// A type selector, like `a`.
+    Type(String),
+    /// An id selector, like `#main`.
+    Id(String),
+    /// A class selector, like `.active`.
+    Class(String),
+    Attribute(AttributeSelector),
+    PseudoClass(PseudoClass),
+    /// A pseudo-element, like `::before`, with its argument if it has one,
+    /// like `part(label)`.
+    PseudoElement(String),
+}
+
+impl SimpleSelector {
+    /// Returns the specificity of the simple selector.
+    pub fn specificity(&self) -> Specificity {
+        match self {
+            SimpleSelector::Universal => Specificity(0, 0, 0),
+            SimpleSelector::Id(_) => Specificity(1, 0, 0),
+            SimpleSelector::Class(_) | SimpleSelector::Attribute(_) => Specificity(0, 1, 0),
+            SimpleSelector::Type(_) | SimpleSelector::PseudoElement(_) => Specificity(0, 0, 1),
+            SimpleSelector::PseudoClass(PseudoClass::Name(_)) => Specificity(0, 1, 0),
+            SimpleSelector::PseudoClass(PseudoClass::Selectors(name, selectors)) => {
+                if name == "where" {
+                    Specificity(0, 0, 0)
+                } else {
+                    max_specificity(selectors)
+                }
+            }
+            SimpleSelector::PseudoClass(PseudoClass::Function(name, argument)) => {
+                // The selectors after `of`, like in `:nth-child(2n of .item)`.
+                let of = match name.as_str() {
+                    "nth-child" | "nth-last-child" => argument
+                        .split_once(" of ")
+                        .and_then(|(_, selectors)| Selector::parse_list(selectors).ok())
+                        .map(|selectors| max_specificity(&selectors))
+                        .unwrap_or_default(),
+                    _ => Specificity::default(),
+                };
+                Specificity(0, 1, 0) + of
+            }
+        }
+    }
+}
+
+fn max_specificity(selectors: &[Selector]) -> Specificity {
+    selectors
+        .iter()
+        .map(Selector::specificity)
+        .max()
+        .unwrap_or_default()
+}
+
+/// A compound selector, the simple selectors between two combinators,
+/// like `a.active:hover`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Compound {
+    /// How the compound relates to the one before it, which is `None`
+    /// for the first one, unless the selector is relative, like the
+    /// `> img` in `:has(> img)`.
+    pub combinator: Option<Combinator>,
+    pub selectors: Vec<SimpleSelector>,
+}
+
+/// A CSS selector, like `nav > a.active:hover`.
+///
+/// Selectors can be built from their parts, or parsed from a string,
+/// and are rendered back to a string with `Display`, so they can be
+/// passed anywhere a selector string is accepted, like `add_style`.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::{AttributeOperator, Selector, Specificity};
+/// use html_tag::{Style, StyleSheet};
+///
+/// let link = Selector::tag("nav").child(
+///     Selector::tag("a")
+///         .with_class("active")
+///         .with_attribute_value("href", AttributeOperator::Prefix, "https")
+///         .with_pseudo_class("hover"),
+/// );
+///
+/// assert_eq!(link.to_string(), "nav > a.active[href^=\"https\"]:hover");
+/// assert_eq!(link.specificity(), Specificity(0, 3, 2));
+///
+/// let mut sheet = StyleSheet::new();
+/// sheet.add_style(&link, "color", "red");
+/// assert_eq!(sheet[&link.to_string()]["color"], "red");
+/// ```
+///
+/// Parsing also understands pseudo-classes that take selectors, and
+/// counts their specificity like browsers do.
+///
+/// ```
+/// use html_tag::styles::{Selector, Specificity};
+///
+/// let selector = Selector::parse("li:NOT(.done, #last) :where(#x)::before").unwrap();
+///
+/// assert_eq!(selector.to_string(), "li:not(.done, #last) :where(#x)::before");
+/// assert_eq!(selector.specificity(), Specificity(1, 0, 2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Selector {
+    /// The compound selectors, from left to right.
+    pub compounds: Vec<Compound>,
+}
+
+impl Selector {
+    /// Parses a single selector.
+    ///
+    /// A selector list, like `h1, h2`, is an error, use `parse_list`
+    /// for those.
+    pub fn parse(selector: &str) -> Result<Selector, CssParseError> {
+        let mut parser = SelectorParser {
+            input: selector,
+            pos: 0,
+        };
+        parser.skip_whitespace();
+        let parsed = parser.selector(false)?;
+        match parser.peek() {
+            None => Ok(parsed),
+            Some(c) => Err(parser.error(CssParseErrorKind::UnexpectedChar(c), parser.pos)),
+        }
+    }
+
+    /// Parses a comma separated list of selectors, like `h1, h2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::styles::Selector;
+    ///
+    /// let selectors = Selector::parse_list("h1, .title > span").unwrap();
+    ///
+    /// assert_eq!(selectors.len(), 2);
+    /// assert_eq!(selectors[1].to_string(), ".title > span");
+    ///
+    /// let error = Selector::parse_list("h1, ").unwrap_err();
+    /// assert_eq!(error.to_string(), "empty selector at line 1, column 5");
+    /// ```
+    pub fn parse_list(selectors: &str) -> Result<Vec<Selector>, CssParseError> {
+        let mut parser = SelectorParser {
+            input: selectors,
+            pos: 0,
+        };
+        parser.list(false, false)
+    }
+
+    /// Creates a selector matching any element, i.e. `*`.
+    pub fn universal() -> Selector {
+        Selector::new_with(SimpleSelector::Universal)
+    }
+
+    /// Creates a selector matching elements of the given type.
+    pub fn tag(name: &str) -> Selector {
+        Selector::new_with(SimpleSelector::Type(name.to_string()))
+    }
+
+    /// Creates a selector matching elements with the given class.
+    pub fn class(name: &str) -> Selector {
+        Selector::new_with(SimpleSelector::Class(name.to_string()))
+    }
+
+    /// Creates a selector matching the element with the given id.
+    pub fn id(id: &str) -> Selector {
+        Selector::new_with(SimpleSelector::Id(id.to_string()))
+    }
+
+    fn new_with(selector: SimpleSelector) -> Selector {
+        Selector {
+            compounds: vec![Compound {
+                combinator: None,
+                selectors: vec![selector],
+            }],
+        }
+    }
+
+    /// Returns the last compound selector, which the `with_*` methods
+    /// add to.
+    fn last_compound(&mut self) -> &mut Vec<SimpleSelector> {
+        if self.compounds.is_empty() {
+            self.compounds.push(Compound::default());
+        }
+        let last = self.compounds.len() - 1;
+        &mut self.compounds[last].selectors
+    }
+
+    /// Adds a simple selector to the last compound selector.
+    pub fn with(mut self, selector: SimpleSelector) -> Self {
+        let compound = self.last_compound();
+        match selector {
+            // A type always comes first.
+            SimpleSelector::Type(_) | SimpleSelector::Universal => {
+                compound
+                    .retain(|s| !matches!(s, SimpleSelector::Type(_) | SimpleSelector::Universal));
+                compound.insert(0, selector);
+            }
+            _ => compound.push(selector),
+        }
+        self
+    }
+
+    /// Chaining method for a type selector, which replaces any type
+    /// of the last compound selector.
+    pub fn with_tag(self, name: &str) -> Self {
+        self.with(SimpleSelector::Type(name.to_string()))
+    }
+
+    /// Chaining method for a class selector.
+    pub fn with_class(self, name: &str) -> Self {
+        self.with(SimpleSelector::Class(name.to_string()))
+    }
+
+    /// Chaining method for an id selector.
+    pub fn with_id(self, id: &str) -> Self {
+        self.with(SimpleSelector::Id(id.to_string()))
+    }
+
+    /// Chaining method for an attribute selector that only checks
+    /// that the attribute is present, like `[disabled]`.
+    pub fn with_attribute(self, name: &str) -> Self {
+        self.with(SimpleSelector::Attribute(AttributeSelector {
+            name: name.to_string(),
+            value: None,
+            case_insensitive: false,
+        }))
+    }
+
+    /// Chaining method for an attribute selector that checks the value
+    /// of the attribute, like `[type="checkbox"]`.
+    pub fn with_attribute_value(
+        self,
+        name: &str,
+        operator: AttributeOperator,
+        value: &str,
+    ) -> Self {
+        self.with(SimpleSelector::Attribute(AttributeSelector {
+            name: name.to_string(),
+            value: Some((operator, value.to_string())),
+            case_insensitive: false,
+        }))
+    }
+
+    /// Chaining method for a pseudo-class, like `hover`, or one with
+    /// an argument, like `nth-child(2n + 1)`.
+    pub fn with_pseudo_class(self, pseudo_class: &str) -> Self {
+        let pseudo_class = match pseudo_class.split_once('(') {
+            Some((name, argument)) => PseudoClass::Function(
+                name.to_ascii_lowercase(),
+                argument
+                    .strip_suffix(')')
+                    .unwrap_or(argument)
+                    .trim()
+                    .to_string(),
+            ),
+            None => PseudoClass::Name(pseudo_class.to_ascii_lowercase()),
+        };
+        self.with(SimpleSelector::PseudoClass(pseudo_class))
+    }
+
+    /// Chaining method for a pseudo-class taking selectors, like
+    /// `not` or `is`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::styles::Selector;
+    ///
+    /// let selector = Selector::tag("button")
+    ///     .with_selector_pseudo_class("not", vec![Selector::class("primary"), Selector::id("ok")]);
+    ///
+    /// assert_eq!(selector.to_string(), "button:not(.primary, #ok)");
+    /// ```
+    pub fn with_selector_pseudo_class(self, name: &str, selectors: Vec<Selector>) -> Self {
+        self.with(SimpleSelector::PseudoClass(PseudoClass::Selectors(
+            name.to_ascii_lowercase(),
+            selectors,
+        )))
+    }
+
+    /// Chaining method for a pseudo-element, like `before`.
+    pub fn with_pseudo_element(self, name: &str) -> Self {
+        self.with(SimpleSelector::PseudoElement(name.to_ascii_lowercase()))
+    }
+
+    fn combine(mut self, combinator: Combinator, other: Selector) -> Self {
+        let mut compounds = other.compounds.into_iter();
+        if let Some(mut first) = compounds.next() {
+            if !self.compounds.is_empty() {
+                first.combinator = Some(combinator);
+            }
+            self.compounds.push(first);
+        }
+        self.compounds.extend(compounds);
+        self
+    }
+
+    /// Combines the selector with one for its descendants, like `nav a`.
+    pub fn descendant(self, other: Selector) -> Self {
+        self.combine(Combinator::Descendant, other)
+    }
+
+    /// Combines the selector with one for its children, like `ul > li`.
+    pub fn child(self, other: Selector) -> Self {
+        self.combine(Combinator::Child, other)
+    }
+
+    /// Combines the selector with one for the sibling right after it,
+    /// like `h1 + p`.
+    pub fn next_sibling(self, other: Selector) -> Self {
+        self.combine(Combinator::NextSibling, other)
+    }
+
+    /// Combines the selector with one for any sibling after it,
+    /// like `h1 ~ p`.
+    pub fn subsequent_sibling(self, other: Selector) -> Self {
+        self.combine(Combinator::SubsequentSibling, other)
+    }
+
+    /// Returns the specificity of the selector.
+    pub fn specificity(&self) -> Specificity {
+        self.compounds
+            .iter()
+            .flat_map(|compound| &compound.selectors)
+            .map(SimpleSelector::specificity)
+            .fold(Specificity::default(), Add::add)
+    }
+}
+
+/// Writes a name, escaping the characters that are not allowed in it.
+fn write_identifier(f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    for (i, c) in name.chars().enumerate() {
+        let digit_first = c.is_ascii_digit() && (i == 0 || (i == 1 && name.starts_with('-')));
+        if digit_first || c.is_control() {
+            write!(f, "\\{:x} ", c as u32)?;
+        } else if c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+            f.write_char(c)?;
+        } else {
+            write!(f, "\\{}", c)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a value as a CSS string.
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => write!(f, "\\{}", c)?,
+            c if c.is_control() => write!(f, "\\{:x} ", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, selectors: &[Selector]) -> fmt::Result {
+    for (i, selector) in selectors.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", selector)?;
+    }
+    Ok(())
+}
+
+impl Display for SimpleSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimpleSelector::Universal => f.write_char('*'),
+            SimpleSelector::Type(name) => write_identifier(f, name),
+            SimpleSelector::Id(id) => {
+                f.write_char('#')?;
+                write_identifier(f, id)
+            }
+            SimpleSelector::Class(name) => {
+                f.write_char('.')?;
+                write_identifier(f, name)
+            }
+            SimpleSelector::Attribute(attribute) => {
+                f.write_char('[')?;
+                write_identifier(f, &attribute.name)?;
+                if let Some((operator, value)) = &attribute.value {
+                    f.write_str(operator.as_str())?;
+                    write_string(f, value)?;
+                    if attribute.case_insensitive {
+                        f.write_str(" i")?;
+                    }
+                }
+                f.write_char(']')
+            }
+            SimpleSelector::PseudoClass(PseudoClass::Name(name)) => {
+                f.write_char(':')?;
+                write_identifier(f, name)
+            }
+            SimpleSelector::PseudoClass(PseudoClass::Selectors(name, selectors)) => {
+                f.write_char(':')?;
+                write_identifier(f, name)?;
+                f.write_char('(')?;
+                write_list(f, selectors)?;
+                f.write_char(')')
+            }
+            SimpleSelector::PseudoClass(PseudoClass::Function(name, argument)) => {
+                f.write_char(':')?;
+                write_identifier(f, name)?;
+                write!(f, "({})", argument)
+            }
+            SimpleSelector::PseudoElement(name) => write!(f, "::{}", name),
+        }
+    }
+}
+
+impl Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, compound) in self.compounds.iter().enumerate() {
+            match compound.combinator {
+                Some(combinator) if i > 0 => f.write_str(combinator.as_str())?,
+                Some(combinator) => f.write_str(combinator.as_str().trim_start())?,
+                None => {}
+            }
+            if compound.selectors.is_empty() {
+                f.write_char('*')?;
+            }
+            for selector in &compound.selectors {
+                write!(f, "{}", selector)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct SelectorParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> SelectorParser<'a> {
+    fn error(&self, kind: CssParseErrorKind, offset: usize) -> CssParseError {
+        CssParseError::new(kind, self.input, offset)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn unexpected(&self) -> CssParseError {
+        match self.peek() {
+            Some(c) => self.error(CssParseErrorKind::UnexpectedChar(c), self.pos),
+            None => self.error(CssParseErrorKind::UnexpectedEof, self.pos),
+        }
+    }
+
+    /// Skips whitespace and comments, returning whether there were any.
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => self.pos += c.len_utf8(),
+                Some('/') if self.input[self.pos..].starts_with("/*") => {
+                    match self.input[self.pos + 2..].find("*/") {
+                        Some(end) => self.pos += 2 + end + 2,
+                        None => self.pos = self.input.len(),
+                    }
+                }
+                _ => return self.pos != start,
+            }
+        }
+    }
+
+    /// Parses a selector list, until the end of the input, or until the
+    /// `)` that closes the pseudo-class it is the argument of.
+    fn list(&mut self, nested: bool, relative: bool) -> Result<Vec<Selector>, CssParseError> {
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.selector(relative)?);
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(')') if nested => return Ok(selectors),
+                None if !nested => return Ok(selectors),
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn combinator(&mut self) -> Option<Combinator> {
+        let combinator = match self.peek()? {
+            '>' => Combinator::Child,
+            '+' => Combinator::NextSibling,
+            '~' => Combinator::SubsequentSibling,
+            _ => return None,
+        };
+        self.pos += 1;
+        self.skip_whitespace();
+        Some(combinator)
+    }
+
+    fn selector(&mut self, relative: bool) -> Result<Selector, CssParseError> {
+        let start = self.pos;
+        let mut combinator = if relative { self.combinator() } else { None };
+        let mut compounds = Vec::new();
+        loop {
+            let selectors = self.compound()?;
+            if selectors.is_empty() {
+                if compounds.is_empty() && combinator.is_none() {
+                    return Err(self.error(CssParseErrorKind::EmptySelector, start));
+                }
+                return Err(self.unexpected());
+            }
+            compounds.push(Compound {
+                combinator,
+                selectors,
+            });
+
+            let whitespace = self.skip_whitespace();
+            combinator = match self.combinator() {
+                Some(combinator) => Some(combinator),
+                None if whitespace && !matches!(self.peek(), None | Some(',' | ')')) => {
+                    Some(Combinator::Descendant)
+                }
+                None => return Ok(Selector { compounds }),
+            };
+        }
+    }
+
+    fn compound(&mut self) -> Result<Vec<SimpleSelector>, CssParseError> {
+        let mut selectors = Vec::new();
+        loop {
+            let start = self.pos;
+            let selector = match self.peek() {
+                Some('*') if selectors.is_empty() => {
+                    self.pos += 1;
+                    SimpleSelector::Universal
+                }
+                Some(c) if selectors.is_empty() && is_name_start(c) => {
+                    SimpleSelector::Type(self.name()?)
+                }
+                Some('#') => {
+                    self.pos += 1;
+                    SimpleSelector::Id(self.name()?)
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    SimpleSelector::Class(self.name()?)
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    SimpleSelector::Attribute(self.attribute()?)
+                }
+                Some(':') if self.input[self.pos..].starts_with("::") => {
+                    self.pos += 2;
+                    SimpleSelector::PseudoElement(self.pseudo_element()?)
+                }
+                Some(':') => {
+                    self.pos += 1;
+                    self.pseudo_class()?
+                }
+                _ => return Ok(selectors),
+            };
+            if start == self.pos {
+                return Err(self.unexpected());
+            }
+            selectors.push(selector);
+        }
+    }
+
+    /// Reads a name, like a class or an attribute, resolving escapes.
+    fn name(&mut self) -> Result<String, CssParseError> {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '\\' => {
+                    self.pos += 1;
+                    name.push(self.escape());
+                }
+                c if c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() => {
+                    name.push(c);
+                    self.pos += c.len_utf8();
+                }
+                _ => break,
+            }
+        }
+        if name.is_empty() {
+            return Err(self.unexpected());
+        }
+        Ok(name)
+    }
+
+    /// Reads the character of an escape, right after the `\`.
+    fn escape(&mut self) -> char {
+        let rest = &self.input[self.pos..];
+        let hex_len = rest
+            .chars()
+            .take(6)
+            .take_while(|c| c.is_ascii_hexdigit())
+            .count();
+        if hex_len > 0 {
+            let code = u32::from_str_radix(&rest[..hex_len], 16).unwrap_or(0xfffd);
+            self.pos += hex_len;
+            // A single whitespace ends the escape.
+            if let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+                self.pos += c.len_utf8();
+            }
+            return char::from_u32(code)
+                .filter(|&c| c != '\0')
+                .unwrap_or('\u{fffd}');
+        }
+        match self.peek() {
+            Some(c) => {
+                self.pos += c.len_utf8();
+                c
+            }
+            None => '\u{fffd}',
+        }
+    }
+
+    fn attribute(&mut self) -> Result<AttributeSelector, CssParseError> {
+        self.skip_whitespace();
+        let name = self.name()?;
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let operator = [
+            AttributeOperator::Includes,
+            AttributeOperator::DashMatch,
+            AttributeOperator::Prefix,
+            AttributeOperator::Suffix,
+            AttributeOperator::Substring,
+            AttributeOperator::Equals,
+        ]
+        .into_iter()
+        .find(|operator| rest.starts_with(operator.as_str()));
+
+        let mut attribute = AttributeSelector {
+            name,
+            value: None,
+            case_insensitive: false,
+        };
+        if let Some(operator) = operator {
+            self.pos += operator.as_str().len();
+            self.skip_whitespace();
+            let value = match self.peek() {
+                Some(quote @ ('"' | '\'')) => self.string(quote)?,
+                _ => self.name()?,
+            };
+            attribute.value = Some((operator, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some('i' | 'I') => {
+                    attribute.case_insensitive = true;
+                    self.pos += 1;
+                }
+                Some('s' | 'S') => self.pos += 1,
+                _ => {}
+            }
+            self.skip_whitespace();
+        }
+        match self.peek() {
+            Some(']') => {
+                self.pos += 1;
+                Ok(attribute)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Reads a string, resolving escapes.
+    fn string(&mut self, quote: char) -> Result<String, CssParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut value = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+            match c {
+                '\\' => match self.peek() {
+                    // An escaped newline continues the string.
+                    Some('\n') => self.pos += 1,
+                    _ => value.push(self.escape()),
+                },
+                '\n' => break,
+                c if c == quote => return Ok(value),
+                c => value.push(c),
+            }
+        }
+        Err(self.error(CssParseErrorKind::UnterminatedString, start))
+    }
+
+    /// Reads the argument of a pseudo-class or pseudo-element as it is,
+    /// up to and including its closing `)`.
+    fn argument(&mut self) -> Result<String, CssParseError> {
+        let start = self.pos;
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' | '\'' => {
+                    self.string(c)?;
+                    continue;
+                }
+                '\\' => self.pos += 1,
+                '(' => depth += 1,
+                ')' if depth == 0 => {
+                    let argument = self.input[start..self.pos].trim().to_string();
+                    self.pos += 1;
+                    return Ok(argument);
+                }
+                ')' => depth -= 1,
+                _ => {}
+            }
+            self.pos += self.peek().map_or(0, char::len_utf8);
+        }
+        Err(self.error(CssParseErrorKind::UnexpectedEof, start))
+    }
+
+    fn pseudo_class(&mut self) -> Result<SimpleSelector, CssParseError> {
+        let name = self.name()?.to_ascii_lowercase();
+        if LEGACY_PSEUDO_ELEMENTS.contains(&name.as_str()) {
+            return Ok(SimpleSelector::PseudoElement(name));
+        }
+        if self.peek() != Some('(') {
+            return Ok(SimpleSelector::PseudoClass(PseudoClass::Name(name)));
+        }
+        self.pos += 1;
+        if SELECTOR_PSEUDO_CLASSES.contains(&name.as_str()) {
+            let selectors = self.list(true, name == "has")?;
+            self.pos += 1;
+            Ok(SimpleSelector::PseudoClass(PseudoClass::Selectors(
+                name, selectors,
+            )))
+        } else {
+            let argument = self.argument()?;
+            Ok(SimpleSelector::PseudoClass(PseudoClass::Function(
+                name, argument,
+            )))
+        }
+    }
+
+    fn pseudo_element(&mut self) -> Result<String, CssParseError> {
+        let mut name = self.name()?.to_ascii_lowercase();
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            let argument = self.argument()?;
+            name = format!("{}({})", name, argument);
+        }
+        Ok(name)
+    }
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '-' || c == '_' || c == '\\' || !c.is_ascii()
+}
@@ -0,0 +1,550 @@
+//! Typed CSS values, which catch typos like `20 px` or `#ff000` when
+//! they are built instead of when the page is looked at.
+//!
+//! Every value implements `Display`, so it can be passed anywhere a
+//! value string is accepted, like `add_style` or `HtmlTag::set_style`.
+//!
+//! ```
+//! use html_tag::styles::{Color, Length, Percent};
+//! use html_tag::{HtmlTag, Style, StyleSheet};
+//!
+//! let mut sheet = StyleSheet::new();
+//! sheet.add_style(".card", "padding", Length::rem(1.5));
+//! sheet.add_style(".card", "width", Percent(100.0) - Length::px(32));
+//! sheet.add_style(".card", "color", Color::hex("#1e90ff").unwrap());
+//!
+//! assert_eq!(sheet[".card"]["padding"], "1.5rem");
+//! assert_eq!(sheet[".card"]["width"], "calc(100% - 32px)");
+//!
+//! let div = HtmlTag::new("div").with_style("background", Color::rgba(0, 0, 0, 0.5));
+//! assert_eq!(div.get_style("background"), Some("rgba(0, 0, 0, 0.5)"));
+//! ```
+
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// An error for a value that is not valid CSS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueError {
+    /// A hex color without 3, 4, 6 or 8 hex digits.
+    InvalidHexColor(String),
+}
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueError::InvalidHexColor(hex) => write!(f, "invalid hex color `{}`", hex),
+        }
+    }
+}
+
+impl std::error::Error for ValueError {}
+
+/// Turns `NaN` into `0`, and the infinities into the largest finite
+/// numbers, since CSS numbers can not be written otherwise.
+fn finite(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(f64::MIN, f64::MAX)
+    }
+}
+
+/// Writes a number the way CSS expects it, without a trailing `.0`.
+///
+/// Values are made finite when they are built, but the fields are
+/// public, so this makes sure again.
+fn write_number(f: &mut fmt::Formatter<'_>, value: f64) -> fmt::Result {
+    let value = finite(value);
+    // Avoids printing `-0`.
+    if value == 0.0 {
+        f.write_str("0")
+    } else {
+        write!(f, "{}", value)
+    }
+}
+
+/// The unit of a `Length`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthUnit {
+    Px,
+    Em,
+    Rem,
+    Ch,
+    Ex,
+    Vw,
+    Vh,
+    Vmin,
+    Vmax,
+    Pt,
+    Pc,
+    Cm,
+    Mm,
+    In,
+}
+
+impl LengthUnit {
+    /// Returns the unit as it is written in CSS.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LengthUnit::Px => "px",
+            LengthUnit::Em => "em",
+            LengthUnit::Rem => "rem",
+            LengthUnit::Ch => "ch",
+            LengthUnit::Ex => "ex",
+            LengthUnit::Vw => "vw",
+            LengthUnit::Vh => "vh",
+            LengthUnit::Vmin => "vmin",
+            LengthUnit::Vmax => "vmax",
+            LengthUnit::Pt => "pt",
+            LengthUnit::Pc => "pc",
+            LengthUnit::Cm => "cm",
+            LengthUnit::Mm => "mm",
+            LengthUnit::In => "in",
+        }
+    }
+}
+
+/// A length, like `20px` or `1.5rem`.
+///
+/// A length can be multiplied or divided by a number, and `NaN` or
+/// infinite values are made finite, see the constructors.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::{Length, LengthUnit};
+///
+/// assert_eq!(Length::px(20).to_string(), "20px");
+/// assert_eq!(Length::em(-0.25).to_string(), "-0.25em");
+/// assert_eq!(Length::new(1, LengthUnit::In).to_string(), "1in");
+/// assert_eq!(Length::zero().to_string(), "0");
+/// assert_eq!((Length::rem(1.5) * 2.0).to_string(), "3rem");
+/// assert_eq!(Length::px(f64::NAN).to_string(), "0px");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Length {
+    pub value: f64,
+    /// The unit, or `None` for a unitless zero.
+    pub unit: Option<LengthUnit>,
+}
+
+impl Length {
+    /// Creates a length with the given unit.
+    ///
+    /// `NaN` becomes `0`, and the infinities become the largest
+    /// finite numbers.
+    pub fn new(value: impl Into<f64>, unit: LengthUnit) -> Length {
+        Length {
+            value: finite(value.into()),
+            unit: Some(unit),
+        }
+    }
+
+    /// Creates a length of zero, which needs no unit, except inside
+    /// `calc()`, where it is written as `0px`.
+    pub fn zero() -> Length {
+        Length {
+            value: 0.0,
+            unit: None,
+        }
+    }
+
+    /// Creates a length in pixels.
+    pub fn px(value: impl Into<f64>) -> Length {
+        Length::new(value, LengthUnit::Px)
+    }
+
+    /// Creates a length relative to the font size of the element.
+    pub fn em(value: impl Into<f64>) -> Length {
+        Length::new(value, LengthUnit::Em)
+    }
+
+    /// Creates a length relative to the font size of the root element.
+    pub fn rem(value: impl Into<f64>) -> Length {
+        Length::new(value, LengthUnit::Rem)
+    }
+
+    /// Creates a length relative to the width of the `0` character.
+    pub fn ch(value: impl Into<f64>) -> Length {
+        Length::new(value, LengthUnit::Ch)
+    }
+
+    /// Creates a length in percent of the viewport width.
+    pub fn vw(value: impl Into<f64>) -> Length {
+        Length::new(value, LengthUnit::Vw)
+    }
+
+    /// Creates a length in percent of the viewport height.
+    pub fn vh(value: impl Into<f64>) -> Length {
+        Length::new(value, LengthUnit::Vh)
+    }
+
+    /// Creates a length in points.
+    pub fn pt(value: impl Into<f64>) -> Length {
+        Length::new(value, LengthUnit::Pt)
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_number(f, self.value)?;
+        match self.unit {
+            Some(unit) => f.write_str(unit.as_str()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A percentage, like `50%`.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::Percent;
+///
+/// assert_eq!(Percent(33.5).to_string(), "33.5%");
+/// assert_eq!((Percent(50.0) / 4.0).to_string(), "12.5%");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percent(pub f64);
+
+impl Percent {
+    /// Creates a percentage, making `NaN` and the infinities finite
+    /// like `Length::new` does.
+    pub fn new(value: impl Into<f64>) -> Percent {
+        Percent(finite(value.into()))
+    }
+}
+
+impl Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_number(f, self.0)?;
+        f.write_str("%")
+    }
+}
+
+/// A color, built from its components or a hex string.
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::{Color, ValueError};
+///
+/// assert_eq!(Color::rgb(255, 128, 0).to_string(), "rgb(255, 128, 0)");
+/// assert_eq!(Color::hsla(210, 50, 40, 0.8).to_string(), "hsla(210, 50%, 40%, 0.8)");
+/// assert_eq!(Color::hex("1E90FF").unwrap().to_string(), "#1e90ff");
+/// assert_eq!(Color::hex("#f0a").unwrap().to_string(), "#f0a");
+/// assert_eq!(
+///     Color::hex("#ff000"),
+///     Err(ValueError::InvalidHexColor("#ff000".to_string()))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Color(ColorValue);
+
+#[derive(Debug, Clone, PartialEq)]
+enum ColorValue {
+    Hex(String),
+    Rgb(u8, u8, u8, Option<f64>),
+    Hsl(f64, f64, f64, Option<f64>),
+    Keyword(&'static str),
+}
+
+impl Color {
+    /// Creates a color from its red, green and blue components.
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Color {
+        Color(ColorValue::Rgb(red, green, blue, None))
+    }
+
+    /// Creates a color from its red, green and blue components and
+    /// an alpha between `0` and `1`, which is clamped.
+    pub fn rgba(red: u8, green: u8, blue: u8, alpha: f64) -> Color {
+        Color(ColorValue::Rgb(
+            red,
+            green,
+            blue,
+            Some(finite(alpha).clamp(0.0, 1.0)),
+        ))
+    }
+
+    /// Creates a color from its hue in degrees, and its saturation
+    /// and lightness in percent, which are clamped.
+    pub fn hsl(
+        hue: impl Into<f64>,
+        saturation: impl Into<f64>,
+        lightness: impl Into<f64>,
+    ) -> Color {
+        Color(ColorValue::Hsl(
+            finite(hue.into()),
+            finite(saturation.into()).clamp(0.0, 100.0),
+            finite(lightness.into()).clamp(0.0, 100.0),
+            None,
+        ))
+    }
+
+    /// Like `hsl`, with an alpha between `0` and `1`, which is clamped.
+    pub fn hsla(
+        hue: impl Into<f64>,
+        saturation: impl Into<f64>,
+        lightness: impl Into<f64>,
+        alpha: f64,
+    ) -> Color {
+        Color(ColorValue::Hsl(
+            finite(hue.into()),
+            finite(saturation.into()).clamp(0.0, 100.0),
+            finite(lightness.into()).clamp(0.0, 100.0),
+            Some(finite(alpha).clamp(0.0, 1.0)),
+        ))
+    }
+
+    /// Creates a color from a hex string of 3, 4, 6 or 8 digits, with
+    /// or without the `#`.
+    pub fn hex(hex: &str) -> Result<Color, ValueError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !matches!(digits.len(), 3 | 4 | 6 | 8) || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(ValueError::InvalidHexColor(hex.to_string()));
+        }
+        Ok(Color(ColorValue::Hex(digits.to_ascii_lowercase())))
+    }
+
+    /// The fully transparent color.
+    pub fn transparent() -> Color {
+        Color(ColorValue::Keyword("transparent"))
+    }
+
+    /// The value of the `color` property of the element.
+    pub fn current_color() -> Color {
+        Color(ColorValue::Keyword("currentcolor"))
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            ColorValue::Hex(digits) => write!(f, "#{}", digits),
+            ColorValue::Rgb(r, g, b, None) => write!(f, "rgb({}, {}, {})", r, g, b),
+            ColorValue::Rgb(r, g, b, Some(alpha)) => {
+                write!(f, "rgba({}, {}, {}, ", r, g, b)?;
+                write_number(f, *alpha)?;
+                f.write_str(")")
+            }
+            ColorValue::Hsl(h, s, l, alpha) => {
+                f.write_str(if alpha.is_some() { "hsla(" } else { "hsl(" })?;
+                write_number(f, *h)?;
+                f.write_str(", ")?;
+                write_number(f, *s)?;
+                f.write_str("%, ")?;
+                write_number(f, *l)?;
+                f.write_str("%")?;
+                if let Some(alpha) = alpha {
+                    f.write_str(", ")?;
+                    write_number(f, *alpha)?;
+                }
+                f.write_str(")")
+            }
+            ColorValue::Keyword(keyword) => f.write_str(keyword),
+        }
+    }
+}
+
+/// A `calc()` expression, usually built by adding or subtracting
+/// lengths and percentages, and multiplying or dividing by numbers.
+///
+/// Numbers are only valid as factors and divisors, so there is no
+/// way to add a plain number to a length:
+///
+/// ```compile_fail
+/// use html_tag::styles::Length;
+///
+/// let width = Length::px(10) + 2.0;
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use html_tag::styles::{Calc, Length, Percent};
+///
+/// let width = (Percent(100.0) - Length::rem(2) - Length::px(1)) / 3.0;
+/// assert_eq!(width.to_string(), "calc((100% - 2rem - 1px) / 3)");
+///
+/// let offset = Length::px(10) - (Length::em(1) + Percent(5.0)) * 2.0;
+/// assert_eq!(offset.to_string(), "calc(10px - (1em + 5%) * 2)");
+///
+/// assert_eq!(Calc::from(Length::vh(100)).to_string(), "calc(100vh)");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Calc {
+    Length(Length),
+    Percent(Percent),
+    Add(Box<Calc>, Box<Calc>),
+    Sub(Box<Calc>, Box<Calc>),
+    Mul(Box<Calc>, f64),
+    Div(Box<Calc>, f64),
+}
+
+impl Calc {
+    fn is_sum(&self) -> bool {
+        matches!(self, Calc::Add(..) | Calc::Sub(..))
+    }
+
+    /// Writes the expression without the surrounding `calc()`.
+    fn write_expression(&self, f: &mut fmt::Formatter<'_>, parens: bool) -> fmt::Result {
+        if parens {
+            f.write_str("(")?;
+        }
+        match self {
+            // A unitless zero is a number, not a length, inside `calc()`.
+            Calc::Length(Length { unit: None, .. }) => f.write_str("0px")?,
+            Calc::Length(length) => write!(f, "{}", length)?,
+            Calc::Percent(percent) => write!(f, "{}", percent)?,
+            Calc::Add(left, right) => {
+                left.write_expression(f, false)?;
+                f.write_str(" + ")?;
+                right.write_expression(f, false)?;
+            }
+            Calc::Sub(left, right) => {
+                left.write_expression(f, false)?;
+                f.write_str(" - ")?;
+                right.write_expression(f, right.is_sum())?;
+            }
+            Calc::Mul(left, factor) => {
+                left.write_expression(f, left.is_sum())?;
+                f.write_str(" * ")?;
+                write_number(f, *factor)?;
+            }
+            Calc::Div(left, divisor) => {
+                left.write_expression(f, left.is_sum())?;
+                f.write_str(" / ")?;
+                write_number(f, *divisor)?;
+            }
+        }
+        if parens {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Calc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("calc(")?;
+        self.write_expression(f, false)?;
+        f.write_str(")")
+    }
+}
+
+impl From<Length> for Calc {
+    fn from(length: Length) -> Calc {
+        Calc::Length(length)
+    }
+}
+
+impl From<Percent> for Calc {
+    fn from(percent: Percent) -> Calc {
+        Calc::Percent(percent)
+    }
+}
+
+impl<T: Into<Calc>> Add<T> for Calc {
+    type Output = Calc;
+
+    fn add(self, other: T) -> Calc {
+        Calc::Add(Box::new(self), Box::new(other.into()))
+    }
+}
+
+impl<T: Into<Calc>> Sub<T> for Calc {
+    type Output = Calc;
+
+    fn sub(self, other: T) -> Calc {
+        Calc::Sub(Box::new(self), Box::new(other.into()))
+    }
+}
+
+impl Mul<f64> for Calc {
+    type Output = Calc;
+
+    fn mul(self, factor: f64) -> Calc {
+        Calc::Mul(Box::new(self), finite(factor))
+    }
+}
+
+impl Div<f64> for Calc {
+    type Output = Calc;
+
+    fn div(self, divisor: f64) -> Calc {
+        Calc::Div(Box::new(self), finite(divisor))
+    }
+}
+
+impl<T: Into<Calc>> Add<T> for Length {
+    type Output = Calc;
+
+    fn add(self, other: T) -> Calc {
+        Calc::from(self) + other
+    }
+}
+
+impl<T: Into<Calc>> Sub<T> for Length {
+    type Output = Calc;
+
+    fn sub(self, other: T) -> Calc {
+        Calc::from(self) - other
+    }
+}
+
+impl<T: Into<Calc>> Add<T> for Percent {
+    type Output = Calc;
+
+    fn add(self, other: T) -> Calc {
+        Calc::from(self) + other
+    }
+}
+
+impl<T: Into<Calc>> Sub<T> for Percent {
+    type Output = Calc;
+
+    fn sub(self, other: T) -> Calc {
+        Calc::from(self) - other
+    }
+}
+
+impl Mul<f64> for Length {
+    type Output = Length;
+
+    fn mul(self, factor: f64) -> Length {
+        Length {
+            value: finite(self.value * factor),
+            unit: self.unit,
+        }
+    }
+}
+
+impl Div<f64> for Length {
+    type Output = Length;
+
+    fn div(self, divisor: f64) -> Length {
+        Length {
+            value: finite(self.value / divisor),
+            unit: self.unit,
+        }
+    }
+}
+
+impl Mul<f64> for Percent {
+    type Output = Percent;
+
+    fn mul(self, factor: f64) -> Percent {
+        Percent::new(self.0 * factor)
+    }
+}
+
+impl Div<f64> for Percent {
+    type Output = Percent;
+
+    fn div(self, divisor: f64) -> Percent {
+        Percent::new(self.0 / divisor)
+    }
+}
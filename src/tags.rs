@@ -2,17 +2,23 @@ use std::fmt::Display;
 
 /// A struct that represents a HTML tag.
 ///
-/// This has some of the most used HTML tags as enums.
-/// However, there is nothing inherently special about these tags.
-/// These are just to make it a bit more convenient to use.
+/// This has every element of the WHATWG HTML standard as an enum,
+/// including the `svg` and `math` elements that embed foreign content.
 ///
-/// You can essentially declare a `p` tag using the P enum or using the
-/// `Custom` enum.
+/// The `Custom` enum is meant for autonomous custom elements, like
+/// `my-element`, and any other tag that is not part of the standard.
+/// `TagType::from` never returns a `Custom` tag for a standard element.
+///
+/// Every tag also knows which content categories of the standard it
+/// belongs to, see `is_void`, `is_phrasing`, `is_flow` and friends.
 ///
 /// The only thing that determines whether a tag is valid or not is the
 /// `TagType` enum.
-/// Moreover, the `TagType` enum also implements `Ord` and `PartialOrd` traits
-/// so that you can sort the tags and use it in a `BTreeMap` or `BTreeSet`.
+/// Moreover, the `TagType` enum also implements `Ord`, `PartialOrd` and
+/// `Hash` traits so that you can sort the tags and use it in a `BTreeMap`,
+/// `BTreeSet`, `HashMap` or `HashSet`.
+///
+/// Tags are ordered alphabetically by their name.
 ///
 /// # Examples
 ///
@@ -27,17 +33,16 @@ use std::fmt::Display;
 /// ];
 ///
 /// tags.sort();
-/// tags.reverse();
 ///
 /// assert_eq!(tags, vec![
-///   TagType::Div,
 ///   TagType::A,
-///   TagType::Span,
+///   TagType::Div,
 ///   TagType::P,
+///   TagType::Span,
 /// ]);
 ///
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TagType {
     P,
     Div,
@@ -54,10 +59,228 @@ pub enum TagType {
     Tr,
     Td,
     Th,
+    Area,
+    Base,
+    Br,
+    Col,
+    Embed,
+    Hr,
+    Input,
+    Link,
+    Meta,
+    Source,
+    Track,
+    Wbr,
+    Abbr,
+    Address,
+    Article,
+    Aside,
+    Audio,
+    B,
+    Bdi,
+    Bdo,
+    Blockquote,
+    Body,
+    Button,
+    Canvas,
+    Caption,
+    Cite,
+    Code,
+    Colgroup,
+    Data,
+    Datalist,
+    Dd,
+    Del,
+    Details,
+    Dfn,
+    Dialog,
+    Dl,
+    Dt,
+    Em,
+    Fieldset,
+    Figcaption,
+    Figure,
+    Footer,
+    Form,
+    Head,
+    Header,
+    Hgroup,
+    Html,
+    I,
+    Iframe,
+    Ins,
+    Kbd,
+    Label,
+    Legend,
+    Li,
+    Main,
+    Map,
+    Mark,
+    Math,
+    Menu,
+    Meter,
+    Nav,
+    Noscript,
+    Object,
+    Ol,
+    Optgroup,
+    Option,
+    Output,
+    Picture,
+    Pre,
+    Progress,
+    Q,
+    Rp,
+    Rt,
+    Ruby,
+    S,
+    Samp,
+    Script,
+    Search,
+    Section,
+    Select,
+    Slot,
+    Small,
+    Strong,
+    Style,
+    Sub,
+    Summary,
+    Sup,
+    Svg,
+    Tbody,
+    Template,
+    Textarea,
+    Tfoot,
+    Thead,
+    Time,
+    Title,
+    U,
+    Ul,
+    Var,
+    Video,
     Custom(String),
 }
 
 impl TagType {
+    /// Every element defined by the HTML standard, i.e. every
+    /// `TagType` except `Custom`.
+    pub const STANDARD: &'static [TagType] = &[
+        TagType::P,
+        TagType::Div,
+        TagType::Span,
+        TagType::A,
+        TagType::H1,
+        TagType::H2,
+        TagType::H3,
+        TagType::H4,
+        TagType::H5,
+        TagType::H6,
+        TagType::Img,
+        TagType::Table,
+        TagType::Tr,
+        TagType::Td,
+        TagType::Th,
+        TagType::Area,
+        TagType::Base,
+        TagType::Br,
+        TagType::Col,
+        TagType::Embed,
+        TagType::Hr,
+        TagType::Input,
+        TagType::Link,
+        TagType::Meta,
+        TagType::Source,
+        TagType::Track,
+        TagType::Wbr,
+        TagType::Abbr,
+        TagType::Address,
+        TagType::Article,
+        TagType::Aside,
+        TagType::Audio,
+        TagType::B,
+        TagType::Bdi,
+        TagType::Bdo,
+        TagType::Blockquote,
+        TagType::Body,
+        TagType::Button,
+        TagType::Canvas,
+        TagType::Caption,
+        TagType::Cite,
+        TagType::Code,
+        TagType::Colgroup,
+        TagType::Data,
+        TagType::Datalist,
+        TagType::Dd,
+        TagType::Del,
+        TagType::Details,
+        TagType::Dfn,
+        TagType::Dialog,
+        TagType::Dl,
+        TagType::Dt,
+        TagType::Em,
+        TagType::Fieldset,
+        TagType::Figcaption,
+        TagType::Figure,
+        TagType::Footer,
+        TagType::Form,
+        TagType::Head,
+        TagType::Header,
+        TagType::Hgroup,
+        TagType::Html,
+        TagType::I,
+        TagType::Iframe,
+        TagType::Ins,
+        TagType::Kbd,
+        TagType::Label,
+        TagType::Legend,
+        TagType::Li,
+        TagType::Main,
+        TagType::Map,
+        TagType::Mark,
+        TagType::Math,
+        TagType::Menu,
+        TagType::Meter,
+        TagType::Nav,
+        TagType::Noscript,
+        TagType::Object,
+        TagType::Ol,
+        TagType::Optgroup,
+        TagType::Option,
+        TagType::Output,
+        TagType::Picture,
+        TagType::Pre,
+        TagType::Progress,
+        TagType::Q,
+        TagType::Rp,
+        TagType::Rt,
+        TagType::Ruby,
+        TagType::S,
+        TagType::Samp,
+        TagType::Script,
+        TagType::Search,
+        TagType::Section,
+        TagType::Select,
+        TagType::Slot,
+        TagType::Small,
+        TagType::Strong,
+        TagType::Style,
+        TagType::Sub,
+        TagType::Summary,
+        TagType::Sup,
+        TagType::Svg,
+        TagType::Tbody,
+        TagType::Template,
+        TagType::Textarea,
+        TagType::Tfoot,
+        TagType::Thead,
+        TagType::Time,
+        TagType::Title,
+        TagType::U,
+        TagType::Ul,
+        TagType::Var,
+        TagType::Video,
+    ];
+
     /// Creates a new `TagType` from the given string.
     ///
     /// Tries to essentially convert the string to a `TagType` enum.
@@ -74,6 +297,15 @@ impl TagType {
     /// ```
     ///
     /// If a tag is not valid, then it will be converted to a `Custom` tag.
+    /// Every standard element round trips through `html`.
+    ///
+    /// ```
+    /// use html_tag::TagType;
+    ///
+    /// for tag in TagType::STANDARD {
+    ///     assert_eq!(&TagType::from(&tag.html()), tag);
+    /// }
+    /// ```
     pub fn from(tag: &str) -> TagType {
         let tag = tag.to_lowercase();
         match tag.as_str() {
@@ -92,6 +324,105 @@ impl TagType {
             "tr" => TagType::Tr,
             "td" => TagType::Td,
             "th" => TagType::Th,
+            "area" => TagType::Area,
+            "base" => TagType::Base,
+            "br" => TagType::Br,
+            "col" => TagType::Col,
+            "embed" => TagType::Embed,
+            "hr" => TagType::Hr,
+            "input" => TagType::Input,
+            "link" => TagType::Link,
+            "meta" => TagType::Meta,
+            "source" => TagType::Source,
+            "track" => TagType::Track,
+            "wbr" => TagType::Wbr,
+            "abbr" => TagType::Abbr,
+            "address" => TagType::Address,
+            "article" => TagType::Article,
+            "aside" => TagType::Aside,
+            "audio" => TagType::Audio,
+            "b" => TagType::B,
+            "bdi" => TagType::Bdi,
+            "bdo" => TagType::Bdo,
+            "blockquote" => TagType::Blockquote,
+            "body" => TagType::Body,
+            "button" => TagType::Button,
+            "canvas" => TagType::Canvas,
+            "caption" => TagType::Caption,
+            "cite" => TagType::Cite,
+            "code" => TagType::Code,
+            "colgroup" => TagType::Colgroup,
+            "data" => TagType::Data,
+            "datalist" => TagType::Datalist,
+            "dd" => TagType::Dd,
+            "del" => TagType::Del,
+            "details" => TagType::Details,
+            "dfn" => TagType::Dfn,
+            "dialog" => TagType::Dialog,
+            "dl" => TagType::Dl,
+            "dt" => TagType::Dt,
+            "em" => TagType::Em,
+            "fieldset" => TagType::Fieldset,
+            "figcaption" => TagType::Figcaption,
+            "figure" => TagType::Figure,
+            "footer" => TagType::Footer,
+            "form" => TagType::Form,
+            "head" => TagType::Head,
+            "header" => TagType::Header,
+            "hgroup" => TagType::Hgroup,
+            "html" => TagType::Html,
+            "i" => TagType::I,
+            "iframe" => TagType::Iframe,
+            "ins" => TagType::Ins,
+            "kbd" => TagType::Kbd,
+            "label" => TagType::Label,
+            "legend" => TagType::Legend,
+            "li" => TagType::Li,
+            "main" => TagType::Main,
+            "map" => TagType::Map,
+            "mark" => TagType::Mark,
+            "math" => TagType::Math,
+            "menu" => TagType::Menu,
+            "meter" => TagType::Meter,
+            "nav" => TagType::Nav,
+            "noscript" => TagType::Noscript,
+            "object" => TagType::Object,
+            "ol" => TagType::Ol,
+            "optgroup" => TagType::Optgroup,
+            "option" => TagType::Option,
+            "output" => TagType::Output,
+            "picture" => TagType::Picture,
+            "pre" => TagType::Pre,
+            "progress" => TagType::Progress,
+            "q" => TagType::Q,
+            "rp" => TagType::Rp,
+            "rt" => TagType::Rt,
+            "ruby" => TagType::Ruby,
+            "s" => TagType::S,
+            "samp" => TagType::Samp,
+            "script" => TagType::Script,
+            "search" => TagType::Search,
+            "section" => TagType::Section,
+            "select" => TagType::Select,
+            "slot" => TagType::Slot,
+            "small" => TagType::Small,
+            "strong" => TagType::Strong,
+            "style" => TagType::Style,
+            "sub" => TagType::Sub,
+            "summary" => TagType::Summary,
+            "sup" => TagType::Sup,
+            "svg" => TagType::Svg,
+            "tbody" => TagType::Tbody,
+            "template" => TagType::Template,
+            "textarea" => TagType::Textarea,
+            "tfoot" => TagType::Tfoot,
+            "thead" => TagType::Thead,
+            "time" => TagType::Time,
+            "title" => TagType::Title,
+            "u" => TagType::U,
+            "ul" => TagType::Ul,
+            "var" => TagType::Var,
+            "video" => TagType::Video,
             _ => TagType::Custom(tag.to_string()),
         }
     }
@@ -109,25 +440,412 @@ impl TagType {
     ///
     /// This is essentially the same as the `Display` trait.
     pub fn html(&self) -> String {
+        self.as_str().to_string()
+    }
+
+    /// Returns the name of the tag, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::TagType;
+    ///
+    /// assert_eq!(TagType::Section.as_str(), "section");
+    /// assert_eq!(TagType::from("my-element").as_str(), "my-element");
+    /// ```
+    pub fn as_str(&self) -> &str {
         match self {
-            TagType::P => "p".to_string(),
-            TagType::Div => "div".to_string(),
-            TagType::Span => "span".to_string(),
-            TagType::A => "a".to_string(),
-            TagType::H1 => "h1".to_string(),
-            TagType::H2 => "h2".to_string(),
-            TagType::H3 => "h3".to_string(),
-            TagType::H4 => "h4".to_string(),
-            TagType::H5 => "h5".to_string(),
-            TagType::H6 => "h6".to_string(),
-            TagType::Img => "img".to_string(),
-            TagType::Custom(tag) => tag.to_string(),
-            TagType::Table => "table".to_string(),
-            TagType::Tr => "tr".to_string(),
-            TagType::Td => "td".to_string(),
-            TagType::Th => "th".to_string(),
+            TagType::P => "p",
+            TagType::Div => "div",
+            TagType::Span => "span",
+            TagType::A => "a",
+            TagType::H1 => "h1",
+            TagType::H2 => "h2",
+            TagType::H3 => "h3",
+            TagType::H4 => "h4",
+            TagType::H5 => "h5",
+            TagType::H6 => "h6",
+            TagType::Img => "img",
+            TagType::Table => "table",
+            TagType::Tr => "tr",
+            TagType::Td => "td",
+            TagType::Th => "th",
+            TagType::Area => "area",
+            TagType::Base => "base",
+            TagType::Br => "br",
+            TagType::Col => "col",
+            TagType::Embed => "embed",
+            TagType::Hr => "hr",
+            TagType::Input => "input",
+            TagType::Link => "link",
+            TagType::Meta => "meta",
+            TagType::Source => "source",
+            TagType::Track => "track",
+            TagType::Wbr => "wbr",
+            TagType::Abbr => "abbr",
+            TagType::Address => "address",
+            TagType::Article => "article",
+            TagType::Aside => "aside",
+            TagType::Audio => "audio",
+            TagType::B => "b",
+            TagType::Bdi => "bdi",
+            TagType::Bdo => "bdo",
+            TagType::Blockquote => "blockquote",
+            TagType::Body => "body",
+            TagType::Button => "button",
+            TagType::Canvas => "canvas",
+            TagType::Caption => "caption",
+            TagType::Cite => "cite",
+            TagType::Code => "code",
+            TagType::Colgroup => "colgroup",
+            TagType::Data => "data",
+            TagType::Datalist => "datalist",
+            TagType::Dd => "dd",
+            TagType::Del => "del",
+            TagType::Details => "details",
+            TagType::Dfn => "dfn",
+            TagType::Dialog => "dialog",
+            TagType::Dl => "dl",
+            TagType::Dt => "dt",
+            TagType::Em => "em",
+            TagType::Fieldset => "fieldset",
+            TagType::Figcaption => "figcaption",
+            TagType::Figure => "figure",
+            TagType::Footer => "footer",
+            TagType::Form => "form",
+            TagType::Head => "head",
+            TagType::Header => "header",
+            TagType::Hgroup => "hgroup",
+            TagType::Html => "html",
+            TagType::I => "i",
+            TagType::Iframe => "iframe",
+            TagType::Ins => "ins",
+            TagType::Kbd => "kbd",
+            TagType::Label => "label",
+            TagType::Legend => "legend",
+            TagType::Li => "li",
+            TagType::Main => "main",
+            TagType::Map => "map",
+            TagType::Mark => "mark",
+            TagType::Math => "math",
+            TagType::Menu => "menu",
+            TagType::Meter => "meter",
+            TagType::Nav => "nav",
+            TagType::Noscript => "noscript",
+            TagType::Object => "object",
+            TagType::Ol => "ol",
+            TagType::Optgroup => "optgroup",
+            TagType::Option => "option",
+            TagType::Output => "output",
+            TagType::Picture => "picture",
+            TagType::Pre => "pre",
+            TagType::Progress => "progress",
+            TagType::Q => "q",
+            TagType::Rp => "rp",
+            TagType::Rt => "rt",
+            TagType::Ruby => "ruby",
+            TagType::S => "s",
+            TagType::Samp => "samp",
+            TagType::Script => "script",
+            TagType::Search => "search",
+            TagType::Section => "section",
+            TagType::Select => "select",
+            TagType::Slot => "slot",
+            TagType::Small => "small",
+            TagType::Strong => "strong",
+            TagType::Style => "style",
+            TagType::Sub => "sub",
+            TagType::Summary => "summary",
+            TagType::Sup => "sup",
+            TagType::Svg => "svg",
+            TagType::Tbody => "tbody",
+            TagType::Template => "template",
+            TagType::Textarea => "textarea",
+            TagType::Tfoot => "tfoot",
+            TagType::Thead => "thead",
+            TagType::Time => "time",
+            TagType::Title => "title",
+            TagType::U => "u",
+            TagType::Ul => "ul",
+            TagType::Var => "var",
+            TagType::Video => "video",
+            TagType::Custom(tag) => tag,
         }
     }
+
+    /// Returns whether the tag is a `Custom` tag, i.e. not one of
+    /// the elements defined by the HTML standard.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, TagType::Custom(_))
+    }
+
+    /// Returns whether the tag is a void element.
+    ///
+    /// Void elements can not have any content, and are rendered
+    /// without a closing tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::TagType;
+    ///
+    /// assert!(TagType::Img.is_void());
+    /// assert!(TagType::from("br").is_void());
+    /// assert!(!TagType::Div.is_void());
+    /// ```
+    pub fn is_void(&self) -> bool {
+        matches!(
+            self,
+            TagType::Area
+                | TagType::Base
+                | TagType::Br
+                | TagType::Col
+                | TagType::Embed
+                | TagType::Hr
+                | TagType::Img
+                | TagType::Input
+                | TagType::Link
+                | TagType::Meta
+                | TagType::Source
+                | TagType::Track
+                | TagType::Wbr
+        )
+    }
+
+    /// Returns whether the tag is a raw text element, i.e. `script` or `style`.
+    ///
+    /// The content of raw text elements is not parsed as HTML, so text
+    /// added to them is still escaped when rendered, and content that
+    /// must stay as it is has to be added as `RawHtml`.
+    pub fn is_raw_text(&self) -> bool {
+        matches!(self, TagType::Script | TagType::Style)
+    }
+
+    /// Returns whether the tag is an escapable raw text element,
+    /// i.e. `textarea` or `title`.
+    ///
+    /// These can only contain text, in which character references
+    /// are still allowed.
+    pub fn is_escapable_raw_text(&self) -> bool {
+        matches!(self, TagType::Textarea | TagType::Title)
+    }
+
+    /// Returns whether the tag is heading content, i.e. `h1` to `h6`
+    /// and `hgroup`.
+    pub fn is_heading(&self) -> bool {
+        matches!(
+            self,
+            TagType::H1
+                | TagType::H2
+                | TagType::H3
+                | TagType::H4
+                | TagType::H5
+                | TagType::H6
+                | TagType::Hgroup
+        )
+    }
+
+    /// Returns whether the tag is sectioning content, i.e. `article`,
+    /// `aside`, `nav` and `section`.
+    pub fn is_sectioning(&self) -> bool {
+        matches!(
+            self,
+            TagType::Article | TagType::Aside | TagType::Nav | TagType::Section
+        )
+    }
+
+    /// Returns whether the tag is phrasing content, i.e. the text
+    /// level elements that can be used inside a paragraph.
+    ///
+    /// Some of these, like `area`, `link` or `meta`, only count as
+    /// phrasing content in certain contexts, which are not taken
+    /// into account here. Custom elements are phrasing content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use html_tag::TagType;
+    ///
+    /// assert!(TagType::Span.is_phrasing());
+    /// assert!(TagType::Strong.is_phrasing());
+    /// assert!(!TagType::Div.is_phrasing());
+    /// ```
+    pub fn is_phrasing(&self) -> bool {
+        matches!(
+            self,
+            TagType::A
+                | TagType::Abbr
+                | TagType::Area
+                | TagType::Audio
+                | TagType::B
+                | TagType::Bdi
+                | TagType::Bdo
+                | TagType::Br
+                | TagType::Button
+                | TagType::Canvas
+                | TagType::Cite
+                | TagType::Code
+                | TagType::Data
+                | TagType::Datalist
+                | TagType::Del
+                | TagType::Dfn
+                | TagType::Em
+                | TagType::Embed
+                | TagType::I
+                | TagType::Iframe
+                | TagType::Img
+                | TagType::Input
+                | TagType::Ins
+                | TagType::Kbd
+                | TagType::Label
+                | TagType::Link
+                | TagType::Map
+                | TagType::Mark
+                | TagType::Math
+                | TagType::Meta
+                | TagType::Meter
+                | TagType::Noscript
+                | TagType::Object
+                | TagType::Output
+                | TagType::Picture
+                | TagType::Progress
+                | TagType::Q
+                | TagType::Ruby
+                | TagType::S
+                | TagType::Samp
+                | TagType::Script
+                | TagType::Select
+                | TagType::Slot
+                | TagType::Small
+                | TagType::Span
+                | TagType::Strong
+                | TagType::Sub
+                | TagType::Sup
+                | TagType::Svg
+                | TagType::Template
+                | TagType::Textarea
+                | TagType::Time
+                | TagType::U
+                | TagType::Var
+                | TagType::Video
+                | TagType::Wbr
+                | TagType::Custom(_)
+        )
+    }
+
+    /// Returns whether the tag is flow content, which covers most
+    /// of the elements that can be used inside `body`.
+    ///
+    /// All phrasing content is also flow content.
+    pub fn is_flow(&self) -> bool {
+        matches!(
+            self,
+            TagType::A
+                | TagType::Abbr
+                | TagType::Address
+                | TagType::Area
+                | TagType::Article
+                | TagType::Aside
+                | TagType::Audio
+                | TagType::B
+                | TagType::Bdi
+                | TagType::Bdo
+                | TagType::Blockquote
+                | TagType::Br
+                | TagType::Button
+                | TagType::Canvas
+                | TagType::Cite
+                | TagType::Code
+                | TagType::Data
+                | TagType::Datalist
+                | TagType::Del
+                | TagType::Details
+                | TagType::Dfn
+                | TagType::Dialog
+                | TagType::Div
+                | TagType::Dl
+                | TagType::Em
+                | TagType::Embed
+                | TagType::Fieldset
+                | TagType::Figure
+                | TagType::Footer
+                | TagType::Form
+                | TagType::H1
+                | TagType::H2
+                | TagType::H3
+                | TagType::H4
+                | TagType::H5
+                | TagType::H6
+                | TagType::Header
+                | TagType::Hgroup
+                | TagType::Hr
+                | TagType::I
+                | TagType::Iframe
+                | TagType::Img
+                | TagType::Input
+                | TagType::Ins
+                | TagType::Kbd
+                | TagType::Label
+                | TagType::Link
+                | TagType::Main
+                | TagType::Map
+                | TagType::Mark
+                | TagType::Math
+                | TagType::Menu
+                | TagType::Meta
+                | TagType::Meter
+                | TagType::Nav
+                | TagType::Noscript
+                | TagType::Object
+                | TagType::Ol
+                | TagType::Output
+                | TagType::P
+                | TagType::Picture
+                | TagType::Pre
+                | TagType::Progress
+                | TagType::Q
+                | TagType::Ruby
+                | TagType::S
+                | TagType::Samp
+                | TagType::Script
+                | TagType::Search
+                | TagType::Section
+                | TagType::Select
+                | TagType::Slot
+                | TagType::Small
+                | TagType::Span
+                | TagType::Strong
+                | TagType::Sub
+                | TagType::Sup
+                | TagType::Svg
+                | TagType::Table
+                | TagType::Template
+                | TagType::Textarea
+                | TagType::Time
+                | TagType::U
+                | TagType::Ul
+                | TagType::Var
+                | TagType::Video
+                | TagType::Wbr
+                | TagType::Custom(_)
+        )
+    }
+
+    /// Returns whether the tag is a form associated element, i.e. one
+    /// that can have a form owner.
+    pub fn is_form_associated(&self) -> bool {
+        matches!(
+            self,
+            TagType::Button
+                | TagType::Fieldset
+                | TagType::Img
+                | TagType::Input
+                | TagType::Object
+                | TagType::Output
+                | TagType::Select
+                | TagType::Textarea
+        )
+    }
 }
 
 impl Display for TagType {
@@ -143,32 +861,13 @@ impl PartialOrd for TagType {
 }
 
 impl Ord for TagType {
+    /// Tags are ordered by their name.
+    ///
+    /// A standard tag comes before a `Custom` tag with the same name,
+    /// which keeps the order consistent with `Eq`.
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match (&self, other) {
-            (TagType::Div, _) => std::cmp::Ordering::Greater,
-            (_, TagType::Div) => std::cmp::Ordering::Less,
-            (TagType::H1, _) => std::cmp::Ordering::Greater,
-            (_, TagType::H1) => std::cmp::Ordering::Less,
-            (TagType::H2, _) => std::cmp::Ordering::Greater,
-            (_, TagType::H2) => std::cmp::Ordering::Less,
-            (TagType::H3, _) => std::cmp::Ordering::Greater,
-            (_, TagType::H3) => std::cmp::Ordering::Less,
-            (TagType::H4, _) => std::cmp::Ordering::Greater,
-            (_, TagType::H4) => std::cmp::Ordering::Less,
-            (TagType::H5, _) => std::cmp::Ordering::Greater,
-            (_, TagType::H5) => std::cmp::Ordering::Less,
-            (TagType::H6, _) => std::cmp::Ordering::Greater,
-            (_, TagType::H6) => std::cmp::Ordering::Less,
-            (TagType::Img, _) => std::cmp::Ordering::Greater,
-            (_, TagType::Img) => std::cmp::Ordering::Less,
-            (TagType::Custom(_), _) => std::cmp::Ordering::Greater,
-            (_, TagType::Custom(_)) => std::cmp::Ordering::Less,
-            (TagType::P, _) => std::cmp::Ordering::Less,
-            (_, TagType::P) => std::cmp::Ordering::Greater,
-            (TagType::Span, _) => std::cmp::Ordering::Less,
-            (_, TagType::Span) => std::cmp::Ordering::Greater,
-            (TagType::A, _) => std::cmp::Ordering::Less,
-            (_, _) => std::cmp::Ordering::Equal,
-        }
+        self.as_str()
+            .cmp(other.as_str())
+            .then_with(|| self.is_custom().cmp(&other.is_custom()))
     }
 }